@@ -4,6 +4,7 @@ use super::validate::{
 use crate::content::{create_page, create_post};
 use crate::error::{KrikError, KrikResult, ServerError, ServerErrorKind};
 use crate::generator::SiteGenerator;
+use crate::i18n::status::{format_status_table, translation_status};
 use crate::init::init_site;
 use crate::lint::{generate_html_report, lint_content, lint_content_with_links};
 use crate::logging;
@@ -76,6 +77,7 @@ pub async fn handle_server(server_matches: &ArgMatches) -> KrikResult<()> {
         "Parsing --port value for server",
     )?;
     let no_live_reload = server_matches.get_flag("no-live-reload");
+    let compress = server_matches.get_flag("compress");
 
     info!("Starting development server on port {}", port);
     debug!("Input directory: {}", input_dir.display());
@@ -85,8 +87,16 @@ pub async fn handle_server(server_matches: &ArgMatches) -> KrikResult<()> {
         theme_dir.as_ref().map(|p| p.display())
     );
     debug!("Live reload: {}", !no_live_reload);
-
-    let server = DevServer::new(input_dir, output_dir, theme_dir, port, !no_live_reload);
+    debug!("Precompressed output: {}", compress);
+
+    let server = DevServer::new(
+        input_dir,
+        output_dir,
+        theme_dir,
+        port,
+        !no_live_reload,
+        compress,
+    );
     server
         .start()
         .await
@@ -293,6 +303,42 @@ pub async fn handle_lint(lint_matches: &ArgMatches) -> KrikResult<()> {
     Ok(())
 }
 
+/// Handle the i18n subcommand
+pub fn handle_i18n(i18n_matches: &ArgMatches) -> KrikResult<()> {
+    let _span = logging::get_logger("i18n");
+    let _enter = _span.enter();
+
+    match i18n_matches.subcommand() {
+        Some(("status", status_matches)) => {
+            let input_dir = validate_directory(
+                status_matches
+                    .get_one::<String>("input")
+                    .map(|s| s.as_str())
+                    .unwrap_or("content"),
+                "Validating --input directory for i18n status",
+            )?;
+            let json = status_matches.get_flag("json");
+            debug!("Collecting translation status for {}", input_dir.display());
+
+            let report = translation_status(&input_dir)?;
+            if json {
+                let output = serde_json::to_string_pretty(&report).map_err(|e| {
+                    KrikError::Io(Box::new(crate::error::IoError {
+                        kind: crate::error::IoErrorKind::WriteFailed(e.into()),
+                        path: PathBuf::from("<stdout>"),
+                        context: "Serializing translation status report as JSON".to_string(),
+                    }))
+                })?;
+                println!("{output}");
+            } else {
+                print!("{}", format_status_table(&report));
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
 /// Handle the default generate command
 pub fn handle_generate(matches: &ArgMatches) -> KrikResult<()> {
     let _span = logging::get_logger("generate");
@@ -326,7 +372,7 @@ pub fn handle_generate(matches: &ArgMatches) -> KrikResult<()> {
         theme_dir.as_ref().map(|p| p.display())
     );
 
-    let generator = SiteGenerator::new(&input_dir, &output_dir, theme_dir.as_ref())
+    let mut generator = SiteGenerator::new(&input_dir, &output_dir, theme_dir.as_ref())
         .map_err(|e| match &e {
             KrikError::Theme(theme_err) => {
                 error!("Theme Error: {theme_err}");
@@ -335,6 +381,8 @@ pub fn handle_generate(matches: &ArgMatches) -> KrikResult<()> {
             }
             _ => e,
         })?;
+    generator.strict = matches.get_flag("strict");
+    generator.compress = matches.get_flag("compress");
 
     generator.generate_site().map_err(|e| {
         match &e {
@@ -27,10 +27,18 @@ impl KrikCli {
             .subcommand(Self::build_post_command())
             .subcommand(Self::build_page_command())
             .subcommand(Self::build_lint_command())
+            .subcommand(Self::build_i18n_command())
             .arg(Self::input_arg())
             .arg(Self::output_arg())
             .arg(Self::theme_arg())
             .arg(Self::verbose_arg())
+            .arg(
+                Arg::new("strict")
+                    .long("strict")
+                    .help("Fail the build on broken links between documents")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(Self::compress_arg())
     }
 
     /// Build the server subcommand
@@ -55,6 +63,7 @@ impl KrikCli {
                     .help("Disable live reload functionality")
                     .action(clap::ArgAction::SetTrue),
             )
+            .arg(Self::compress_arg())
     }
 
     /// Build the init subcommand
@@ -157,6 +166,26 @@ impl KrikCli {
             )
     }
 
+    /// Build the i18n subcommand
+    fn build_i18n_command() -> Command {
+        Command::new("i18n")
+            .about("Inspect translations")
+            .subcommand_required(true)
+            .arg_required_else_help(true)
+            .subcommand(
+                Command::new("status")
+                    .about("Report missing, outdated and inconsistent translations per language")
+                    .arg(Self::input_arg())
+                    .arg(Self::verbose_arg())
+                    .arg(
+                        Arg::new("json")
+                            .long("json")
+                            .help("Print the report as JSON (for CI dashboards)")
+                            .action(clap::ArgAction::SetTrue),
+                    ),
+            )
+    }
+
     /// Create the input directory argument
     fn input_arg() -> Arg {
         Self::create_dir_arg(
@@ -195,6 +224,14 @@ impl KrikCli {
             .default_missing_value("info")
     }
 
+    /// Create the compress argument
+    fn compress_arg() -> Arg {
+        Arg::new("compress")
+            .long("compress")
+            .help("Write precompressed .gz and .br copies of HTML, CSS, JS, XML, JSON and SVG")
+            .action(clap::ArgAction::SetTrue)
+    }
+
     /// Helper method to create directory arguments with consistent structure
     fn create_dir_arg(
         name: &'static str,
@@ -227,6 +264,7 @@ impl KrikCli {
             Some(("post", post_matches)) => commands::handle_post(post_matches),
             Some(("page", page_matches)) => commands::handle_page(page_matches),
             Some(("lint", lint_matches)) => commands::handle_lint(lint_matches).await,
+            Some(("i18n", i18n_matches)) => commands::handle_i18n(i18n_matches),
             _ => commands::handle_generate(&self.matches),
         }
     }
@@ -144,6 +144,8 @@ pub enum MarkdownErrorKind {
     InvalidLanguage(String),
     /// Circular reference in content
     CircularReference(PathBuf),
+    /// TeX math that cannot be converted to MathML
+    InvalidMath(String),
 }
 
 /// Template processing errors
@@ -186,6 +188,13 @@ pub enum ThemeErrorKind {
     MissingTemplate(String),
     /// Asset processing failed
     AssetError(String),
+    /// Sass/SCSS stylesheet failed to compile
+    StyleCompileError {
+        file: PathBuf,
+        line: Option<usize>,
+        column: Option<usize>,
+        message: String,
+    },
 }
 
 /// Development server errors
@@ -250,6 +259,8 @@ pub enum GenerationErrorKind {
     FeedError(String),
     /// Sitemap generation failed
     SitemapError(String),
+    /// Internal links to missing documents or headings (with `--strict`)
+    BrokenLinks(Vec<String>),
 }
 
 // Display implementations for user-friendly error messages (inner types)
@@ -443,6 +454,13 @@ impl fmt::Display for MarkdownError {
                 write!(f, "Invalid language code '{}' in {}{}\n  Supported languages: en, it, es, fr, de, pt, ja, zh, ru, ar\n  Context: {}", 
                        lang, file_str, location, self.context)
             }
+            MarkdownErrorKind::InvalidMath(msg) => {
+                write!(
+                    f,
+                    "Invalid math in {}{}\n  Error: {}\n  Context: {}",
+                    file_str, location, msg, self.context
+                )
+            }
             MarkdownErrorKind::CircularReference(ref_path) => {
                 write!(
                     f,
@@ -531,6 +549,26 @@ impl fmt::Display for ThemeError {
                     theme_str, msg, self.context
                 )
             }
+            ThemeErrorKind::StyleCompileError {
+                file,
+                line,
+                column,
+                message,
+            } => {
+                let location = match (line, column) {
+                    (Some(line), Some(col)) => format!(" at line {line}, column {col}"),
+                    (Some(line), None) => format!(" at line {line}"),
+                    _ => String::new(),
+                };
+                write!(
+                    f,
+                    "Stylesheet compilation failed in {}{}\n  Error: {}\n  Context: {}",
+                    file.display(),
+                    location,
+                    message,
+                    self.context
+                )
+            }
         }
     }
 }
@@ -643,6 +681,13 @@ impl fmt::Display for GenerationError {
                     msg, self.context
                 )
             }
+            GenerationErrorKind::BrokenLinks(links) => {
+                write!(f, "Found {} broken internal link(s)", links.len())?;
+                for link in links {
+                    write!(f, "\n  - {link}")?;
+                }
+                write!(f, "\n  Context: {}", self.context)
+            }
         }
     }
 }
@@ -1,11 +1,19 @@
 use crate::error::{IoError, IoErrorKind, KrikError, KrikResult};
+use crate::generator::{image_metadata, minify};
+use crate::site::IgnoreRules;
+use crate::theme::fingerprint::MANIFEST_PATH;
 use crate::theme::Theme;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use tracing::warn;
 use walkdir::WalkDir;
 
+/// Directory next to the content directory whose files are copied verbatim to
+/// the output root
+pub const STATIC_DIR: &str = "static";
+
 /// Return true if the asset should be ignored (not copied)
-fn is_ignored_asset(path: &Path) -> bool {
+pub fn is_ignored_asset(path: &Path) -> bool {
     if let Some(file_name_os) = path.file_name() {
         if let Some(file_name) = file_name_os.to_str() {
             // Ignore dotfiles like .DS_Store and hidden files
@@ -43,11 +51,48 @@ fn is_ignored_asset(path: &Path) -> bool {
     false
 }
 
-/// Copy non-markdown files from source to output directory
-pub fn copy_non_markdown_files(source_dir: &Path, output_dir: &Path) -> KrikResult<()> {
+/// Processing applied to content assets while copying
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CopyOptions {
+    /// Remove EXIF/XMP metadata from JPEG, PNG and WebP images
+    pub strip_metadata: bool,
+    /// Minify CSS and JS files
+    pub minify: bool,
+}
+
+/// Copy an asset, removing image metadata or minifying CSS/JS as requested
+fn copy_asset(path: &Path, dest_path: &Path, options: CopyOptions) -> std::io::Result<()> {
+    let strippable = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            image_metadata::STRIPPABLE_EXTENSIONS.contains(&ext.to_lowercase().as_str())
+        });
+    if options.strip_metadata && strippable {
+        if let Some(stripped) = image_metadata::strip_metadata(&fs::read(path)?) {
+            return fs::write(dest_path, stripped);
+        }
+    }
+    if options.minify && minify::is_minifiable(path) {
+        if let Ok(source) = fs::read_to_string(path) {
+            return fs::write(dest_path, minify::minify(path, &source));
+        }
+    }
+    fs::copy(path, dest_path).map(|_| ())
+}
+
+/// Copy non-markdown files from source to output directory, processing them
+/// according to `options` and skipping paths matched by `ignore`
+pub fn copy_non_markdown_files(
+    source_dir: &Path,
+    output_dir: &Path,
+    options: CopyOptions,
+    ignore: &IgnoreRules,
+) -> KrikResult<()> {
     for entry in WalkDir::new(source_dir)
         .follow_links(true)
         .into_iter()
+        .filter_entry(|e| !ignore.is_ignored(e.path()))
         .filter_map(|e| e.ok())
     {
         let path = entry.path();
@@ -94,7 +139,7 @@ pub fn copy_non_markdown_files(source_dir: &Path, output_dir: &Path) -> KrikResu
         }
 
         // Copy the file
-        fs::copy(path, &dest_path).map_err(|e| {
+        copy_asset(path, &dest_path, options).map_err(|e| {
             KrikError::Io(Box::new(IoError {
                 kind: IoErrorKind::WriteFailed(e),
                 path: dest_path.clone(),
@@ -106,87 +151,56 @@ pub fn copy_non_markdown_files(source_dir: &Path, output_dir: &Path) -> KrikResu
     Ok(())
 }
 
-/// Copy theme assets to the output directory
+/// Write theme assets, their content-hashed copies and the `manifest.json`
+/// mapping to the output directory
 pub fn copy_theme_assets(theme: &Theme, output_dir: &Path) -> KrikResult<()> {
-    let asset_dir = theme.theme_path.join("assets");
-    if asset_dir.exists() {
-        let dest_assets_dir = output_dir.join("assets");
-
-        // Always attempt to create destination directory (idempotent)
-        fs::create_dir_all(&dest_assets_dir).map_err(|e| {
-            KrikError::Io(Box::new(IoError {
-                kind: IoErrorKind::WriteFailed(e),
-                path: dest_assets_dir.clone(),
-                context: "Creating destination assets directory".to_string(),
-            }))
-        })?;
-
-        // Copy all files from theme assets
-        copy_directory_contents(&asset_dir, &dest_assets_dir)?;
+    if theme.assets.assets.is_empty() {
+        return Ok(());
     }
+    let dest_assets_dir = output_dir.join("assets");
 
-    Ok(())
-}
-
-/// Recursively copy directory contents
-fn copy_directory_contents(src: &Path, dest: &Path) -> KrikResult<()> {
-    for entry in WalkDir::new(src)
-        .follow_links(true)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        // Skip non-files using DirEntry to avoid extra stat
-        if !entry.file_type().is_file() {
-            continue;
-        }
-
-        let path = entry.path();
-
-        // Skip ignored assets (dotfiles, editor temp files, backups)
-        if is_ignored_asset(path) {
-            continue;
-        }
-
-        let relative_path = path.strip_prefix(src).map_err(|_| {
-            KrikError::Io(Box::new(IoError {
-                kind: IoErrorKind::InvalidPath,
-                path: path.to_path_buf(),
-                context: format!(
-                    "Computing relative path from {} to {}",
-                    src.display(),
-                    path.display()
-                ),
-            }))
-        })?;
-        let dest_path = dest.join(relative_path);
-
-        // Always attempt to create parent directories
-        if let Some(parent) = dest_path.parent() {
-            fs::create_dir_all(parent).map_err(|e| {
+    for (original, entry) in &theme.assets.assets {
+        for relative in [original, &entry.path] {
+            let dest_path = dest_assets_dir.join(relative);
+            // Always attempt to create parent directories
+            if let Some(parent) = dest_path.parent() {
+                fs::create_dir_all(parent).map_err(|e| {
+                    KrikError::Io(Box::new(IoError {
+                        kind: IoErrorKind::WriteFailed(e),
+                        path: parent.to_path_buf(),
+                        context: "Creating parent directories for theme asset copy".to_string(),
+                    }))
+                })?;
+            }
+            fs::write(&dest_path, &entry.contents).map_err(|e| {
                 KrikError::Io(Box::new(IoError {
                     kind: IoErrorKind::WriteFailed(e),
-                    path: parent.to_path_buf(),
-                    context: "Creating parent directories for theme asset copy".to_string(),
+                    path: dest_path.clone(),
+                    context: format!("Writing theme asset {original}"),
                 }))
             })?;
         }
-
-        // Copy the file
-        fs::copy(path, &dest_path).map_err(|e| {
-            KrikError::Io(Box::new(IoError {
-                kind: IoErrorKind::WriteFailed(e),
-                path: dest_path.clone(),
-                context: format!("Copying theme asset from {}", path.display()),
-            }))
-        })?;
     }
 
-    Ok(())
+    let manifest_path = dest_assets_dir.join(MANIFEST_PATH);
+    let json = serde_json::to_string_pretty(&theme.assets.assets).unwrap_or_default();
+    fs::write(&manifest_path, json).map_err(|e| {
+        KrikError::Io(Box::new(IoError {
+            kind: IoErrorKind::WriteFailed(e),
+            path: manifest_path.clone(),
+            context: "Writing theme asset manifest".to_string(),
+        }))
+    })
 }
 
 /// Copy a single asset file from `source_dir` into the mirrored path under `output_dir`.
 /// Skips markdown files and ignored assets. Returns Ok even if the path is not a regular file.
-pub fn copy_single_asset(source_dir: &Path, output_dir: &Path, file_path: &Path) -> KrikResult<()> {
+pub fn copy_single_asset(
+    source_dir: &Path,
+    output_dir: &Path,
+    file_path: &Path,
+    options: CopyOptions,
+) -> KrikResult<()> {
     if !file_path.exists() || file_path.is_dir() {
         return Ok(());
     }
@@ -223,7 +237,7 @@ pub fn copy_single_asset(source_dir: &Path, output_dir: &Path, file_path: &Path)
             }))
         })?;
     }
-    fs::copy(file_path, &dest_path).map_err(|e| {
+    copy_asset(file_path, &dest_path, options).map_err(|e| {
         KrikError::Io(Box::new(IoError {
             kind: IoErrorKind::WriteFailed(e),
             path: dest_path.clone(),
@@ -240,9 +254,13 @@ pub fn remove_single_asset(
     output_dir: &Path,
     file_path: &Path,
 ) -> KrikResult<()> {
-    let relative_path = match file_path.strip_prefix(source_dir) {
-        Ok(rel) => rel,
-        Err(_) => return Ok(()),
+    let Ok(relative_path) = file_path.strip_prefix(source_dir) else {
+        warn!(
+            "Not removing {}: outside of {}",
+            file_path.display(),
+            source_dir.display()
+        );
+        return Ok(());
     };
     let dest_path = output_dir.join(relative_path);
     if dest_path.exists() && dest_path.is_file() {
@@ -250,3 +268,90 @@ pub fn remove_single_asset(
     }
     Ok(())
 }
+
+/// Canonical form of `path`; a path that no longer exists is resolved through
+/// its parent directory, so removed files compare equal to the paths they had
+pub fn canonical_path(path: &Path) -> PathBuf {
+    if let Ok(canonical) = fs::canonicalize(path) {
+        return canonical;
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => fs::canonicalize(parent)
+            .map(|parent| parent.join(name))
+            .unwrap_or_else(|_| path.to_path_buf()),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// The `static/` directory that belongs to a content directory
+pub fn static_dir(source_dir: &Path) -> PathBuf {
+    let source_dir = fs::canonicalize(source_dir).unwrap_or_else(|_| source_dir.to_path_buf());
+    source_dir
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(STATIC_DIR)
+}
+
+/// Copy every file under `static_dir`, dotfiles such as `.nojekyll` included, to
+/// the same path under `output_dir` without any processing. Only files matching
+/// the `.krikignore` and `exclude` patterns (relative to `static_dir`) are skipped.
+pub fn copy_static_files(
+    static_dir: &Path,
+    output_dir: &Path,
+    ignore: &IgnoreRules,
+) -> KrikResult<()> {
+    if !static_dir.is_dir() {
+        return Ok(());
+    }
+    for entry in WalkDir::new(static_dir)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        if entry.file_type().is_file() {
+            copy_static_file(static_dir, output_dir, entry.path(), ignore)?;
+        }
+    }
+    Ok(())
+}
+
+/// Copy a single file from `static_dir` to the same path under `output_dir`
+pub fn copy_static_file(
+    static_dir: &Path,
+    output_dir: &Path,
+    file_path: &Path,
+    ignore: &IgnoreRules,
+) -> KrikResult<()> {
+    if !file_path.is_file() {
+        return Ok(());
+    }
+    let Ok(relative_path) = file_path.strip_prefix(static_dir) else {
+        warn!(
+            "Not copying {}: outside of {}",
+            file_path.display(),
+            static_dir.display()
+        );
+        return Ok(());
+    };
+    if ignore.is_ignored(relative_path) {
+        return Ok(());
+    }
+    let dest_path = output_dir.join(relative_path);
+    if let Some(parent) = dest_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| {
+            KrikError::Io(Box::new(IoError {
+                kind: IoErrorKind::WriteFailed(e),
+                path: parent.to_path_buf(),
+                context: "Creating parent directories for static file copy".to_string(),
+            }))
+        })?;
+    }
+    fs::copy(file_path, &dest_path).map_err(|e| {
+        KrikError::Io(Box::new(IoError {
+            kind: IoErrorKind::WriteFailed(e),
+            path: dest_path.clone(),
+            context: format!("Copying static file from {}", file_path.display()),
+        }))
+    })?;
+    Ok(())
+}
@@ -1,6 +1,19 @@
-use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
-use regex::Regex;
-use std::collections::HashMap;
+use crate::generator::code_blocks::render_code_blocks;
+use crate::generator::footnotes::render_footnotes;
+use crate::generator::images::render_images;
+use crate::generator::links::{apply_external_link_policy, link_host, render_wiki_links};
+use crate::generator::math::{render_math_event, MathError};
+use crate::generator::typography::localize_events;
+use crate::i18n::I18nManager;
+use crate::site::{
+    ExternalLinksConfig, FootnoteStyle, HeadingConfig, HighlightConfig, ImageConfig, SiteConfig,
+    SlugStrategy, TocConfig,
+};
+use pulldown_cmark::{BlockQuoteKind, CowStr, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use pulldown_cmark_escape::{escape_href, escape_html};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 
 /// Represents a heading in the document structure
 #[derive(Debug, Clone)]
@@ -12,13 +25,19 @@ pub struct Heading {
 }
 
 /// Represents a footnote reference or definition
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct Footnote {
     pub id: String,
     pub reference_text: String,
     pub definition_text: String,
     pub reference_line: usize,
     pub definition_line: usize,
+    /// Display number, in order of first reference
+    pub number: usize,
+    /// Rendered definition, without backlinks
+    pub html: String,
+    /// Element IDs of the references, in document order
+    pub backrefs: Vec<String>,
 }
 
 /// AST parsing result containing headings and footnotes
@@ -27,44 +46,288 @@ pub struct AstParseResult {
     pub headings: Vec<Heading>,
     pub footnotes: HashMap<String, Footnote>,
     pub html_content: String,
+    /// Math expressions that could not be converted to MathML
+    pub math_errors: Vec<MathError>,
 }
 
-/// Parse markdown content using AST to extract headings and footnotes
-pub fn parse_markdown_ast(markdown: &str) -> AstParseResult {
+/// Per-document settings for Markdown rendering
+#[derive(Debug, Clone)]
+pub struct MarkdownOptions {
+    /// Document language, used for locale-aware typography
+    pub language: String,
+    /// Build-time syntax highlighting settings
+    pub highlight: HighlightConfig,
+    /// Heading ID strategy and anchor links
+    pub headings: HeadingConfig,
+    /// Table of contents levels and numbering
+    pub toc: TocConfig,
+    /// Endnotes or sidenotes
+    pub footnotes: FootnoteStyle,
+    /// Attributes of links to other sites
+    pub external_links: ExternalLinksConfig,
+    /// Responsive image settings
+    pub images: ImageConfig,
+    /// Content directory, used to find images referenced from the site root
+    pub content_dir: Option<PathBuf>,
+    /// Path of the Markdown file, used to resolve code block includes and report errors
+    pub source_path: Option<PathBuf>,
+    /// Content or mounted directory holding the Markdown file; code block includes
+    /// must stay inside it
+    pub source_root: Option<PathBuf>,
+    /// Lines preceding the Markdown body in the source file (front matter)
+    pub line_offset: usize,
+}
+
+impl MarkdownOptions {
+    /// Options for a document in `language`, using the site-wide settings
+    pub fn new(site_config: &SiteConfig, language: &str) -> Self {
+        // The site's own hosts, including each language's `base_url`, stay internal
+        let mut external_links = site_config.external_links.clone();
+        let base_urls = std::iter::once(&site_config.base_url).chain(
+            site_config
+                .languages
+                .values()
+                .map(|language| &language.base_url),
+        );
+        external_links
+            .internal_domains
+            .extend(base_urls.filter_map(|url| url.as_deref().and_then(link_host)));
+        Self {
+            language: language.to_string(),
+            highlight: site_config.highlight.clone(),
+            headings: site_config.headings.clone(),
+            toc: site_config.toc,
+            footnotes: site_config.footnotes.style,
+            external_links,
+            images: site_config.images.clone(),
+            content_dir: None,
+            source_path: None,
+            source_root: None,
+            line_offset: 0,
+        }
+    }
+}
+
+impl Default for MarkdownOptions {
+    fn default() -> Self {
+        Self {
+            language: crate::i18n::DEFAULT_LANGUAGE.to_string(),
+            highlight: HighlightConfig::default(),
+            headings: HeadingConfig::default(),
+            toc: TocConfig::default(),
+            footnotes: FootnoteStyle::default(),
+            external_links: ExternalLinksConfig::default(),
+            images: ImageConfig::default(),
+            content_dir: None,
+            source_path: None,
+            source_root: None,
+            line_offset: 0,
+        }
+    }
+}
+
+/// pulldown-cmark extensions enabled for all content
+pub fn markdown_parser_options() -> Options {
     let mut options = Options::empty();
     options.insert(Options::ENABLE_TABLES);
     options.insert(Options::ENABLE_FOOTNOTES);
     options.insert(Options::ENABLE_STRIKETHROUGH);
     options.insert(Options::ENABLE_TASKLISTS);
     options.insert(Options::ENABLE_SMART_PUNCTUATION);
+    options.insert(Options::ENABLE_MATH);
+    options.insert(Options::ENABLE_GFM);
+    options.insert(Options::ENABLE_HEADING_ATTRIBUTES);
+    options
+}
+
+/// Parse markdown content using AST to extract headings and footnotes
+pub fn parse_markdown_ast(markdown: &str) -> AstParseResult {
+    parse_markdown_ast_with_options(markdown, &MarkdownOptions::default())
+}
+
+/// Parse markdown content using AST, applying per-document options
+pub fn parse_markdown_ast_with_options(
+    markdown: &str,
+    options: &MarkdownOptions,
+) -> AstParseResult {
+    let parser = Parser::new_ext(markdown, markdown_parser_options());
 
-    let parser = Parser::new_ext(markdown, options);
-    let mut ast_parser = AstParser::new();
+    // Convert math to MathML while source offsets are still known
+    let mut math_errors = Vec::new();
+    let events: Vec<_> = parser
+        .into_offset_iter()
+        .map(|(event, range)| render_math_event(event, markdown, range.start, &mut math_errors))
+        .collect();
 
     // Collect headings and footnotes
-    let events: Vec<_> = parser.collect();
+    let events = localize_events(events, &options.language);
+    let mut ast_parser = AstParser::new(options.headings.slug, &events);
     for event in &events {
         ast_parser.process_event(event.clone());
     }
 
+    let events = apply_heading_ids(events, &ast_parser.headings, options.headings.anchors);
+    let events = render_code_blocks(events, options);
+    let events = render_images(events, options);
+    let events = render_callouts(events, &options.language);
+    let events = render_wiki_links(events);
+    let events = apply_external_link_policy(events, &options.external_links);
+    let events = render_footnotes(events, options.footnotes, &mut ast_parser.footnotes);
+
     // Generate HTML using default pulldown-cmark HTML generation
     let mut html_output = String::new();
     use pulldown_cmark::html::push_html;
     push_html(&mut html_output, events.into_iter());
 
-    // Post-process HTML to add IDs to headings
-    let processed_html = add_heading_ids_to_html(&html_output, &ast_parser.headings);
-
     AstParseResult {
         headings: ast_parser.headings,
         footnotes: ast_parser.footnotes,
-        html_content: processed_html,
+        html_content: html_output,
+        math_errors,
+    }
+}
+
+/// Localized title of a GitHub-style alert (`> [!NOTE]`, `> [!WARNING]`, ...)
+pub fn callout_title(kind: BlockQuoteKind, language: &str) -> String {
+    let key = match kind {
+        BlockQuoteKind::Note => "callout_note",
+        BlockQuoteKind::Tip => "callout_tip",
+        BlockQuoteKind::Important => "callout_important",
+        BlockQuoteKind::Warning => "callout_warning",
+        BlockQuoteKind::Caution => "callout_caution",
+    };
+    I18nManager::translate_string(key, language)
+}
+
+/// Turn GitHub-style alert blockquotes into callout blocks with a localized title
+pub fn render_callouts<'a>(events: Vec<Event<'a>>, language: &str) -> Vec<Event<'a>> {
+    events
+        .into_iter()
+        .map(|event| match event {
+            Event::Start(Tag::BlockQuote(Some(kind))) => {
+                let name = callout_class(kind);
+                Event::Html(CowStr::from(format!(
+                    "<div class=\"callout callout-{name}\">\n<p class=\"callout-title\">{}</p>\n",
+                    callout_title(kind, language)
+                )))
+            }
+            Event::End(TagEnd::BlockQuote(Some(_))) => Event::Html(CowStr::from("</div>\n")),
+            other => other,
+        })
+        .collect()
+}
+
+/// Rewrite alert markers as bold titles, for renderers (such as pandoc) that would
+/// otherwise print `[!NOTE]` verbatim. The blockquote itself is kept.
+pub fn callouts_to_markdown(markdown: &str, language: &str) -> String {
+    let mut out = String::with_capacity(markdown.len());
+    let mut cursor = 0;
+    for (event, range) in Parser::new_ext(markdown, markdown_parser_options()).into_offset_iter() {
+        let Event::Start(Tag::BlockQuote(Some(kind))) = event else {
+            continue;
+        };
+        let Some(marker) = markdown[range.clone()].find("[!") else {
+            continue;
+        };
+        let start = range.start + marker;
+        let Some(len) = markdown[start..].find(']') else {
+            continue;
+        };
+        if start < cursor {
+            continue;
+        }
+        out.push_str(&markdown[cursor..start]);
+        // Trailing backslash: hard line break, so the body starts on its own line
+        out.push_str(&format!("**{}**\\", callout_title(kind, language)));
+        cursor = start + len + 1;
+    }
+    out.push_str(&markdown[cursor..]);
+    out
+}
+
+fn callout_class(kind: BlockQuoteKind) -> &'static str {
+    match kind {
+        BlockQuoteKind::Note => "note",
+        BlockQuoteKind::Tip => "tip",
+        BlockQuoteKind::Important => "important",
+        BlockQuoteKind::Warning => "warning",
+        BlockQuoteKind::Caution => "caution",
+    }
+}
+
+/// Turn heading text into an ID fragment using `strategy`.
+///
+/// Returns an empty string when no letters or digits remain (e.g. emoji-only headings).
+pub fn slugify(text: &str, strategy: SlugStrategy) -> String {
+    let text = match strategy {
+        SlugStrategy::Unicode => text.to_string(),
+        SlugStrategy::Transliterate => deunicode::deunicode(text),
+    };
+
+    let mut slug = String::with_capacity(text.len());
+    for c in text.to_lowercase().chars() {
+        let keep = match strategy {
+            SlugStrategy::Unicode => c.is_alphanumeric(),
+            SlugStrategy::Transliterate => c.is_ascii_alphanumeric(),
+        };
+        if keep || c == '_' {
+            slug.push(c);
+        } else if (c.is_whitespace() || c == '-') && !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
     }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Set the collected IDs on heading tags, optionally appending a hover anchor link
+fn apply_heading_ids<'a>(
+    events: Vec<Event<'a>>,
+    headings: &[Heading],
+    anchors: bool,
+) -> Vec<Event<'a>> {
+    let mut ids = headings.iter().map(|h| h.id.as_str());
+    let mut current_id = None;
+    let mut out = Vec::with_capacity(events.len());
+
+    for event in events {
+        match event {
+            Event::Start(Tag::Heading {
+                level,
+                classes,
+                attrs,
+                ..
+            }) => {
+                let id = ids.next().unwrap_or_default();
+                current_id = Some(id);
+                out.push(Event::Start(Tag::Heading {
+                    level,
+                    id: Some(CowStr::from(id.to_string())),
+                    classes,
+                    attrs,
+                }));
+            }
+            Event::End(TagEnd::Heading(level)) => {
+                if let (true, Some(id)) = (anchors, current_id.take()) {
+                    let mut anchor = String::from(" <a class=\"heading-anchor\" href=\"#");
+                    let _ = escape_href(&mut anchor, id);
+                    anchor.push_str("\" aria-hidden=\"true\">#</a>");
+                    out.push(Event::Html(CowStr::from(anchor)));
+                }
+                out.push(Event::End(TagEnd::Heading(level)));
+            }
+            other => out.push(other),
+        }
+    }
+    out
 }
 
 /// AST parser that collects headings and footnotes
 struct AstParser {
     headings: Vec<Heading>,
+    slug_strategy: SlugStrategy,
+    /// Explicit `{#id}` attributes, which generated IDs must not collide with
+    reserved_ids: HashSet<String>,
+    current_custom_id: Option<String>,
     footnotes: HashMap<String, Footnote>,
     current_heading_text: String,
     current_heading_level: Option<HeadingLevel>,
@@ -76,9 +339,19 @@ struct AstParser {
 }
 
 impl AstParser {
-    fn new() -> Self {
+    fn new(slug_strategy: SlugStrategy, events: &[Event]) -> Self {
+        let reserved_ids = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Start(Tag::Heading { id: Some(id), .. }) => Some(id.to_string()),
+                _ => None,
+            })
+            .collect();
         Self {
             headings: Vec::new(),
+            slug_strategy,
+            reserved_ids,
+            current_custom_id: None,
             footnotes: HashMap::new(),
             current_heading_text: String::new(),
             current_heading_level: None,
@@ -92,14 +365,18 @@ impl AstParser {
 
     fn process_event(&mut self, event: Event) {
         match event {
-            Event::Start(Tag::Heading { level, .. }) => {
+            Event::Start(Tag::Heading { level, id, .. }) => {
+                self.current_custom_id = id.map(|id| id.to_string());
                 self.current_heading_level = Some(level);
                 self.current_heading_text.clear();
                 self.in_heading = true;
             }
             Event::End(TagEnd::Heading(_)) => {
                 if let Some(heading_level) = self.current_heading_level {
-                    let heading_id = self.generate_heading_id(&self.current_heading_text);
+                    let heading_id = match self.current_custom_id.take() {
+                        Some(id) => id,
+                        None => self.generate_heading_id(&self.current_heading_text),
+                    };
                     let heading = Heading {
                         level: heading_level,
                         text: self.current_heading_text.clone(),
@@ -111,7 +388,7 @@ impl AstParser {
                     self.in_heading = false;
                 }
             }
-            Event::Text(text) => {
+            Event::Text(text) | Event::Code(text) => {
                 if self.in_heading {
                     self.current_heading_text.push_str(&text);
                 } else if self.in_footnote_definition {
@@ -125,14 +402,16 @@ impl AstParser {
             }
             Event::End(TagEnd::FootnoteDefinition) => {
                 if let Some(footnote_id) = self.current_footnote_id.take() {
-                    let footnote = Footnote {
-                        id: footnote_id.clone(),
-                        reference_text: String::new(), // Will be filled by reference processing
-                        definition_text: self.current_footnote_text.clone(),
-                        reference_line: 0, // Will be filled by reference processing
-                        definition_line: self.line_number,
-                    };
-                    self.footnotes.insert(footnote_id, footnote);
+                    // Keep the reference line when the reference came first
+                    let footnote = self
+                        .footnotes
+                        .entry(footnote_id.clone())
+                        .or_insert_with(|| Footnote {
+                            id: footnote_id,
+                            ..Default::default()
+                        });
+                    footnote.definition_text = self.current_footnote_text.clone();
+                    footnote.definition_line = self.line_number;
                     self.in_footnote_definition = false;
                 }
             }
@@ -145,10 +424,8 @@ impl AstParser {
                     // Create footnote entry if it doesn't exist yet
                     let footnote = Footnote {
                         id: footnote_id_str.clone(),
-                        reference_text: String::new(),
-                        definition_text: String::new(),
                         reference_line: self.line_number,
-                        definition_line: 0,
+                        ..Default::default()
                     };
                     self.footnotes.insert(footnote_id_str, footnote);
                 }
@@ -161,26 +438,18 @@ impl AstParser {
     }
 
     fn generate_heading_id(&self, text: &str) -> String {
-        // Generate a URL-friendly ID from heading text
-        let mut id = text
-            .to_lowercase()
-            .chars()
-            .filter(|c| c.is_alphanumeric() || c.is_whitespace())
-            .collect::<String>();
-
-        // Replace spaces with hyphens and remove multiple hyphens
-        id = id.replace(' ', "-");
-        while id.contains("--") {
-            id = id.replace("--", "-");
+        // Headings without letters or digits still need a usable fragment
+        let mut base_id = slugify(text, self.slug_strategy);
+        if base_id.is_empty() {
+            base_id = "section".to_string();
         }
 
-        // Remove leading/trailing hyphens
-        id = id.trim_matches('-').to_string();
-
-        // Ensure uniqueness by adding counter if needed
-        let base_id = id.clone();
+        // Ensure uniqueness within the document by adding a counter if needed
+        let taken =
+            |id: &str| self.reserved_ids.contains(id) || self.headings.iter().any(|h| h.id == id);
+        let mut id = base_id.clone();
         let mut counter = 1;
-        while self.headings.iter().any(|h| h.id == id) {
+        while taken(&id) {
             id = format!("{base_id}-{counter}");
             counter += 1;
         }
@@ -189,71 +458,84 @@ impl AstParser {
     }
 }
 
-/// Add heading IDs to HTML content
-fn add_heading_ids_to_html(html: &str, headings: &[Heading]) -> String {
-    let mut result = html.to_string();
-
-    // Use regex to find and replace heading tags
-    let heading_regex = Regex::new(r"<h([1-6])([^>]*)>([^<]*)</h[1-6]>").unwrap();
-
-    result = heading_regex
-        .replace_all(&result, |caps: &regex::Captures| {
-            let level = &caps[1];
-            let attrs = &caps[2];
-            let text = &caps[3];
-
-            // Find matching heading by text content
-            if let Some(heading) = headings.iter().find(|h| h.text.trim() == text.trim()) {
-                format!(
-                    "<h{}{} id=\"{}\">{}</h{}>",
-                    level, attrs, heading.id, text, level
-                )
-            } else {
-                // If no match found, just return the original
-                caps[0].to_string()
-            }
-        })
-        .to_string();
-
-    result
-}
-
 /// Generate table of contents from parsed headings
 pub fn generate_toc_from_headings(headings: &[Heading], title: Option<&str>) -> String {
+    generate_toc_with_options(headings, title, &TocConfig::default())
+}
+
+/// Generate a nested table of contents limited to the configured levels.
+///
+/// Deeper headings nest under the closest shallower one; a heading that skips
+/// levels is nested one step only. The h1 matching `title` is left out.
+pub fn generate_toc_with_options(
+    headings: &[Heading],
+    title: Option<&str>,
+    config: &TocConfig,
+) -> String {
     let mut toc_html = String::new();
+    // Levels of the currently open lists, with the section counter of each
+    let mut open: Vec<(u8, usize)> = Vec::new();
 
     for heading in headings {
-        // Skip h1 if it matches the title
-        if !(heading.level == HeadingLevel::H1
-            && title.is_some_and(|t| t.trim() == heading.text.trim()))
-        {
-            let indent = "  ".repeat((heading.level as u8).saturating_sub(1) as usize);
-            toc_html.push_str(&format!(
-                "{}<li><a href=\"#{}\">{}</a></li>\n",
-                indent, heading.id, heading.text
-            ));
+        let level = heading.level as u8;
+        let is_title = heading.level == HeadingLevel::H1
+            && title.is_some_and(|t| t.trim() == heading.text.trim());
+        if is_title || level < config.min || level > config.max {
+            continue;
         }
-    }
-
-    if !toc_html.is_empty() {
-        toc_html = format!("<ul class=\"toc\">\n{toc_html}</ul>");
-    }
 
-    toc_html
-}
+        match open.last() {
+            None => {
+                toc_html.push_str("<ul class=\"toc\">\n");
+                open.push((level, 0));
+            }
+            Some(&(top, _)) if level > top => {
+                toc_html.push_str("\n<ul>\n");
+                open.push((level, 0));
+            }
+            Some(_) => {
+                // Close the lists nested deeper than the heading; a skipped level
+                // (H2 > H4, then H3) keeps the heading in the deeper list
+                while open.len() > 1 && open[open.len() - 2].0 >= level {
+                    open.pop();
+                    toc_html.push_str("</li>\n</ul>\n");
+                }
+                toc_html.push_str("</li>\n");
+                if let Some((top, _)) = open.last_mut() {
+                    *top = (*top).min(level);
+                }
+            }
+        }
 
-/// Process footnotes to add proper navigation
-pub fn process_footnotes_ast(footnotes: &HashMap<String, Footnote>) -> HashMap<String, Footnote> {
-    let mut processed_footnotes = footnotes.clone();
+        if let Some((_, counter)) = open.last_mut() {
+            *counter += 1;
+        }
+        let number = if config.numbered {
+            let number = open
+                .iter()
+                .map(|(_, counter)| counter.to_string())
+                .collect::<Vec<_>>()
+                .join(".");
+            format!("<span class=\"toc-number\">{number}</span> ")
+        } else {
+            String::new()
+        };
+        toc_html.push_str("<li><a href=\"#");
+        let _ = escape_href(&mut toc_html, &heading.id);
+        toc_html.push_str("\">");
+        toc_html.push_str(&number);
+        let _ = escape_html(&mut toc_html, &heading.text);
+        toc_html.push_str("</a>");
+    }
 
-    for (id, footnote) in processed_footnotes.iter_mut() {
-        // Ensure proper ID format
-        if !id.starts_with("fn:") {
-            footnote.id = format!("fn:{id}");
+    while open.pop().is_some() {
+        toc_html.push_str("</li>\n</ul>");
+        if !open.is_empty() {
+            toc_html.push('\n');
         }
     }
 
-    processed_footnotes
+    toc_html
 }
 
 // tests moved to tests/ directory
@@ -0,0 +1,139 @@
+//! Page bundles
+//!
+//! A page written as `index.md` in its own directory (`posts/my-trip/index.md`,
+//! or a translation such as `index.it.md`) is a page bundle. It renders to
+//! `index.html` in the same directory and the files next to it are copied
+//! alongside, so relative paths like `![](photo.jpg)` keep working in HTML and
+//! PDF output. Those files are listed in the page's `resources`, which templates
+//! can use to build galleries or download lists.
+
+use super::assets::is_ignored_asset;
+use super::images::image_dimensions;
+use crate::site::IgnoreRules;
+use serde::Serialize;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// File stem of the page that makes a directory a bundle
+pub const BUNDLE_INDEX: &str = "index";
+
+/// Extensions of bundle resources by kind; other files are of kind `file`
+const RESOURCE_KINDS: &[(&str, &[&str])] = &[
+    (
+        "image",
+        &["jpg", "jpeg", "png", "gif", "webp", "avif", "svg"],
+    ),
+    ("video", &["mp4", "webm", "ogv", "mov"]),
+    ("audio", &["mp3", "ogg", "oga", "wav", "m4a", "flac"]),
+];
+
+/// A file co-located with a bundle's page
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Resource {
+    /// Path relative to the page, e.g. `photo.jpg` or `gallery/beach.jpg`
+    pub path: String,
+    /// File name, e.g. `beach.jpg`
+    pub name: String,
+    /// `image`, `video`, `audio` or `file`
+    pub kind: String,
+    /// Display dimensions of images
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+/// Return true for `index.md` pages below the content root, including
+/// translations such as `index.it.md`
+pub fn is_bundle_index(file_path: &str) -> bool {
+    let path = Path::new(file_path);
+    let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+        return false;
+    };
+    let base = stem.split('.').next().unwrap_or(stem);
+    base == BUNDLE_INDEX && path.parent().is_some_and(|p| !p.as_os_str().is_empty())
+}
+
+/// Directory of the bundle a page belongs to, relative to the content root
+pub fn bundle_dir(file_path: &str) -> Option<&Path> {
+    if is_bundle_index(file_path) {
+        Path::new(file_path).parent()
+    } else {
+        None
+    }
+}
+
+/// Resources of the bundle a page belongs to, sorted by path. Markdown files,
+/// ignored files and nested bundles are left out. Pages that are not bundles have no resources.
+pub fn bundle_resources(source_dir: &Path, file_path: &str, ignore: &IgnoreRules) -> Vec<Resource> {
+    let Some(dir) = bundle_dir(file_path) else {
+        return Vec::new();
+    };
+    let root = source_dir.join(dir);
+    let mut resources = Vec::new();
+    let mut walker = WalkDir::new(&root).sort_by_file_name().into_iter();
+    while let Some(entry) = walker.next() {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let path = entry.path();
+        if entry.file_type().is_dir() {
+            if path != root && contains_bundle_index(path) {
+                walker.skip_current_dir();
+            }
+            continue;
+        }
+        if is_ignored_asset(path) || is_markdown(path) || ignore.is_ignored(path) {
+            continue;
+        }
+        let Ok(relative) = path.strip_prefix(&root) else {
+            continue;
+        };
+        let kind = resource_kind(path);
+        let (width, height) = match kind {
+            "image" => image_dimensions(path).unzip(),
+            _ => (None, None),
+        };
+        resources.push(Resource {
+            path: relative.to_string_lossy().replace('\\', "/"),
+            name: entry.file_name().to_string_lossy().into_owned(),
+            kind: kind.to_string(),
+            width,
+            height,
+        });
+    }
+    resources
+}
+
+/// Return true when `path` (relative to the content root) lies inside the
+/// bundle of the page `file_path`
+pub fn is_in_bundle(file_path: &str, path: &Path) -> bool {
+    bundle_dir(file_path).is_some_and(|dir| path.starts_with(dir))
+}
+
+fn contains_bundle_index(dir: &Path) -> bool {
+    std::fs::read_dir(dir).is_ok_and(|entries| {
+        entries.filter_map(|e| e.ok()).any(|entry| {
+            let path = entry.path();
+            is_markdown(&path)
+                && path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .is_some_and(|stem| stem.split('.').next() == Some(BUNDLE_INDEX))
+        })
+    })
+}
+
+fn is_markdown(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "md")
+}
+
+fn resource_kind(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+    RESOURCE_KINDS
+        .iter()
+        .find(|(_, extensions)| extensions.contains(&extension.as_str()))
+        .map_or("file", |(kind, _)| kind)
+}
@@ -0,0 +1,405 @@
+//! Fenced code block rendering: info string attributes, file includes and line markup
+//!
+//! Attributes follow the language token in braces:
+//!
+//! ````markdown
+//! ```rust {linenos=true hl_lines=[3,5-7] title="main.rs"}
+//! ```
+//! ````
+//!
+//! `include="path"` (relative to the Markdown file) replaces the block body with the
+//! file contents, optionally limited with `lines="10-20"`. Included files must lie
+//! inside the content directory (or the mounted directory holding the Markdown file).
+
+use crate::generator::ast_parser::{markdown_parser_options, MarkdownOptions};
+use crate::generator::highlight::highlight_code;
+use crate::site::HighlightFallback;
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, Parser, Tag, TagEnd};
+use std::path::Path;
+use tracing::warn;
+
+/// Attributes parsed from a fence info string
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CodeBlockAttributes {
+    /// Show line numbers
+    pub linenos: bool,
+    /// Number of the first line (defaults to the start of `lines`, or 1)
+    pub linenostart: Option<usize>,
+    /// Inclusive line ranges to emphasize, 1-based
+    pub hl_lines: Vec<(usize, usize)>,
+    /// Caption shown above the block
+    pub title: Option<String>,
+    /// File to include, relative to the Markdown file
+    pub include: Option<String>,
+    /// Inclusive line range of the included file
+    pub lines: Option<(usize, usize)>,
+}
+
+impl CodeBlockAttributes {
+    /// Whether the block needs per-line markup
+    pub fn has_line_markup(&self) -> bool {
+        self.linenos || !self.hl_lines.is_empty()
+    }
+
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn is_highlighted_line(&self, number: usize) -> bool {
+        self.hl_lines
+            .iter()
+            .any(|&(start, end)| number >= start && number <= end)
+    }
+}
+
+/// Split a fence info string into its language token and attributes
+pub fn parse_info_string(info: &str) -> (String, CodeBlockAttributes) {
+    let (head, attrs) = match (info.find('{'), info.rfind('}')) {
+        (Some(open), Some(close)) if open < close => (&info[..open], &info[open + 1..close]),
+        _ => (info, ""),
+    };
+    let lang = head
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .next()
+        .unwrap_or("")
+        .to_string();
+
+    let mut attributes = CodeBlockAttributes::default();
+    for (key, value) in split_attributes(attrs) {
+        match key.as_str() {
+            "linenos" => attributes.linenos = value != "false",
+            "linenostart" => attributes.linenostart = value.parse().ok(),
+            "hl_lines" => attributes.hl_lines = parse_line_ranges(&value),
+            "title" => attributes.title = Some(value),
+            "include" => attributes.include = Some(value),
+            "lines" => attributes.lines = parse_line_ranges(&value).first().copied(),
+            _ => warn!("Unknown code block attribute '{}'", key),
+        }
+    }
+
+    (lang, attributes)
+}
+
+/// Render fenced code blocks: resolve includes, highlight and add line markup.
+///
+/// Blocks without attributes that cannot be highlighted are left as-is when the
+/// fallback is `client`, so the theme's client-side highlighter can handle them.
+pub fn render_code_blocks<'a>(events: Vec<Event<'a>>, options: &MarkdownOptions) -> Vec<Event<'a>> {
+    let mut out = Vec::with_capacity(events.len());
+    let mut block: Option<(String, Vec<Event<'a>>, String)> = None;
+
+    for event in events {
+        if let Some((_, ref mut buffered, ref mut code)) = block {
+            match event {
+                Event::End(TagEnd::CodeBlock) => {
+                    buffered.push(event);
+                    let (info, buffered, code) = block.take().unwrap();
+                    match render_block(&info, code, options) {
+                        Some(html) => out.push(Event::Html(CowStr::from(html))),
+                        None => out.extend(buffered),
+                    }
+                }
+                Event::Text(ref text) => {
+                    code.push_str(text);
+                    buffered.push(event);
+                }
+                _ => buffered.push(event),
+            }
+            continue;
+        }
+
+        match event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(ref info))) => {
+                block = Some((info.to_string(), vec![event], String::new()));
+            }
+            _ => out.push(event),
+        }
+    }
+
+    // An unterminated block cannot happen with pulldown-cmark, but keep the events if it does
+    if let Some((_, buffered, _)) = block {
+        out.extend(buffered);
+    }
+
+    out
+}
+
+/// Render one block, or `None` to keep the original events
+fn render_block(info: &str, mut code: String, options: &MarkdownOptions) -> Option<String> {
+    let (mut lang, attrs) = parse_info_string(info);
+
+    if let Some(include) = attrs.include.as_deref() {
+        let root = options.source_root.as_deref();
+        match read_include(include, attrs.lines, options.source_path.as_deref(), root) {
+            Ok(included) => code = included,
+            Err(message) => warn!("{}", message),
+        }
+        if lang.is_empty() {
+            lang = Path::new(include)
+                .extension()
+                .map(|ext| ext.to_string_lossy().to_string())
+                .unwrap_or_default();
+        }
+    }
+
+    let first_line = attrs
+        .linenostart
+        .or_else(|| attrs.lines.map(|(start, _)| start))
+        .unwrap_or(1);
+    let data_lang = if lang.is_empty() {
+        String::new()
+    } else {
+        format!(" data-lang=\"{}\"", escape_attr(&lang))
+    };
+
+    let pre = match highlight_code(&lang, &code, &options.highlight) {
+        Some(spans) => format!(
+            "<pre class=\"highlight hl-code\"{data_lang}><code>{}</code></pre>\n",
+            line_markup(&spans, &attrs, first_line)
+        ),
+        None if options.highlight.fallback == HighlightFallback::Client => {
+            if attrs.is_empty() {
+                return None;
+            }
+            if attrs.has_line_markup() || lang.is_empty() {
+                format!(
+                    "<pre class=\"highlight\"{data_lang}><code>{}</code></pre>\n",
+                    line_markup(&escape_html(&code), &attrs, first_line)
+                )
+            } else {
+                format!(
+                    "<pre><code class=\"language-{}\">{}</code></pre>\n",
+                    escape_attr(&lang),
+                    escape_html(&code)
+                )
+            }
+        }
+        None => format!(
+            "<pre class=\"highlight\"{data_lang}><code>{}</code></pre>\n",
+            line_markup(&escape_html(&code), &attrs, first_line)
+        ),
+    };
+
+    Some(match attrs.title.as_deref() {
+        Some(title) => format!(
+            "<figure class=\"code-block\">\n\
+             <figcaption class=\"code-title\">{}</figcaption>\n\
+             {}</figure>\n",
+            escape_html(title),
+            pre
+        ),
+        None => pre,
+    })
+}
+
+/// Wrap each line in `<span class="line">`, adding numbers and emphasis as requested.
+/// Spans left open by the highlighter are closed and reopened around line breaks.
+fn line_markup(html: &str, attrs: &CodeBlockAttributes, first_line: usize) -> String {
+    if !attrs.has_line_markup() {
+        return html.to_string();
+    }
+
+    let mut out = String::with_capacity(html.len() * 2);
+    let mut open_tags: Vec<&str> = Vec::new();
+    // The highlighter may close its outer spans after the final newline
+    let mut body = html;
+    while let Some(rest) = body.strip_suffix("</span>") {
+        body = rest;
+    }
+    let trailing_closes = &html[body.len()..];
+    let body = body.strip_suffix('\n').unwrap_or(body);
+    let mut lines: Vec<String> = body.split('\n').map(str::to_string).collect();
+    if let Some(last) = lines.last_mut() {
+        last.push_str(trailing_closes);
+    }
+
+    for (index, line) in lines.iter().enumerate() {
+        let number = first_line + index;
+        let class = if attrs.is_highlighted_line(index + 1) {
+            "line hl-line"
+        } else {
+            "line"
+        };
+        out.push_str(&format!("<span class=\"{class}\">"));
+        if attrs.linenos {
+            out.push_str(&format!(
+                "<span class=\"line-number\" aria-hidden=\"true\">{number}</span>"
+            ));
+        }
+        for tag in &open_tags {
+            out.push_str(tag);
+        }
+        out.push_str(line);
+        track_open_spans(line, &mut open_tags);
+        for _ in &open_tags {
+            out.push_str("</span>");
+        }
+        out.push_str("</span>\n");
+    }
+
+    out
+}
+
+/// Update the stack of `<span ...>` tags still open after `line`
+fn track_open_spans<'a>(line: &'a str, open_tags: &mut Vec<&'a str>) {
+    let mut rest = line;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        let Some(end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[..=end];
+        if tag.starts_with("</span") {
+            open_tags.pop();
+        } else if tag.starts_with("<span") {
+            open_tags.push(tag);
+        }
+        rest = &rest[end + 1..];
+    }
+}
+
+/// Read an included file, optionally limited to an inclusive 1-based line range.
+///
+/// The file must lie inside `root` (the content or mounted directory holding the
+/// Markdown file), or inside the Markdown file's directory when no root is known.
+fn read_include(
+    include: &str,
+    lines: Option<(usize, usize)>,
+    source_path: Option<&Path>,
+    root: Option<&Path>,
+) -> Result<String, String> {
+    let source = source_path.unwrap_or(Path::new("<markdown>")).display();
+    let base = source_path.and_then(Path::parent).unwrap_or(Path::new("."));
+    let path = base.join(include);
+    let cannot_include =
+        |e: std::io::Error| format!("{}: cannot include '{}': {}", source, path.display(), e);
+    let file = std::fs::canonicalize(&path).map_err(cannot_include)?;
+    let root = std::fs::canonicalize(root.unwrap_or(base)).map_err(cannot_include)?;
+    if !file.starts_with(&root) {
+        return Err(format!(
+            "{}: cannot include '{}': outside of '{}'",
+            source,
+            path.display(),
+            root.display()
+        ));
+    }
+    let content = std::fs::read_to_string(&file).map_err(cannot_include)?;
+
+    let Some((start, end)) = lines else {
+        return Ok(content);
+    };
+    let selected: Vec<&str> = content
+        .lines()
+        .skip(start.saturating_sub(1))
+        .take(end.saturating_sub(start.max(1)) + 1)
+        .collect();
+    if selected.is_empty() {
+        return Err(format!(
+            "{}: line range {}-{} is outside '{}'",
+            source,
+            start,
+            end,
+            path.display()
+        ));
+    }
+    Ok(selected.join("\n") + "\n")
+}
+
+/// Split `key=value` pairs separated by whitespace or commas, honoring quotes and brackets
+fn split_attributes(attrs: &str) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    let mut chars = attrs.chars().peekable();
+
+    loop {
+        while chars.peek().is_some_and(|c| c.is_whitespace() || *c == ',') {
+            chars.next();
+        }
+        let key: String =
+            std::iter::from_fn(|| chars.next_if(|c| *c != '=' && !c.is_whitespace())).collect();
+        if key.is_empty() {
+            break;
+        }
+        if chars.next_if_eq(&'=').is_none() {
+            pairs.push((key, "true".to_string()));
+            continue;
+        }
+
+        let value = match chars.peek() {
+            Some('"') | Some('\'') => {
+                let quote = chars.next().unwrap();
+                let value = std::iter::from_fn(|| chars.next_if(|c| *c != quote)).collect();
+                chars.next();
+                value
+            }
+            Some('[') => {
+                chars.next();
+                let value = std::iter::from_fn(|| chars.next_if(|c| *c != ']')).collect();
+                chars.next();
+                value
+            }
+            _ => {
+                std::iter::from_fn(|| chars.next_if(|c| !c.is_whitespace() && *c != ',')).collect()
+            }
+        };
+        pairs.push((key, value));
+    }
+
+    pairs
+}
+
+/// Parse `3,5-7` (or `3 5-7`) into inclusive ranges
+fn parse_line_ranges(value: &str) -> Vec<(usize, usize)> {
+    value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .filter_map(|part| match part.split_once('-') {
+            Some((start, end)) => Some((start.trim().parse().ok()?, end.trim().parse().ok()?)),
+            None => part.trim().parse().ok().map(|n| (n, n)),
+        })
+        .collect()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn escape_attr(text: &str) -> String {
+    escape_html(text).replace('"', "&quot;")
+}
+
+/// Resolve `include` attributes in Markdown source, for renderers (such as pandoc)
+/// that do not go through the HTML pipeline. Other fences are kept verbatim, as
+/// are fences including files outside `root`.
+pub fn expand_includes(markdown: &str, source_path: &Path, root: &Path) -> String {
+    let parser = Parser::new_ext(markdown, markdown_parser_options());
+    let mut out = String::with_capacity(markdown.len());
+    let mut cursor = 0;
+
+    for (event, range) in parser.into_offset_iter() {
+        let Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) = event else {
+            continue;
+        };
+        let (lang, attrs) = parse_info_string(&info);
+        let Some(include) = attrs.include.as_deref() else {
+            continue;
+        };
+        let code = match read_include(include, attrs.lines, Some(source_path), Some(root)) {
+            Ok(code) => code,
+            Err(message) => {
+                warn!("{}", message);
+                continue;
+            }
+        };
+
+        let longest_run = code.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+        let fence = "`".repeat(longest_run.max(2) + 1);
+        out.push_str(&markdown[cursor..range.start]);
+        out.push_str(&format!("{fence}{lang}\n{code}{fence}"));
+        cursor = range.end;
+    }
+
+    out.push_str(&markdown[cursor..]);
+    out
+}
@@ -0,0 +1,175 @@
+//! Precompressed output
+//!
+//! `kk --compress` writes gzip (`.gz`) and brotli (`.br`) siblings next to text
+//! files in the output directory, so web servers configured to look for them
+//! (nginx `gzip_static`/`brotli_static`, S3 with `Content-Encoding` metadata)
+//! can skip compressing on the fly. Files are compressed in parallel, and
+//! siblings that are already newer than their source are left alone. The
+//! siblings written are listed in a manifest kept in `.krik-cache` next to the
+//! content directory (never in the deployable output), so stale ones can be
+//! removed without touching `.gz`/`.br` files that belong to the site itself.
+
+use crate::error::{IoError, IoErrorKind, KrikError, KrikResult};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Extensions of files that get precompressed siblings
+pub const COMPRESSIBLE_EXTENSIONS: &[&str] = &["html", "css", "js", "xml", "json", "svg"];
+
+/// Files smaller than this many bytes are not worth compressing
+pub const MIN_SIZE: u64 = 1024;
+
+/// Content encodings in order of preference, with the extension of their siblings
+pub const ENCODINGS: &[(&str, &str)] = &[("br", "br"), ("gzip", "gz")];
+
+/// Directory of the sibling manifests, relative to the parent of the content directory
+pub const MANIFEST_DIR: &str = ".krik-cache/precompressed";
+
+/// Brotli quality (0-11) and window size used for siblings
+const BROTLI_QUALITY: u32 = 11;
+const BROTLI_WINDOW: u32 = 22;
+
+/// Return true for files that get precompressed siblings
+pub fn is_compressible(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| COMPRESSIBLE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// Path of a file's sibling for the given extension: `index.html` → `index.html.gz`
+pub fn sibling_path(path: &Path, extension: &str) -> PathBuf {
+    let mut sibling = path.as_os_str().to_owned();
+    sibling.push(".");
+    sibling.push(extension);
+    PathBuf::from(sibling)
+}
+
+/// Return true when `sibling` exists and is at least as new as `source`
+pub fn is_fresh(source: &Path, sibling: &Path) -> bool {
+    let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+    match (modified(source), modified(sibling)) {
+        (Some(source), Some(sibling)) => sibling >= source,
+        _ => false,
+    }
+}
+
+/// Manifest listing the siblings written into `output_dir` for the site in
+/// `source_dir`; each output directory gets its own
+pub fn manifest_path(source_dir: &Path, output_dir: &Path) -> PathBuf {
+    let output_dir = fs::canonicalize(output_dir).unwrap_or_else(|_| output_dir.to_path_buf());
+    let hash: String = Sha256::digest(output_dir.to_string_lossy().as_bytes())
+        .iter()
+        .take(8)
+        .map(|b| format!("{b:02x}"))
+        .collect();
+    source_dir
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(MANIFEST_DIR)
+        .join(hash)
+}
+
+/// Write `.gz` and `.br` siblings for every compressible file of at least
+/// `MIN_SIZE` bytes under `output_dir`, and remove siblings written by earlier
+/// runs whose source is gone or has become too small. Other files are never
+/// removed. Returns the number of files compressed.
+pub fn compress_output(source_dir: &Path, output_dir: &Path) -> KrikResult<usize> {
+    let manifest_path = manifest_path(source_dir, output_dir);
+    let previous = read_manifest(&manifest_path);
+    let mut siblings = BTreeSet::new();
+    let mut sources = Vec::new();
+    for entry in WalkDir::new(output_dir).into_iter().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if !entry.file_type().is_file() || !is_compressible(path) {
+            continue;
+        }
+        if entry.metadata().map(|m| m.len()).unwrap_or(0) < MIN_SIZE {
+            continue;
+        }
+        let mut stale = false;
+        for (_, ext) in ENCODINGS {
+            let sibling = sibling_path(path, ext);
+            stale |= !is_fresh(path, &sibling);
+            if let Ok(relative) = sibling.strip_prefix(output_dir) {
+                siblings.insert(relative.to_string_lossy().replace('\\', "/"));
+            }
+        }
+        if stale {
+            sources.push(path.to_path_buf());
+        }
+    }
+
+    sources
+        .par_iter()
+        .map(|path| compress_file(path))
+        .collect::<KrikResult<Vec<()>>>()?;
+
+    for orphan in previous.difference(&siblings) {
+        remove_file(&output_dir.join(orphan))?;
+    }
+    let manifest: String = siblings.iter().map(|s| format!("{s}\n")).collect();
+    if let Some(parent) = manifest_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| io_error(IoErrorKind::WriteFailed(e), parent))?;
+    }
+    write_file(&manifest_path, manifest.as_bytes())?;
+    Ok(sources.len())
+}
+
+/// Sibling paths, relative to the output directory, listed in a manifest
+fn read_manifest(path: &Path) -> BTreeSet<String> {
+    fs::read_to_string(path)
+        .map(|contents| {
+            contents
+                .lines()
+                .filter(|line| !line.is_empty() && !line.contains(".."))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Write the `.gz` and `.br` siblings of one file
+fn compress_file(path: &Path) -> KrikResult<()> {
+    let bytes = fs::read(path).map_err(|e| io_error(IoErrorKind::ReadFailed(e), path))?;
+
+    let mut gzip = GzEncoder::new(Vec::new(), Compression::best());
+    let gzip = gzip
+        .write_all(&bytes)
+        .and_then(|_| gzip.finish())
+        .map_err(|e| io_error(IoErrorKind::WriteFailed(e), path))?;
+    write_file(&sibling_path(path, "gz"), &gzip)?;
+
+    let mut brotli = brotli::CompressorWriter::new(Vec::new(), 4096, BROTLI_QUALITY, BROTLI_WINDOW);
+    brotli
+        .write_all(&bytes)
+        .map_err(|e| io_error(IoErrorKind::WriteFailed(e), path))?;
+    write_file(&sibling_path(path, "br"), &brotli.into_inner())
+}
+
+fn write_file(path: &Path, contents: &[u8]) -> KrikResult<()> {
+    fs::write(path, contents).map_err(|e| io_error(IoErrorKind::WriteFailed(e), path))
+}
+
+fn remove_file(path: &Path) -> KrikResult<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(io_error(IoErrorKind::WriteFailed(e), path))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn io_error(kind: IoErrorKind, path: &Path) -> KrikError {
+    KrikError::Io(Box::new(IoError {
+        kind,
+        path: path.to_path_buf(),
+        context: "Precompressing output".to_string(),
+    }))
+}
@@ -1,5 +1,7 @@
 use crate::error::{KrikError, KrikResult, ThemeError, ThemeErrorKind};
-use crate::i18n::I18nManager;
+use crate::generator::assets::CopyOptions;
+use crate::generator::minify::AssetProcessing;
+use crate::generator::templates::paths::get_translation_key;
 use crate::parser::Document;
 use crate::site::SiteConfig;
 use crate::theme::Theme;
@@ -12,8 +14,14 @@ use tracing::{debug, error, info, warn};
 pub enum ChangeType {
     ThemeRelated,
     SiteConfig,
-    Markdown { relative_path: String },
+    Markdown {
+        relative_path: String,
+    },
     Asset,
+    /// The content directory's `.krikignore` file
+    IgnoreFile,
+    /// A file in the `static/` directory next to the content directory
+    Static,
     Unrelated,
 }
 
@@ -56,6 +64,12 @@ pub struct SiteGenerator {
     pub documents: Vec<Document>,
     /// Incremental cache: map from relative file path to Document
     pub document_cache: HashMap<String, Document>,
+    /// Fail the build on problems that are otherwise warnings, such as broken internal links
+    pub strict: bool,
+    /// Write gzip and brotli siblings of text files after each full build
+    pub compress: bool,
+    /// Development build for `kk server`; changed through `set_dev`
+    dev: bool,
 }
 
 impl SiteGenerator {
@@ -112,12 +126,22 @@ impl SiteGenerator {
                 .autoescape_html(false)
                 .enable_reload(false)
                 .build()
-                .map_err(|_| {
-                    KrikError::Theme(Box::new(ThemeError {
+                .map_err(|e| match e {
+                    // Keep stylesheet and bundle errors, which point at the offending file
+                    KrikError::Theme(theme_error)
+                        if matches!(
+                            theme_error.kind,
+                            ThemeErrorKind::StyleCompileError { .. }
+                                | ThemeErrorKind::AssetError(_)
+                        ) =>
+                    {
+                        KrikError::Theme(theme_error)
+                    }
+                    _ => KrikError::Theme(Box::new(ThemeError {
                         kind: ThemeErrorKind::NotFound,
                         theme_path: path.clone(),
                         context: format!("Loading custom theme from {}", path.display()),
-                    }))
+                    })),
                 })?
         } else {
             let default_path = PathBuf::from("themes/default");
@@ -138,9 +162,11 @@ impl SiteGenerator {
                         author: None,
                         description: None,
                         templates: HashMap::new(),
+                        bundles: Vec::new(),
                     },
                     templates: tera::Tera::new("themes/default/templates/**/*").unwrap_or_default(),
                     theme_path: default_path,
+                    assets: Default::default(),
                 })
         };
 
@@ -153,6 +179,9 @@ impl SiteGenerator {
             SiteConfig::default()
         });
 
+        let mut theme = theme;
+        theme.refresh_assets(&AssetProcessing::new(&site_config.assets, false))?;
+
         Ok(Self {
             source_dir,
             output_dir,
@@ -160,9 +189,25 @@ impl SiteGenerator {
             site_config,
             documents: Vec::new(),
             document_cache: HashMap::new(),
+            strict: false,
+            compress: false,
+            dev: false,
         })
     }
 
+    /// Switch between production and development (`kk server`) builds. Development
+    /// builds skip CSS/JS and HTML minification and write source maps for theme bundles.
+    pub fn set_dev(&mut self, dev: bool) -> KrikResult<()> {
+        self.dev = dev;
+        self.site_config.html.minify &= !dev;
+        self.theme.refresh_assets(&self.asset_processing())
+    }
+
+    /// How stylesheets and scripts are processed in this build
+    fn asset_processing(&self) -> AssetProcessing {
+        AssetProcessing::new(&self.site_config.assets, self.dev)
+    }
+
     /// Scan files in the source directory and parse markdown documents
     pub fn scan_files(&mut self) -> KrikResult<()> {
         info!(
@@ -172,12 +217,12 @@ impl SiteGenerator {
         // Full scan rebuilds the cache
         self.document_cache.clear();
         self.documents.clear();
-        let result = super::markdown::scan_files(&self.source_dir, &mut self.documents).map_err(
-            |e| match e {
-                KrikError::Generation(gen_err) => KrikError::Generation(gen_err),
-                other => other,
-            },
-        );
+        let result =
+            super::markdown::scan_files(&self.source_dir, &self.site_config, &mut self.documents)
+                .map_err(|e| match e {
+                    KrikError::Generation(gen_err) => KrikError::Generation(gen_err),
+                    other => other,
+                });
 
         match &result {
             Ok(_) => {
@@ -201,9 +246,10 @@ impl SiteGenerator {
     /// 2. Generate HTML pages from documents
     /// 3. Generate index page with post listings
     /// 4. Generate Atom feed
-    /// 5. Generate XML sitemap
+    /// 5. Generate XML sitemap and JSON link graph
     /// 6. Generate robots.txt
     /// 7. Generate PDFs (if pandoc and typst are available)
+    /// 8. Write gzip and brotli siblings of text files (with `compress`)
     pub fn generate_site(&self) -> KrikResult<()> {
         use super::pipeline::{EmitPhase, RenderPhase, ScanPhase, TransformPhase};
 
@@ -222,16 +268,25 @@ impl SiteGenerator {
 
         // Scan
         info!("Scanning source files");
-        let documents = scan.scan(&self.source_dir)?;
+        let documents = scan.scan(&self.source_dir, &self.site_config)?;
         debug!("Found {} documents to process", documents.len());
 
         // Transform
         info!("Transforming documents");
-        let documents = transform.transform(documents, &self.source_dir);
+        let documents = transform.transform(documents, &self.source_dir, &self.site_config);
+        let documents = transform.resolve_links(documents, self.strict)?;
 
         // Assets
         info!("Copying assets");
-        emit.copy_assets(&self.source_dir, &self.theme, &self.output_dir)?;
+        emit.copy_assets(
+            &self.source_dir,
+            &self.theme,
+            &self.site_config,
+            &self.asset_processing(),
+            &self.output_dir,
+        )?;
+        emit.emit_images(&self.source_dir, &self.site_config, &self.output_dir)?;
+        emit.emit_highlight_css(&self.site_config, &self.output_dir)?;
 
         // Render
         info!("Rendering pages");
@@ -247,6 +302,7 @@ impl SiteGenerator {
         info!("Generating ancillary files");
         emit.emit_feed(&documents, &self.site_config, &self.output_dir)?;
         emit.emit_sitemap(&documents, &self.site_config, &self.output_dir)?;
+        emit.emit_link_graph(&documents, &self.output_dir)?;
         emit.emit_robots(&self.site_config, &self.output_dir)?;
 
         // Generate PDFs if tools are available
@@ -277,6 +333,12 @@ impl SiteGenerator {
             debug!("PDF generation skipped: pandoc and/or typst not available in PATH");
         }
 
+        if self.compress {
+            info!("Precompressing output");
+            let compressed = super::compress::compress_output(&self.source_dir, &self.output_dir)?;
+            debug!("Wrote gzip and brotli siblings for {} files", compressed);
+        }
+
         info!("Site generation completed successfully");
         Ok(())
     }
@@ -285,8 +347,13 @@ impl SiteGenerator {
     ///
     /// Behavior:
     /// - If a markdown file changed: re-scan just that file, update/emit its HTML, and re-render index/feed/sitemap.
-    /// - If a non-markdown content asset changed: copy that single asset into the output.
+    /// - If a non-markdown content asset changed: copy that single asset into the output and
+    ///   re-render the page bundles that list it as a resource.
     /// - If a content file was removed: remove the mirrored output file and refresh index/feed/sitemap.
+    /// - If a file in `static/` changed: copy or remove that single file.
+    /// - If `.krikignore` changed: reload the ignore patterns and regenerate everything.
+    /// - Files in mounted directories are handled like content files at their site path.
+    /// - Changes to ignored paths are skipped.
     /// - If a theme file changed (templates/assets), fall back to full regeneration as templates affect many pages.
     pub fn generate_incremental_for_path<P: AsRef<Path>>(
         &mut self,
@@ -294,18 +361,62 @@ impl SiteGenerator {
         is_removed: bool,
     ) -> KrikResult<()> {
         let changed_path = changed_path.as_ref();
-        let change_type =
-            analyze_change_type(changed_path, &self.theme.theme_path, &self.source_dir)?;
+        if self.site_config.is_ignored(changed_path) {
+            debug!("skipping ignored path {}", changed_path.display());
+            return Ok(());
+        }
+        let change_type = match self.site_config.mount_containing(changed_path) {
+            Some(mount) => analyze_mount_change(changed_path, mount),
+            None => analyze_change_type(changed_path, &self.theme.theme_path, &self.source_dir)?,
+        };
 
         match change_type {
-            ChangeType::ThemeRelated | ChangeType::SiteConfig => {
-                debug!("Theme or site config change detected, triggering full regeneration");
+            ChangeType::ThemeRelated => {
+                debug!("Theme change detected, triggering full regeneration");
+                let processing = self.asset_processing();
+                if is_html_template(changed_path) {
+                    self.theme.try_reload_templates();
+                } else if !is_removed && crate::theme::sass::is_sass(changed_path) {
+                    self.theme
+                        .recompile_stylesheets(changed_path, &processing)?;
+                } else {
+                    self.theme.refresh_assets(&processing)?;
+                }
+                self.generate_site()
+            }
+            ChangeType::SiteConfig => {
+                debug!("Site config change detected, triggering full regeneration");
                 self.generate_site()
             }
             ChangeType::Markdown { relative_path } => {
                 self.handle_markdown_change(&relative_path, changed_path, is_removed)
             }
             ChangeType::Asset => self.handle_asset_change(changed_path, is_removed),
+            ChangeType::IgnoreFile => {
+                debug!("Ignore file change detected, triggering full regeneration");
+                self.site_config.ignore =
+                    crate::site::IgnoreRules::load(&self.source_dir, &self.site_config.exclude)?;
+                for mount in &mut self.site_config.mounts {
+                    mount.ignore = crate::site::IgnoreRules::load(&mount.dir, &[])?;
+                }
+                self.scan_files()?;
+                self.generate_site()
+            }
+            ChangeType::Static => {
+                // `static_dir` is canonical, so compare it with the canonical path
+                let static_dir = super::assets::static_dir(&self.source_dir);
+                let changed_path = super::assets::canonical_path(changed_path);
+                if is_removed {
+                    super::assets::remove_single_asset(&static_dir, &self.output_dir, &changed_path)
+                } else {
+                    super::assets::copy_static_file(
+                        &static_dir,
+                        &self.output_dir,
+                        &changed_path,
+                        &self.site_config.ignore,
+                    )
+                }
+            }
             ChangeType::Unrelated => {
                 debug!("Change not related to content or theme, triggering full regeneration");
                 self.generate_site()
@@ -314,76 +425,60 @@ impl SiteGenerator {
     }
 
     /// Find all documents that are language variants of the given document.
-    /// Language variants share the same base name but have different language extensions.
+    /// Language variants share the same translation key: the `translation_key` front
+    /// matter field when set, otherwise the base name within the same directory.
     /// For example: "welcome.md", "welcome.it.md", "welcome.fr.md" are all variants.
-    fn find_language_variants(&self, target_path: &str) -> Vec<String> {
-        let mut variants = Vec::new();
-
-        // Extract base name by removing language and extension
-        // Example: "pages/welcome.it.md" -> "pages/welcome"
-        let path_buf = std::path::PathBuf::from(target_path);
-        let parent = path_buf
-            .parent()
-            .map(|p| p.to_string_lossy().to_string())
-            .unwrap_or_default();
-
-        if let Some(_filename) = path_buf.file_name().and_then(|n| n.to_str()) {
-            let base_name = if let Some(stem) = path_buf.file_stem().and_then(|s| s.to_str()) {
-                // Check if stem contains a language code (e.g., "welcome.it")
-                if let Some(dot_pos) = stem.rfind('.') {
-                    let potential_lang = &stem[dot_pos + 1..];
-                    // Check if it's a known language code
-                    if I18nManager::is_supported_language(&potential_lang)
-                    {
-                        &stem[..dot_pos] // Remove language part
-                    } else {
-                        stem // No language code found
-                    }
-                } else {
-                    stem // No dots in stem
-                }
-            } else {
-                return variants;
-            };
-
-            // Find all documents with the same base name
-            for doc in &self.documents {
-                let doc_path_buf = std::path::PathBuf::from(&doc.file_path);
-                let doc_parent = doc_path_buf
-                    .parent()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .unwrap_or_default();
-
-                // Must be in same directory
-                if doc_parent != parent {
-                    continue;
-                }
-
-                if let Some(_doc_filename) = doc_path_buf.file_name().and_then(|n| n.to_str()) {
-                    if let Some(doc_stem) = doc_path_buf.file_stem().and_then(|s| s.to_str()) {
-                        let doc_base_name = if let Some(dot_pos) = doc_stem.rfind('.') {
-                            let potential_lang = &doc_stem[dot_pos + 1..];
-                            if I18nManager::is_supported_language(&potential_lang) {
-                                &doc_stem[..dot_pos]
-                            } else {
-                                doc_stem
-                            }
-                        } else {
-                            doc_stem
-                        };
-
-                        // If base names match, this is a language variant
-                        if doc_base_name == base_name {
-                            variants.push(doc.file_path.clone());
-                        }
-                    }
-                }
-            }
+    ///
+    /// Keys are looked up in both the previous and the updated document set so that
+    /// pages which just lost their link to the changed document are re-rendered too.
+    fn find_language_variants(&self, target_path: &str, documents: &[Document]) -> Vec<String> {
+        let keys: Vec<String> = self
+            .documents
+            .iter()
+            .chain(documents.iter())
+            .filter(|doc| doc.file_path == target_path)
+            .map(get_translation_key)
+            .collect();
+
+        if keys.is_empty() {
+            return Vec::new();
         }
 
+        let mut variants: Vec<String> = self
+            .documents
+            .iter()
+            .chain(documents.iter())
+            .filter(|doc| keys.contains(&get_translation_key(doc)))
+            .map(|doc| doc.file_path.clone())
+            .collect();
+        variants.sort();
+        variants.dedup();
         variants
     }
 
+    /// Find the documents whose links or backlinks involve the given document, in the
+    /// previous or the updated document set: the pages linking to it and the pages it
+    /// links to
+    fn find_linked_documents(&self, target_path: &str, documents: &[Document]) -> Vec<String> {
+        let mut linked: Vec<String> = self
+            .documents
+            .iter()
+            .chain(documents.iter())
+            .flat_map(|doc| {
+                if doc.file_path == target_path {
+                    doc.links.clone()
+                } else if doc.links.iter().any(|link| link == target_path) {
+                    vec![doc.file_path.clone()]
+                } else {
+                    Vec::new()
+                }
+            })
+            .collect();
+        linked.sort();
+        linked.dedup();
+        linked
+    }
+
     /// Handle markdown file changes by updating the document cache and re-rendering affected pages
     fn handle_markdown_change(
         &mut self,
@@ -399,7 +494,18 @@ impl SiteGenerator {
 
         emit.ensure_output_dir(&self.output_dir)?;
 
-        let mut documents = self.documents.clone();
+        // Start from the documents as parsed, so that links resolved against the
+        // previous document set are resolved again
+        let mut documents: Vec<Document> = self
+            .documents
+            .iter()
+            .map(|doc| {
+                self.document_cache
+                    .get(&doc.file_path)
+                    .cloned()
+                    .unwrap_or_else(|| doc.clone())
+            })
+            .collect();
 
         if is_removed {
             self.handle_markdown_removal(relative_path, &mut documents)
@@ -408,17 +514,37 @@ impl SiteGenerator {
         };
 
         // Transform documents for correct dates before rendering
-        let documents = transform.transform(documents, &self.source_dir);
+        let documents = transform.transform(documents, &self.source_dir, &self.site_config);
+        let documents = transform.resolve_links(documents, false)?;
 
+        let mut rendered = Vec::new();
         if !is_removed {
             self.render_language_variants(relative_path, &documents)?;
+            rendered = self.find_language_variants(relative_path, &documents);
+        }
+        for linked_path in self.find_linked_documents(relative_path, &documents) {
+            if rendered.contains(&linked_path) {
+                continue;
+            }
+            if let Some(doc) = documents.iter().find(|d| d.file_path == linked_path) {
+                debug!("re-rendering linked page {}", linked_path);
+                super::templates::generate_page(
+                    doc,
+                    &documents,
+                    &self.theme,
+                    &self.site_config,
+                    &self.output_dir,
+                )?;
+            }
         }
 
         // Persist updated working set back into generator state
         self.documents = documents;
 
         // Update global artifacts that depend on full document set
-        debug!("updating global artifacts (index/feed/sitemap/robots) after single-page change");
+        debug!(
+            "updating global artifacts (index/feed/sitemap/graph/robots) after single-page change"
+        );
         render.render_index(
             &self.documents,
             &self.theme,
@@ -427,41 +553,106 @@ impl SiteGenerator {
         )?;
         emit.emit_feed(&self.documents, &self.site_config, &self.output_dir)?;
         emit.emit_sitemap(&self.documents, &self.site_config, &self.output_dir)?;
+        emit.emit_link_graph(&self.documents, &self.output_dir)?;
         emit.emit_robots(&self.site_config, &self.output_dir)?;
 
         Ok(())
     }
 
-    /// Handle asset file changes by copying or removing the file
-    fn handle_asset_change(&self, changed_path: &Path, is_removed: bool) -> KrikResult<()> {
+    /// Handle asset file changes by copying or removing the file, then refresh the
+    /// resources of the page bundles that contain it
+    fn handle_asset_change(&mut self, changed_path: &Path, is_removed: bool) -> KrikResult<()> {
         use super::pipeline::EmitPhase;
 
         let emit = EmitPhase;
         emit.ensure_output_dir(&self.output_dir)?;
 
+        // Mounted files are mirrored under the mount's target
+        let mount = self.site_config.mount_containing(changed_path);
+        let (source_dir, output_dir, images) = match mount {
+            Some(mount) => (
+                mount.dir.clone(),
+                self.output_dir.join(mount.target_path()),
+                super::images::mount_config(&self.source_dir, &self.site_config.images),
+            ),
+            None => (
+                self.source_dir.clone(),
+                self.output_dir.clone(),
+                self.site_config.images.clone(),
+            ),
+        };
+
         if is_removed {
             debug!("removing single asset {}", changed_path.display());
-            super::assets::remove_single_asset(&self.source_dir, &self.output_dir, changed_path)
-                .map_err(|e| {
+            super::assets::remove_single_asset(&source_dir, &output_dir, changed_path).map_err(
+                |e| {
                     create_asset_error(
                         "Removing single changed asset",
-                        &self.source_dir,
-                        &self.output_dir,
+                        &source_dir,
+                        &output_dir,
                         Box::new(e),
                     )
-                })
+                },
+            )?;
         } else {
             debug!("copying single asset {}", changed_path.display());
-            super::assets::copy_single_asset(&self.source_dir, &self.output_dir, changed_path)
-                .map_err(|e| {
-                    create_asset_error(
-                        "Copying single changed asset",
-                        &self.source_dir,
-                        &self.output_dir,
-                        Box::new(e),
-                    )
-                })
+            super::assets::copy_single_asset(
+                &source_dir,
+                &output_dir,
+                changed_path,
+                CopyOptions {
+                    strip_metadata: self.site_config.images.strip_metadata,
+                    minify: self.asset_processing().minify,
+                },
+            )
+            .map_err(|e| {
+                create_asset_error(
+                    "Copying single changed asset",
+                    &source_dir,
+                    &output_dir,
+                    Box::new(e),
+                )
+            })?;
+            super::images::process_image(&source_dir, &output_dir, changed_path, &images)?;
+        }
+        self.refresh_bundle_resources(changed_path)
+    }
+
+    /// Re-list the resources of the page bundles containing a changed asset and
+    /// re-render their pages
+    fn refresh_bundle_resources(&mut self, changed_path: &Path) -> KrikResult<()> {
+        let source_dir =
+            std::fs::canonicalize(&self.source_dir).unwrap_or_else(|_| self.source_dir.clone());
+        let Some(relative) = self
+            .site_config
+            .site_path(&source_dir, changed_path)
+            .or_else(|| self.site_config.site_path(&self.source_dir, changed_path))
+        else {
+            return Ok(());
+        };
+
+        let mut bundled = Vec::new();
+        for (index, doc) in self.documents.iter_mut().enumerate() {
+            if super::bundles::is_in_bundle(&doc.file_path, &relative) {
+                let root = self.site_config.content_root(&self.source_dir, doc);
+                doc.resources = super::bundles::bundle_resources(root.dir, root.path, root.ignore);
+                bundled.push(index);
+            }
+        }
+        for index in bundled {
+            debug!(
+                "re-rendering bundle page {}",
+                self.documents[index].file_path
+            );
+            super::templates::generate_page(
+                &self.documents[index],
+                &self.documents,
+                &self.theme,
+                &self.site_config,
+                &self.output_dir,
+            )?;
         }
+        Ok(())
     }
 
     /// Handle markdown file removal by cleaning up cache and output files
@@ -496,7 +687,8 @@ impl SiteGenerator {
         changed_path: &Path,
         documents: &mut Vec<Document>,
     ) -> KrikResult<()> {
-        match super::markdown::parse_single_file(&self.source_dir, changed_path) {
+        match super::markdown::parse_single_file(&self.source_dir, &self.site_config, changed_path)
+        {
             Ok(doc) => {
                 let prev_pdf = self
                     .document_cache
@@ -524,7 +716,7 @@ impl SiteGenerator {
                     e
                 );
                 documents.clear();
-                super::markdown::scan_files(&self.source_dir, documents)?;
+                super::markdown::scan_files(&self.source_dir, &self.site_config, documents)?;
                 // rebuild cache from full scan
                 self.document_cache.clear();
                 for d in documents {
@@ -553,7 +745,10 @@ impl SiteGenerator {
                 if super::pdf::PdfGenerator::is_available() {
                     match super::pdf::PdfGenerator::new() {
                         Ok(pdf_gen) => {
-                            let input_path = self.source_dir.join(&current_doc.file_path);
+                            let input_path = self
+                                .site_config
+                                .content_root(&self.source_dir, current_doc)
+                                .file();
                             let _ = pdf_gen.generate_pdf_from_file(
                                 &input_path,
                                 &pdf_output_path,
@@ -586,7 +781,7 @@ impl SiteGenerator {
         relative_path: &str,
         documents: &[Document],
     ) -> KrikResult<()> {
-        let variant_paths = self.find_language_variants(relative_path);
+        let variant_paths = self.find_language_variants(relative_path, documents);
         debug!(
             "found {} language variants for {}: {:?}",
             variant_paths.len(),
@@ -656,8 +851,7 @@ pub fn analyze_change_type(
     }
 
     // Changes within content directory
-    let canonical_changed =
-        std::fs::canonicalize(changed_path).unwrap_or_else(|_| changed_path.to_path_buf());
+    let canonical_changed = super::assets::canonical_path(changed_path);
     let canonical_source =
         std::fs::canonicalize(source_dir).unwrap_or_else(|_| source_dir.to_path_buf());
 
@@ -668,6 +862,11 @@ pub fn analyze_change_type(
         if is_site_toml {
             return Ok(ChangeType::SiteConfig);
         }
+        if canonical_changed.parent() == Some(canonical_source.as_path())
+            && changed_path.file_name() == Some(OsStr::new(crate::site::ignore::IGNORE_FILE))
+        {
+            return Ok(ChangeType::IgnoreFile);
+        }
 
         return if is_markdown {
             let relative_path = canonical_changed
@@ -694,9 +893,31 @@ pub fn analyze_change_type(
         };
     }
 
+    if canonical_changed.starts_with(super::assets::static_dir(source_dir)) {
+        return Ok(ChangeType::Static);
+    }
+
     Ok(ChangeType::Unrelated)
 }
 
+/// Analyze a change inside a mounted directory. Markdown files are identified by
+/// their path within the site, so they are rebuilt like content files.
+pub fn analyze_mount_change(changed_path: &Path, mount: &crate::site::Mount) -> ChangeType {
+    if changed_path.parent() == Some(mount.dir.as_path())
+        && changed_path.file_name() == Some(OsStr::new(crate::site::ignore::IGNORE_FILE))
+    {
+        return ChangeType::IgnoreFile;
+    }
+    match mount.site_path(changed_path) {
+        Some(relative) if changed_path.extension().is_some_and(|ext| ext == "md") => {
+            ChangeType::Markdown {
+                relative_path: relative.to_string_lossy().to_string(),
+            }
+        }
+        _ => ChangeType::Asset,
+    }
+}
+
 /// Check if a path is an HTML template
 pub fn is_html_template(path: &Path) -> bool {
     path.extension()
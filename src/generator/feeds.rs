@@ -1,3 +1,5 @@
+use crate::generator::templates::context::feed_file_path;
+use crate::i18n::I18nManager;
 use crate::parser::Document;
 use crate::site::SiteConfig;
 use chrono::{DateTime, Utc};
@@ -5,16 +7,39 @@ use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 
-/// Generate Atom feed for blog posts
+/// Generate Atom feeds for blog posts: `feed.xml` for the default language and
+/// `feed.<lang>.xml` for each language configured under `[languages]`
 pub fn generate_feed(
     documents: &[Document],
     site_config: &SiteConfig,
     output_dir: &Path,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    generate_language_feed(
+        documents,
+        site_config,
+        I18nManager::default_language(),
+        output_dir,
+    )?;
+    for language in site_config.feed_languages() {
+        generate_language_feed(documents, site_config, &language, output_dir)?;
+    }
+    Ok(())
+}
+
+/// Generate the Atom feed for the posts of a single language
+fn generate_language_feed(
+    documents: &[Document],
+    site_config: &SiteConfig,
+    language: &str,
+    output_dir: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let site_config = site_config.for_language(language);
+    let feed_file = feed_file_path(&site_config, language);
+
     // Filter and sort posts
     let mut posts: Vec<&Document> = documents
         .iter()
-        .filter(|doc| is_post_for_feed(doc))
+        .filter(|doc| is_post_for_feed(doc, language))
         .collect();
 
     posts.sort_by(|a, b| {
@@ -27,10 +52,10 @@ pub fn generate_feed(
     // Limit to 20 most recent posts
     posts.truncate(20);
 
-    let feed_content = generate_atom_feed(&posts, site_config)?;
+    let feed_content = generate_atom_feed(&posts, &site_config, &feed_file)?;
 
     // Write feed file
-    let feed_path = output_dir.join("feed.xml");
+    let feed_path = output_dir.join(feed_file.trim_start_matches('/'));
     let mut file = File::create(&feed_path)?;
     file.write_all(feed_content.as_bytes())?;
 
@@ -41,6 +66,7 @@ pub fn generate_feed(
 fn generate_atom_feed(
     posts: &[&Document],
     site_config: &SiteConfig,
+    feed_file: &str,
 ) -> Result<String, Box<dyn std::error::Error>> {
     let mut feed = String::new();
 
@@ -63,8 +89,9 @@ fn generate_atom_feed(
 
     if let Some(ref base_url) = site_config.base_url {
         feed.push_str(&format!(
-            "  <link href=\"{}/feed.xml\" rel=\"self\" />\n",
-            escape_xml_url(base_url)
+            "  <link href=\"{}{}\" rel=\"self\" />\n",
+            escape_xml_url(base_url.trim_end_matches('/')),
+            feed_file
         ));
         feed.push_str(&format!(
             "  <link href=\"{}\" />\n",
@@ -157,9 +184,9 @@ fn generate_post_url(post: &Document, site_config: &SiteConfig) -> String {
     }
 }
 
-/// Check if document should be included in feed
-fn is_post_for_feed(document: &Document) -> bool {
-    // Include only posts (not pages) and only default language
+/// Check if document should be included in the feed for `language`
+fn is_post_for_feed(document: &Document, language: &str) -> bool {
+    // Include only posts (not pages) in the feed's language
     (document
         .front_matter
         .extra
@@ -167,7 +194,7 @@ fn is_post_for_feed(document: &Document) -> bool {
         .and_then(|v| v.as_str())
         == Some("post")
         || document.file_path.starts_with("posts/"))
-        && document.language == "en"
+        && document.language == language
 }
 
 /// Escape XML special characters
@@ -0,0 +1,231 @@
+//! Server-side footnote rendering
+//!
+//! References are numbered in order of first use. Definitions are either collected
+//! into an endnote list with a backlink per reference, or placed next to their
+//! first reference as Tufte-style sidenotes. Sidenotes hold inline content only;
+//! notes with lists, code or other blocks are kept as end notes.
+
+use crate::generator::ast_parser::Footnote;
+use crate::site::FootnoteStyle;
+use pulldown_cmark::{html::push_html, CowStr, Event, Tag, TagEnd};
+use std::collections::{HashMap, HashSet};
+
+/// Render footnote references and definitions, recording number, HTML and
+/// backlink anchors of each footnote in `footnotes`
+pub fn render_footnotes<'a>(
+    events: Vec<Event<'a>>,
+    style: FootnoteStyle,
+    footnotes: &mut HashMap<String, Footnote>,
+) -> Vec<Event<'a>> {
+    let has_footnotes = events.iter().any(|e| {
+        matches!(
+            e,
+            Event::FootnoteReference(_) | Event::Start(Tag::FootnoteDefinition(_))
+        )
+    });
+    if !has_footnotes {
+        return events;
+    }
+
+    let (body, definitions) = split_definitions(events);
+
+    // Number footnotes by first reference, then any that are never referenced
+    let mut numbers: HashMap<String, usize> = HashMap::new();
+    let referenced = body
+        .iter()
+        .chain(definitions.iter().flat_map(|(_, events)| events))
+        .filter_map(|e| match e {
+            Event::FootnoteReference(label) => Some(label.to_string()),
+            _ => None,
+        });
+    let labels = referenced.chain(definitions.iter().map(|(label, _)| label.clone()));
+    for label in labels {
+        if definitions.iter().any(|(l, _)| *l == label) {
+            let next = numbers.len() + 1;
+            numbers.entry(label).or_insert(next);
+        }
+    }
+
+    // Notes with block content (lists, code, quotes...) cannot sit inside the
+    // paragraph of their reference, so they stay end notes in sidenote mode
+    let block_notes: HashSet<String> = definitions
+        .iter()
+        .filter(|(_, events)| events.iter().any(is_block))
+        .map(|(label, _)| label.clone())
+        .collect();
+
+    // References inside definitions link to the note without a backlink of their own
+    let mut rendered: HashMap<String, String> = HashMap::new();
+    for (label, events) in definitions {
+        let events = events.into_iter().map(|event| match event {
+            Event::FootnoteReference(label) => match numbers.get(label.as_ref()) {
+                Some(n) => Event::InlineHtml(CowStr::from(format!(
+                    "<sup class=\"footnote-reference\"><a href=\"#fn-{n}\">{n}</a></sup>"
+                ))),
+                None => Event::Text(CowStr::from(format!("[^{label}]"))),
+            },
+            other => other,
+        });
+        let mut html = String::new();
+        push_html(&mut html, events);
+        rendered.insert(label, html);
+    }
+
+    let mut backrefs: HashMap<String, Vec<String>> = HashMap::new();
+    let mut out = Vec::with_capacity(body.len() + 1);
+    for event in body {
+        let Event::FootnoteReference(label) = event else {
+            out.push(event);
+            continue;
+        };
+        let Some(&n) = numbers.get(label.as_ref()) else {
+            out.push(Event::Text(CowStr::from(format!("[^{label}]"))));
+            continue;
+        };
+        let refs = backrefs.entry(label.to_string()).or_default();
+        let ref_id = if refs.is_empty() {
+            format!("fnref-{n}")
+        } else {
+            format!("fnref-{n}-{}", refs.len() + 1)
+        };
+        let html = match style {
+            FootnoteStyle::Sidenotes if refs.is_empty() && !block_notes.contains(label.as_ref()) => {
+                sidenote(n, &ref_id, &rendered[label.as_ref()])
+            }
+            _ => format!(
+                "<sup class=\"footnote-reference\" id=\"{ref_id}\"><a href=\"#fn-{n}\">{n}</a></sup>"
+            ),
+        };
+        refs.push(ref_id);
+        out.push(Event::InlineHtml(CowStr::from(html)));
+    }
+
+    let mut ordered: Vec<(String, usize)> = numbers.into_iter().collect();
+    ordered.sort_by_key(|(_, n)| *n);
+
+    let endnotes: Vec<&(String, usize)> = ordered
+        .iter()
+        .filter(|(label, _)| style == FootnoteStyle::Endnotes || block_notes.contains(label))
+        .collect();
+    if !endnotes.is_empty() {
+        let mut section =
+            String::from("<section class=\"footnotes\" role=\"doc-endnotes\">\n<hr>\n<ol>\n");
+        for (label, n) in endnotes {
+            let links = backrefs
+                .get(label)
+                .map(|refs| backref_links(refs))
+                .unwrap_or_default();
+            section.push_str(&format!(
+                "<li id=\"fn-{n}\" class=\"footnote-definition\">\n{}</li>\n",
+                append_to_last_paragraph(&rendered[label], &links)
+            ));
+        }
+        section.push_str("</ol>\n</section>\n");
+        out.push(Event::Html(CowStr::from(section)));
+    }
+
+    for (label, n) in ordered {
+        let footnote = footnotes.entry(label.clone()).or_insert_with(|| Footnote {
+            id: label.clone(),
+            ..Default::default()
+        });
+        footnote.number = n;
+        footnote.html = rendered.remove(&label).unwrap_or_default();
+        footnote.backrefs = backrefs.remove(&label).unwrap_or_default();
+    }
+
+    out
+}
+
+/// Whether the event starts block content other than a paragraph
+fn is_block(event: &Event) -> bool {
+    match event {
+        Event::Start(tag) => !matches!(
+            tag,
+            Tag::Paragraph
+                | Tag::Emphasis
+                | Tag::Strong
+                | Tag::Strikethrough
+                | Tag::Superscript
+                | Tag::Subscript
+                | Tag::Link { .. }
+                | Tag::Image { .. }
+        ),
+        Event::Html(_) | Event::Rule => true,
+        _ => false,
+    }
+}
+
+/// Separate footnote definitions from the document flow
+fn split_definitions(events: Vec<Event>) -> (Vec<Event>, Vec<(String, Vec<Event>)>) {
+    let mut body = Vec::with_capacity(events.len());
+    let mut definitions: Vec<(String, Vec<Event>)> = Vec::new();
+    let mut current: Option<(String, Vec<Event>)> = None;
+
+    for event in events {
+        match event {
+            Event::Start(Tag::FootnoteDefinition(label)) => {
+                current = Some((label.to_string(), Vec::new()));
+            }
+            Event::End(TagEnd::FootnoteDefinition) => {
+                if let Some((label, events)) = current.take() {
+                    // The first definition of a label wins, as in GitHub
+                    if !definitions.iter().any(|(l, _)| *l == label) {
+                        definitions.push((label, events));
+                    }
+                }
+            }
+            other => match current.as_mut() {
+                Some((_, events)) => events.push(other),
+                None => body.push(other),
+            },
+        }
+    }
+
+    (body, definitions)
+}
+
+/// Backlinks to every reference of a note; repeated references are numbered
+fn backref_links(refs: &[String]) -> String {
+    refs.iter()
+        .enumerate()
+        .map(|(i, ref_id)| {
+            let suffix = if i == 0 {
+                String::new()
+            } else {
+                format!("<sup>{}</sup>", i + 1)
+            };
+            format!(
+                "<a href=\"#{ref_id}\" class=\"footnote-return\" role=\"doc-backlink\" aria-label=\"Back to reference {}\">↩{suffix}</a>",
+                i + 1
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn append_to_last_paragraph(html: &str, links: &str) -> String {
+    if links.is_empty() {
+        return html.to_string();
+    }
+    match html.trim_end().strip_suffix("</p>") {
+        Some(before) => format!("{before} {links}</p>\n"),
+        None => format!("{html}<p>{links}</p>\n"),
+    }
+}
+
+/// Inline sidenote: a numbered toggle label, the checkbox used to reveal it on
+/// narrow screens and the note itself. Paragraph breaks become line breaks so
+/// the note stays valid inside the surrounding paragraph.
+fn sidenote(n: usize, ref_id: &str, html: &str) -> String {
+    let text = html
+        .trim()
+        .trim_start_matches("<p>")
+        .trim_end_matches("</p>")
+        .replace("</p>\n<p>", "<br>");
+    format!(
+        "<label for=\"sn-{n}\" id=\"{ref_id}\" class=\"margin-toggle sidenote-number\">{n}</label>\
+         <input type=\"checkbox\" id=\"sn-{n}\" class=\"margin-toggle\">\
+         <span class=\"sidenote\" id=\"fn-{n}\"><span class=\"sidenote-label\">{n}</span> {text}</span>"
+    )
+}
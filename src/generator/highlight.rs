@@ -0,0 +1,120 @@
+//! Build-time syntax highlighting for fenced code blocks
+//!
+//! Code is tokenized with syntect and emitted as class-based spans (`hl-*`), so
+//! the colors live in a generated stylesheet with light and dark variants.
+
+use crate::site::HighlightConfig;
+use once_cell::sync::Lazy;
+use syntect::highlighting::{Theme, ThemeSet};
+use syntect::html::{css_for_theme_with_class_style, ClassStyle, ClassedHTMLGenerator};
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+use syntect::util::LinesWithEndings;
+use tracing::warn;
+
+/// Prefix of all generated highlighting classes
+const CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "hl-" };
+
+static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines);
+static THEME_SET: Lazy<ThemeSet> = Lazy::new(ThemeSet::load_defaults);
+
+/// Path of the generated stylesheet, relative to the output directory
+pub const HIGHLIGHT_CSS_PATH: &str = "assets/css/highlight.css";
+
+/// Generate the stylesheet with the light theme and a dark variant that follows
+/// both `prefers-color-scheme` and the themes' `data-theme` toggle
+pub fn highlight_css(config: &HighlightConfig) -> String {
+    let light = theme_css(&config.theme, "InspiredGitHub");
+    let dark = theme_css(&config.dark_theme, "base16-ocean.dark");
+
+    let mut css = String::from("/* Syntax highlighting generated by Krik */\n\n");
+    css.push_str(&light);
+    css.push_str("\n@media (prefers-color-scheme: dark) {\n");
+    css.push_str(&scope_css(&dark, ":root:not([data-theme=\"light\"])"));
+    css.push_str("}\n\n");
+    css.push_str(&scope_css(&dark, "[data-theme=\"dark\"]"));
+    css
+}
+
+/// Highlight `code` as `lang` into class-based spans.
+///
+/// Returns `None` when highlighting is disabled, the language is not in the
+/// configured list or no syntax is known for it.
+pub fn highlight_code(lang: &str, code: &str, config: &HighlightConfig) -> Option<String> {
+    if !config.enabled {
+        return None;
+    }
+    let enabled = config.languages.is_empty()
+        || config
+            .languages
+            .iter()
+            .any(|l| l.eq_ignore_ascii_case(lang));
+    if !enabled {
+        return None;
+    }
+    highlight(find_syntax(lang)?, code)
+}
+
+fn find_syntax(lang: &str) -> Option<&'static SyntaxReference> {
+    if lang.is_empty() {
+        return None;
+    }
+    SYNTAX_SET
+        .find_syntax_by_token(lang)
+        .or_else(|| SYNTAX_SET.find_syntax_by_token(&lang.to_lowercase()))
+}
+
+fn highlight(syntax: &SyntaxReference, code: &str) -> Option<String> {
+    let mut generator =
+        ClassedHTMLGenerator::new_with_class_style(syntax, &SYNTAX_SET, CLASS_STYLE);
+    for line in LinesWithEndings::from(code) {
+        if let Err(e) = generator.parse_html_for_line_which_includes_newline(line) {
+            warn!("Syntax highlighting failed for '{}': {}", syntax.name, e);
+            return None;
+        }
+    }
+    Some(generator.finalize())
+}
+
+fn theme_css(name: &str, default: &str) -> String {
+    let theme: &Theme = THEME_SET.themes.get(name).unwrap_or_else(|| {
+        warn!(
+            "Unknown highlight theme '{}', using '{}' (available: {})",
+            name,
+            default,
+            THEME_SET
+                .themes
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        &THEME_SET.themes[default]
+    });
+    let css = css_for_theme_with_class_style(theme, CLASS_STYLE).unwrap_or_default();
+    // Drop syntect's header comment; each variant is labelled by its scope instead
+    css.split_once("*/")
+        .map(|(_, rest)| rest.trim_start().to_string())
+        .unwrap_or(css)
+}
+
+/// Prefix every selector of a flat stylesheet with `scope`
+fn scope_css(css: &str, scope: &str) -> String {
+    let mut out = String::with_capacity(css.len() * 2);
+    for rule in css.split_inclusive('}') {
+        match rule.split_once('{') {
+            Some((selectors, body)) => {
+                let scoped = selectors
+                    .split(',')
+                    .map(|s| format!("{scope} {}", s.trim()))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                out.push_str(&scoped);
+                out.push_str(" {");
+                out.push_str(body);
+                out.push('\n');
+            }
+            None => out.push_str(rule.trim_end()),
+        }
+    }
+    out
+}
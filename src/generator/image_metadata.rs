@@ -0,0 +1,367 @@
+//! EXIF and XMP metadata in published images
+//!
+//! Metadata is removed at the container level (JPEG segments, PNG chunks, WebP
+//! RIFF chunks) without re-encoding any pixels. The EXIF orientation is the only
+//! value kept, in a minimal EXIF block, so rotated photos still display upright.
+
+/// Extensions of images whose metadata can be stripped
+pub const STRIPPABLE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "webp"];
+
+const JPEG_EXIF: &[u8] = b"Exif\0\0";
+const JPEG_XMP: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+const JPEG_XMP_EXTENSION: &[u8] = b"http://ns.adobe.com/xmp/extension/\0";
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+const TAG_ORIENTATION: u16 = 0x0112;
+const TAG_GPS_IFD: u16 = 0x8825;
+const TAG_GPS_LATITUDE: u16 = 0x0002;
+const TAG_GPS_LONGITUDE: u16 = 0x0004;
+
+/// A metadata block found in an image
+enum Block<'a> {
+    /// TIFF-structured EXIF data
+    Exif(&'a [u8]),
+    /// XMP packet
+    Xmp(&'a [u8]),
+    /// Other descriptive metadata (IPTC, PNG text chunks)
+    Other,
+}
+
+/// A JPEG segment or PNG/WebP chunk: its marker or type, its bytes and the
+/// metadata it carries
+type Part<'a, K> = (K, &'a [u8], Option<Block<'a>>);
+
+/// Image bytes without EXIF, XMP, IPTC and PNG text metadata, keeping a
+/// non-default EXIF orientation. `None` when the data is not a well-formed JPEG,
+/// PNG or WebP file.
+pub fn strip_metadata(bytes: &[u8]) -> Option<Vec<u8>> {
+    if bytes.starts_with(&[0xFF, 0xD8]) {
+        strip_jpeg(bytes)
+    } else if bytes.starts_with(PNG_SIGNATURE) {
+        strip_png(bytes)
+    } else if is_webp(bytes) {
+        strip_webp(bytes)
+    } else {
+        None
+    }
+}
+
+/// Whether the image's EXIF or XMP metadata records a GPS position
+pub fn has_gps_data(bytes: &[u8]) -> bool {
+    let blocks = if bytes.starts_with(&[0xFF, 0xD8]) {
+        jpeg_segments(bytes).map(|segments| segments.into_iter().filter_map(|s| s.2).collect())
+    } else if bytes.starts_with(PNG_SIGNATURE) {
+        png_chunks(bytes).map(|chunks| chunks.into_iter().filter_map(|c| c.2).collect())
+    } else if is_webp(bytes) {
+        webp_chunks(bytes).map(|chunks| chunks.into_iter().filter_map(|c| c.2).collect())
+    } else {
+        None
+    };
+    blocks
+        .unwrap_or_else(Vec::new)
+        .iter()
+        .any(|block| match block {
+            Block::Exif(tiff) => tiff_has_gps(tiff),
+            Block::Xmp(xmp) => {
+                let xmp = String::from_utf8_lossy(xmp);
+                xmp.contains("GPSLatitude") || xmp.contains("GPSLongitude")
+            }
+            Block::Other => false,
+        })
+}
+
+/// JPEG segments as (marker, whole segment, metadata); the entropy-coded data
+/// after the start of scan is returned as one final segment
+fn jpeg_segments(bytes: &[u8]) -> Option<Vec<Part<'_, u8>>> {
+    let mut segments = vec![(0xD8, &bytes[..2], None)];
+    let mut pos = 2;
+    while pos < bytes.len() {
+        if bytes[pos] != 0xFF || pos + 1 >= bytes.len() {
+            return None;
+        }
+        let marker = bytes[pos + 1];
+        match marker {
+            // Fill byte
+            0xFF => {
+                pos += 1;
+                continue;
+            }
+            // Standalone markers
+            0x01 | 0xD0..=0xD7 => {
+                segments.push((marker, &bytes[pos..pos + 2], None));
+                pos += 2;
+                continue;
+            }
+            // Start of scan or end of image: the rest is image data
+            0xDA | 0xD9 => {
+                segments.push((marker, &bytes[pos..], None));
+                return Some(segments);
+            }
+            _ => {}
+        }
+        let length = usize::from(read_u16(bytes, pos + 2, false)?);
+        let end = pos + 2 + length;
+        if length < 2 || end > bytes.len() {
+            return None;
+        }
+        let payload = &bytes[pos + 4..end];
+        let block = match marker {
+            0xE1 if payload.starts_with(JPEG_EXIF) => {
+                Some(Block::Exif(&payload[JPEG_EXIF.len()..]))
+            }
+            0xE1 if payload.starts_with(JPEG_XMP) => Some(Block::Xmp(&payload[JPEG_XMP.len()..])),
+            0xE1 if payload.starts_with(JPEG_XMP_EXTENSION) => Some(Block::Xmp(payload)),
+            0xED => Some(Block::Other),
+            _ => None,
+        };
+        segments.push((marker, &bytes[pos..end], block));
+        pos = end;
+    }
+    Some(segments)
+}
+
+fn strip_jpeg(bytes: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut orientation_kept = false;
+    for (_, segment, block) in jpeg_segments(bytes)? {
+        match block {
+            None => out.extend_from_slice(segment),
+            Some(Block::Exif(tiff)) => {
+                if let Some(orientation) = kept_orientation(tiff, &mut orientation_kept) {
+                    let exif = [JPEG_EXIF, &orientation_exif(orientation)].concat();
+                    out.extend_from_slice(&[0xFF, 0xE1]);
+                    out.extend_from_slice(&((exif.len() + 2) as u16).to_be_bytes());
+                    out.extend_from_slice(&exif);
+                }
+            }
+            Some(_) => {}
+        }
+    }
+    Some(out)
+}
+
+/// PNG chunks as (type, whole chunk, metadata)
+fn png_chunks(bytes: &[u8]) -> Option<Vec<Part<'_, [u8; 4]>>> {
+    let mut chunks = Vec::new();
+    let mut pos = PNG_SIGNATURE.len();
+    while pos < bytes.len() {
+        let length = read_u32(bytes, pos, false)? as usize;
+        let end = pos.checked_add(12)?.checked_add(length)?;
+        if end > bytes.len() {
+            return None;
+        }
+        let kind: [u8; 4] = bytes[pos + 4..pos + 8].try_into().ok()?;
+        let data = &bytes[pos + 8..pos + 8 + length];
+        let block = match &kind {
+            b"eXIf" => Some(Block::Exif(data)),
+            b"iTXt" if data.starts_with(b"XML:com.adobe.xmp\0") => Some(Block::Xmp(data)),
+            b"tEXt" | b"zTXt" | b"iTXt" => Some(Block::Other),
+            _ => None,
+        };
+        chunks.push((kind, &bytes[pos..end], block));
+        pos = end;
+        if &kind == b"IEND" {
+            break;
+        }
+    }
+    Some(chunks)
+}
+
+fn strip_png(bytes: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(bytes.len());
+    out.extend_from_slice(PNG_SIGNATURE);
+    let mut orientation_kept = false;
+    for (_, chunk, block) in png_chunks(bytes)? {
+        match block {
+            None => out.extend_from_slice(chunk),
+            Some(Block::Exif(tiff)) => {
+                if let Some(orientation) = kept_orientation(tiff, &mut orientation_kept) {
+                    let data = orientation_exif(orientation);
+                    let mut typed = b"eXIf".to_vec();
+                    typed.extend_from_slice(&data);
+                    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+                    out.extend_from_slice(&typed);
+                    out.extend_from_slice(&crc32(&typed).to_be_bytes());
+                }
+            }
+            Some(_) => {}
+        }
+    }
+    Some(out)
+}
+
+fn is_webp(bytes: &[u8]) -> bool {
+    bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP"
+}
+
+/// WebP chunks as (FourCC, chunk data, metadata)
+fn webp_chunks(bytes: &[u8]) -> Option<Vec<Part<'_, [u8; 4]>>> {
+    let mut chunks = Vec::new();
+    let mut pos = 12;
+    while pos + 8 <= bytes.len() {
+        let kind: [u8; 4] = bytes[pos..pos + 4].try_into().ok()?;
+        let length = read_u32(bytes, pos + 4, true)? as usize;
+        let end = pos.checked_add(8)?.checked_add(length)?;
+        if end > bytes.len() {
+            return None;
+        }
+        let data = &bytes[pos + 8..end];
+        let block = match &kind {
+            b"EXIF" => Some(Block::Exif(data.strip_prefix(JPEG_EXIF).unwrap_or(data))),
+            b"XMP " => Some(Block::Xmp(data)),
+            _ => None,
+        };
+        chunks.push((kind, data, block));
+        // Chunks are padded to an even size
+        pos = end + (length & 1);
+    }
+    Some(chunks)
+}
+
+fn strip_webp(bytes: &[u8]) -> Option<Vec<u8>> {
+    const XMP_FLAG: u8 = 0x04;
+    const EXIF_FLAG: u8 = 0x08;
+
+    let chunks = webp_chunks(bytes)?;
+    let mut orientation_kept = false;
+    let mut kept: Vec<([u8; 4], Vec<u8>)> = Vec::with_capacity(chunks.len());
+    for (kind, data, block) in chunks {
+        match block {
+            None => kept.push((kind, data.to_vec())),
+            Some(Block::Exif(tiff)) => {
+                if let Some(orientation) = kept_orientation(tiff, &mut orientation_kept) {
+                    kept.push((kind, orientation_exif(orientation)));
+                }
+            }
+            Some(_) => {}
+        }
+    }
+
+    let mut body = b"WEBP".to_vec();
+    for (kind, mut data) in kept {
+        if &kind == b"VP8X" && !data.is_empty() {
+            data[0] &= !XMP_FLAG;
+            if !orientation_kept {
+                data[0] &= !EXIF_FLAG;
+            }
+        }
+        body.extend_from_slice(&kind);
+        body.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        body.extend_from_slice(&data);
+        if data.len() & 1 == 1 {
+            body.push(0);
+        }
+    }
+
+    let mut out = b"RIFF".to_vec();
+    out.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    out.extend_from_slice(&body);
+    Some(out)
+}
+
+/// Orientation to keep from the first EXIF block, if it is not the default
+fn kept_orientation(tiff: &[u8], orientation_kept: &mut bool) -> Option<u16> {
+    if *orientation_kept {
+        return None;
+    }
+    let orientation = exif_orientation(tiff).filter(|&o| (2..=8).contains(&o))?;
+    *orientation_kept = true;
+    Some(orientation)
+}
+
+/// Orientation tag of a TIFF-structured EXIF block
+pub fn exif_orientation(tiff: &[u8]) -> Option<u16> {
+    let (little_endian, entries) = ifd0_entries(tiff)?;
+    entries
+        .into_iter()
+        .find(|&(tag, _)| tag == TAG_ORIENTATION)
+        .and_then(|(_, entry)| read_u16(tiff, entry + 8, little_endian))
+}
+
+fn tiff_has_gps(tiff: &[u8]) -> bool {
+    let Some((little_endian, entries)) = ifd0_entries(tiff) else {
+        return false;
+    };
+    let Some(gps_ifd) = entries
+        .into_iter()
+        .find(|&(tag, _)| tag == TAG_GPS_IFD)
+        .and_then(|(_, entry)| read_u32(tiff, entry + 8, little_endian))
+    else {
+        return false;
+    };
+    ifd_entries(tiff, gps_ifd as usize, little_endian)
+        .unwrap_or_default()
+        .into_iter()
+        .any(|(tag, _)| tag == TAG_GPS_LATITUDE || tag == TAG_GPS_LONGITUDE)
+}
+
+/// Byte order and (tag, entry offset) pairs of the first IFD
+fn ifd0_entries(tiff: &[u8]) -> Option<(bool, Vec<(u16, usize)>)> {
+    let little_endian = match tiff.get(..2)? {
+        b"II" => true,
+        b"MM" => false,
+        _ => return None,
+    };
+    let ifd0 = read_u32(tiff, 4, little_endian)? as usize;
+    Some((little_endian, ifd_entries(tiff, ifd0, little_endian)?))
+}
+
+fn ifd_entries(tiff: &[u8], offset: usize, little_endian: bool) -> Option<Vec<(u16, usize)>> {
+    let count = usize::from(read_u16(tiff, offset, little_endian)?);
+    (0..count)
+        .map(|i| {
+            let entry = offset + 2 + i * 12;
+            tiff.get(entry..entry + 12)?;
+            Some((read_u16(tiff, entry, little_endian)?, entry))
+        })
+        .collect()
+}
+
+/// Big-endian TIFF block holding only the orientation tag
+fn orientation_exif(orientation: u16) -> Vec<u8> {
+    let mut tiff = b"MM\x00\x2a\x00\x00\x00\x08".to_vec();
+    tiff.extend_from_slice(&1u16.to_be_bytes());
+    tiff.extend_from_slice(&TAG_ORIENTATION.to_be_bytes());
+    // SHORT, one value, left-justified in the value field
+    tiff.extend_from_slice(&3u16.to_be_bytes());
+    tiff.extend_from_slice(&1u32.to_be_bytes());
+    tiff.extend_from_slice(&orientation.to_be_bytes());
+    tiff.extend_from_slice(&[0, 0]);
+    // No next IFD
+    tiff.extend_from_slice(&0u32.to_be_bytes());
+    tiff
+}
+
+fn read_u16(bytes: &[u8], offset: usize, little_endian: bool) -> Option<u16> {
+    let raw: [u8; 2] = bytes.get(offset..offset.checked_add(2)?)?.try_into().ok()?;
+    Some(if little_endian {
+        u16::from_le_bytes(raw)
+    } else {
+        u16::from_be_bytes(raw)
+    })
+}
+
+fn read_u32(bytes: &[u8], offset: usize, little_endian: bool) -> Option<u32> {
+    let raw: [u8; 4] = bytes.get(offset..offset.checked_add(4)?)?.try_into().ok()?;
+    Some(if little_endian {
+        u32::from_le_bytes(raw)
+    } else {
+        u32::from_be_bytes(raw)
+    })
+}
+
+/// CRC-32 as used by PNG chunks
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
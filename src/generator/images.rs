@@ -0,0 +1,444 @@
+//! Responsive images
+//!
+//! Local JPEG, PNG and WebP images are resized to the widths configured in the
+//! `[images]` table (PNG optionally also as WebP) when assets are emitted, and Markdown
+//! images are rendered with `srcset`, `sizes`, intrinsic `width`/`height` and lazy
+//! loading. Resized files are cached by content hash, so unchanged images are only
+//! processed once.
+
+use crate::error::{IoError, IoErrorKind, KrikError, KrikResult};
+use crate::generator::assets::is_ignored_asset;
+use crate::generator::ast_parser::MarkdownOptions;
+use crate::site::{IgnoreRules, ImageConfig};
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::metadata::Orientation;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader};
+use pulldown_cmark::{CowStr, Event, Tag, TagEnd};
+use pulldown_cmark_escape::{escape_href, escape_html};
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+use walkdir::WalkDir;
+
+/// Extensions of images that are resized; other images only get dimensions
+pub const RESIZABLE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "webp"];
+
+/// Extensions of images rendered with intrinsic dimensions and lazy loading
+const IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "webp", "gif"];
+
+/// Extensions of images that get WebP variants. WebP is encoded losslessly, which
+/// beats PNG but is usually several times larger than a resized JPEG, so JPEG
+/// originals are only offered as JPEG.
+const WEBP_SOURCE_EXTENSIONS: &[&str] = &["png"];
+
+/// Display dimensions of an image, with its EXIF orientation applied
+pub fn image_dimensions(path: &Path) -> Option<(u32, u32)> {
+    let mut decoder = ImageReader::open(path)
+        .ok()?
+        .with_guessed_format()
+        .ok()?
+        .into_decoder()
+        .ok()?;
+    let (width, height) = decoder.dimensions();
+    match decoder.orientation().unwrap_or(Orientation::NoTransforms) {
+        Orientation::Rotate90
+        | Orientation::Rotate270
+        | Orientation::Rotate90FlipH
+        | Orientation::Rotate270FlipH => Some((height, width)),
+        _ => Some((width, height)),
+    }
+}
+
+/// Configured widths smaller than the original, in ascending order
+pub fn variant_widths(original_width: u32, config: &ImageConfig) -> Vec<u32> {
+    let mut widths: Vec<u32> = config
+        .widths
+        .iter()
+        .copied()
+        .filter(|&w| w > 0 && w < original_width)
+        .collect();
+    widths.sort_unstable();
+    widths.dedup();
+    widths
+}
+
+/// Path or URL of a variant: `photos/cat.jpg` becomes `photos/cat-480w.jpg`
+/// (or `photos/cat-480w.webp`)
+pub fn variant_path(path: &str, width: u32, extension: Option<&str>) -> String {
+    let (stem, original_extension) = match path.rfind('.') {
+        Some(dot) if !path[dot..].contains('/') => (&path[..dot], &path[dot + 1..]),
+        _ => (path, ""),
+    };
+    format!(
+        "{stem}-{width}w.{}",
+        extension.unwrap_or(original_extension)
+    )
+}
+
+fn extension_of(path: &str) -> Option<String> {
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+}
+
+/// Render local images with `srcset`, `sizes`, intrinsic dimensions and lazy
+/// loading. Images that cannot be read are left to the default renderer.
+pub fn render_images<'a>(events: Vec<Event<'a>>, options: &MarkdownOptions) -> Vec<Event<'a>> {
+    if !options.images.enabled {
+        return events;
+    }
+
+    let mut out = Vec::with_capacity(events.len());
+    // Original start event, alt text and buffered events of the image being read
+    let mut image: Option<(Event<'a>, String, Vec<Event<'a>>)> = None;
+    let mut depth = 0;
+
+    for event in events {
+        if let Some((_, ref mut alt, ref mut buffered)) = image {
+            match &event {
+                Event::Start(Tag::Image { .. }) => depth += 1,
+                Event::End(TagEnd::Image) if depth > 0 => depth -= 1,
+                Event::End(TagEnd::Image) => {
+                    buffered.push(event);
+                    let (start, alt, buffered) = image.take().unwrap();
+                    match responsive_image(&start, &alt, options) {
+                        Some(html) => out.push(Event::InlineHtml(CowStr::from(html))),
+                        None => out.extend(buffered),
+                    }
+                    continue;
+                }
+                Event::Text(text) | Event::Code(text) => alt.push_str(text),
+                _ => {}
+            }
+            buffered.push(event);
+            continue;
+        }
+
+        match event {
+            Event::Start(Tag::Image { .. }) => {
+                image = Some((event.clone(), String::new(), vec![event]));
+            }
+            _ => out.push(event),
+        }
+    }
+    if let Some((_, _, buffered)) = image {
+        out.extend(buffered);
+    }
+    out
+}
+
+/// Return true when WebP variants are written and offered for images with `extension`
+fn offers_webp(extension: &str, config: &ImageConfig) -> bool {
+    config.webp && WEBP_SOURCE_EXTENSIONS.contains(&extension)
+}
+
+fn responsive_image(start: &Event, alt: &str, options: &MarkdownOptions) -> Option<String> {
+    let Event::Start(Tag::Image {
+        dest_url, title, ..
+    }) = start
+    else {
+        return None;
+    };
+    let url = dest_url.as_ref();
+    let extension = extension_of(url)?;
+    if !IMAGE_EXTENSIONS.contains(&extension.as_str())
+        || url.contains("://")
+        || url.starts_with("//")
+        || url.starts_with("data:")
+        || url.contains(['?', '#'])
+    {
+        return None;
+    }
+
+    let file = match url.strip_prefix('/') {
+        Some(root_relative) => options.content_dir.as_ref()?.join(root_relative),
+        None => options.source_path.as_ref()?.parent()?.join(url),
+    };
+    let (width, height) = image_dimensions(&file)?;
+    let config = &options.images;
+
+    let mut srcset = String::new();
+    let mut webp_srcset = String::new();
+    if RESIZABLE_EXTENSIONS.contains(&extension.as_str()) {
+        let widths = variant_widths(width, config);
+        if !widths.is_empty() {
+            srcset = srcset_attribute(url, &widths, width, false);
+        }
+        if offers_webp(&extension, config) {
+            webp_srcset = srcset_attribute(url, &widths, width, true);
+        }
+    }
+
+    let mut html = String::new();
+    if !webp_srcset.is_empty() {
+        html.push_str("<picture><source type=\"image/webp\" srcset=\"");
+        let _ = escape_html(&mut html, &webp_srcset);
+        html.push_str("\" sizes=\"");
+        let _ = escape_html(&mut html, &config.sizes);
+        html.push_str("\">");
+    }
+    html.push_str("<img src=\"");
+    let _ = escape_href(&mut html, url);
+    html.push_str("\" alt=\"");
+    let _ = escape_html(&mut html, alt);
+    html.push('"');
+    if !title.is_empty() {
+        html.push_str(" title=\"");
+        let _ = escape_html(&mut html, title);
+        html.push('"');
+    }
+    html.push_str(&format!(" width=\"{width}\" height=\"{height}\""));
+    if !srcset.is_empty() {
+        html.push_str(" srcset=\"");
+        let _ = escape_html(&mut html, &srcset);
+        html.push_str("\" sizes=\"");
+        let _ = escape_html(&mut html, &config.sizes);
+        html.push('"');
+    }
+    if config.lazy {
+        html.push_str(" loading=\"lazy\" decoding=\"async\"");
+    }
+    html.push_str(" />");
+    if !webp_srcset.is_empty() {
+        html.push_str("</picture>");
+    }
+    Some(html)
+}
+
+/// `srcset` listing every variant and the original (as `{width}w`); WebP lists the
+/// original width as a variant too
+fn srcset_attribute(url: &str, widths: &[u32], original_width: u32, webp: bool) -> String {
+    let mut entries: Vec<String> = widths
+        .iter()
+        .map(|&w| format!("{} {w}w", variant_path(url, w, webp.then_some("webp"))))
+        .collect();
+    if webp {
+        entries.push(format!(
+            "{} {original_width}w",
+            variant_path(url, original_width, Some("webp"))
+        ));
+    } else {
+        entries.push(format!("{url} {original_width}w"));
+    }
+    entries.join(", ")
+}
+
+/// Cache directory for `config`, relative to the parent of the content directory
+pub fn cache_dir(source_dir: &Path, config: &ImageConfig) -> PathBuf {
+    let cache_dir = Path::new(&config.cache_dir);
+    if cache_dir.is_absolute() {
+        return cache_dir.to_path_buf();
+    }
+    source_dir
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(cache_dir)
+}
+
+/// Settings for the images of mounted directories, which share the cache of the
+/// content directory instead of creating one next to each mount
+pub fn mount_config(source_dir: &Path, config: &ImageConfig) -> ImageConfig {
+    ImageConfig {
+        cache_dir: cache_dir(source_dir, config).to_string_lossy().into_owned(),
+        ..config.clone()
+    }
+}
+
+/// Write the resized variants of every image in the content directory that is not ignored
+pub fn process_images(
+    source_dir: &Path,
+    output_dir: &Path,
+    config: &ImageConfig,
+    ignore: &IgnoreRules,
+) -> KrikResult<()> {
+    if !config.enabled {
+        return Ok(());
+    }
+    let images: Vec<PathBuf> = WalkDir::new(source_dir)
+        .follow_links(true)
+        .into_iter()
+        .filter_entry(|e| !ignore.is_ignored(e.path()))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && !is_ignored_asset(e.path()))
+        .map(|e| e.into_path())
+        .filter(|path| {
+            path.to_str()
+                .and_then(extension_of)
+                .is_some_and(|ext| RESIZABLE_EXTENSIONS.contains(&ext.as_str()))
+        })
+        .collect();
+
+    images
+        .par_iter()
+        .try_for_each(|path| process_image(source_dir, output_dir, path, config))
+}
+
+/// Write the resized variants of one image, from the cache when possible
+pub fn process_image(
+    source_dir: &Path,
+    output_dir: &Path,
+    path: &Path,
+    config: &ImageConfig,
+) -> KrikResult<()> {
+    if !config.enabled {
+        return Ok(());
+    }
+    let Ok(relative) = path.strip_prefix(source_dir) else {
+        return Ok(());
+    };
+    let relative = relative.to_string_lossy().replace('\\', "/");
+    let Some(extension) = extension_of(&relative) else {
+        return Ok(());
+    };
+    let Some((width, _)) = image_dimensions(path) else {
+        warn!("Skipping unreadable image {}", path.display());
+        return Ok(());
+    };
+
+    let mut variants: Vec<(u32, &str)> = variant_widths(width, config)
+        .into_iter()
+        .map(|w| (w, extension.as_str()))
+        .collect();
+    if offers_webp(&extension, config) {
+        let webp_widths = variant_widths(width, config).into_iter().chain([width]);
+        variants.extend(webp_widths.map(|w| (w, "webp")));
+    }
+    if variants.is_empty() {
+        return Ok(());
+    }
+
+    let bytes =
+        fs::read(path).map_err(|e| io_error(IoErrorKind::ReadFailed(e), path, "Reading image"))?;
+    let hash = content_hash(&bytes, config);
+    let cache = cache_dir(source_dir, config);
+    create_dir(&cache)?;
+
+    let mut decoded: Option<DynamicImage> = None;
+    for (variant_width, variant_extension) in variants {
+        let cached = cache.join(format!("{hash}-{variant_width}w.{variant_extension}"));
+        if !cached.exists() {
+            if decoded.is_none() {
+                decoded = Some(decode(path)?);
+            }
+            let image = decoded.as_ref().unwrap();
+            debug!(
+                "Resizing {} to {}w {}",
+                path.display(),
+                variant_width,
+                variant_extension
+            );
+            write_variant(image, variant_width, variant_extension, config, &cached)?;
+        }
+
+        let target = output_dir.join(variant_path(
+            &relative,
+            variant_width,
+            Some(variant_extension),
+        ));
+        if let Some(parent) = target.parent() {
+            create_dir(parent)?;
+        }
+        fs::copy(&cached, &target).map_err(|e| {
+            io_error(
+                IoErrorKind::WriteFailed(e),
+                &target,
+                "Copying resized image from cache",
+            )
+        })?;
+    }
+    Ok(())
+}
+
+/// Hash of the image and the settings that affect encoding
+fn content_hash(bytes: &[u8], config: &ImageConfig) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    hasher.update([config.quality]);
+    hasher
+        .finalize()
+        .iter()
+        .take(16)
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+fn decode(path: &Path) -> KrikResult<DynamicImage> {
+    let decode_error = |e: image::ImageError| {
+        io_error(
+            IoErrorKind::ReadFailed(std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+            path,
+            "Decoding image",
+        )
+    };
+    let mut decoder = ImageReader::open(path)
+        .map_err(|e| io_error(IoErrorKind::ReadFailed(e), path, "Opening image"))?
+        .with_guessed_format()
+        .map_err(|e| io_error(IoErrorKind::ReadFailed(e), path, "Reading image format"))?
+        .into_decoder()
+        .map_err(decode_error)?;
+    let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+    let mut image = DynamicImage::from_decoder(decoder).map_err(decode_error)?;
+    image.apply_orientation(orientation);
+    Ok(image)
+}
+
+/// Resize to `width`, keeping the aspect ratio, and encode by extension; written
+/// to a temporary file first so an interrupted build never leaves a partial entry
+fn write_variant(
+    image: &DynamicImage,
+    width: u32,
+    extension: &str,
+    config: &ImageConfig,
+    cached: &Path,
+) -> KrikResult<()> {
+    let resized = if width == image.width() {
+        image.clone()
+    } else {
+        let height = ((u64::from(image.height()) * u64::from(width)) / u64::from(image.width()))
+            .max(1) as u32;
+        image.resize_exact(width, height, image::imageops::FilterType::Lanczos3)
+    };
+
+    let mut buffer = Vec::new();
+    let encoded = match extension {
+        "jpg" | "jpeg" => resized
+            .to_rgb8()
+            .write_with_encoder(JpegEncoder::new_with_quality(
+                &mut buffer,
+                config.quality.clamp(1, 100),
+            )),
+        "webp" => resized
+            .to_rgba8()
+            .write_with_encoder(WebPEncoder::new_lossless(&mut buffer)),
+        _ => resized.write_to(&mut std::io::Cursor::new(&mut buffer), ImageFormat::Png),
+    };
+    encoded.map_err(|e| {
+        io_error(
+            IoErrorKind::WriteFailed(std::io::Error::new(std::io::ErrorKind::Other, e)),
+            cached,
+            "Encoding resized image",
+        )
+    })?;
+
+    let partial = cached.with_extension(format!("{extension}.part"));
+    fs::write(&partial, &buffer)
+        .and_then(|_| fs::rename(&partial, cached))
+        .map_err(|e| io_error(IoErrorKind::WriteFailed(e), cached, "Writing image cache"))
+}
+
+fn create_dir(dir: &Path) -> KrikResult<()> {
+    fs::create_dir_all(dir)
+        .map_err(|e| io_error(IoErrorKind::WriteFailed(e), dir, "Creating image directory"))
+}
+
+fn io_error(kind: IoErrorKind, path: &Path, context: &str) -> KrikError {
+    KrikError::Io(Box::new(IoError {
+        kind,
+        path: path.to_path_buf(),
+        context: context.to_string(),
+    }))
+}
@@ -0,0 +1,585 @@
+//! Resolution of links between Markdown documents
+//!
+//! Authors link to sources (`../posts/guide.md#setup`) or by name with wiki-links
+//! (`[[Guide]]`, `[[guide|the guide]]`); after scanning, both are rewritten to the
+//! target's output URL, relative to the linking page. The reader's language variant
+//! of the target is preferred when it exists. Resolved links are recorded on each
+//! document for backlinks and the site's link graph.
+//!
+//! Links to other sites get the `rel`, `target` and `class` attributes from the
+//! `[external_links]` table while Markdown events are rendered.
+
+use crate::generator::ast_parser::markdown_parser_options;
+use crate::generator::templates::paths::{calculate_relative_path, get_translation_key};
+use crate::i18n::I18nManager;
+use crate::parser::Document;
+use crate::site::ExternalLinksConfig;
+use once_cell::sync::Lazy;
+use percent_encoding::percent_decode_str;
+use pulldown_cmark::{CowStr, Event, LinkType, Parser, Tag, TagEnd};
+use pulldown_cmark_escape::{escape_href, escape_html};
+use regex::{Captures, Regex};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+
+static HREF_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"href="([^"]*)""#).unwrap());
+static WIKI_LINK_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\[\[([^\[\]|]+)(?:\|([^\[\]]+))?\]\]").unwrap());
+static WIKI_ANCHOR_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"<a class="wikilink(?: wikilink-missing)?" data-wikilink="([^"]*)">"#).unwrap()
+});
+
+/// File name of the link graph in the output directory
+pub const LINK_GRAPH_PATH: &str = "graph.json";
+
+/// A link that could not be resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedLink {
+    /// Path of the linking document, relative to the content directory
+    pub source: String,
+    /// The href as written, or `[[target]]` for wiki-links
+    pub href: String,
+    pub reason: String,
+}
+
+impl std::fmt::Display for UnresolvedLink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: '{}' {}", self.source, self.href, self.reason)
+    }
+}
+
+/// Output URL of a document, from the site root
+pub fn document_url(document: &Document) -> String {
+    let mut path = PathBuf::from(&document.file_path);
+    path.set_extension("html");
+    format!("/{}", path.to_string_lossy().replace('\\', "/"))
+}
+
+/// Names a wiki-link can use for a document: its title, its base name and its
+/// path without language suffix or extension (`posts/guide`), compared
+/// case-insensitively
+pub fn wiki_names(file_path: &str, base_name: &str, title: Option<&str>) -> Vec<String> {
+    let parent = Path::new(file_path)
+        .parent()
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_default();
+    let path = if parent.is_empty() {
+        base_name.to_string()
+    } else {
+        format!("{parent}/{base_name}")
+    };
+    let mut names = vec![wiki_name(base_name), wiki_name(&path)];
+    if let Some(title) = title {
+        names.push(wiki_name(title));
+    }
+    names.dedup();
+    names
+}
+
+/// Normalized form of a wiki-link target or document name
+pub fn wiki_name(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Targets of the wiki-links in a Markdown body, without anchors
+pub fn wiki_link_targets(markdown: &str) -> Vec<String> {
+    let events: Vec<Event> = Parser::new_ext(markdown, markdown_parser_options()).collect();
+    render_wiki_links(events)
+        .iter()
+        .filter_map(|event| match event {
+            Event::InlineHtml(html) => WIKI_ANCHOR_RE
+                .captures(html)
+                .map(|caps| split_anchor(&unescape_attr(&caps[1])).0.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Turn `[[target]]` and `[[target|label]]` in text into placeholder links that
+/// [`resolve_internal_links`] completes once every document is known. Code is
+/// left alone, and `[[toc]]` stays reserved for the table of contents.
+pub fn render_wiki_links(events: Vec<Event<'_>>) -> Vec<Event<'_>> {
+    let mut out = Vec::with_capacity(events.len());
+    let mut pending: Vec<Event> = Vec::new();
+    let mut in_code_block = false;
+
+    for event in events {
+        match event {
+            Event::Text(_) if !in_code_block => {
+                pending.push(event);
+                continue;
+            }
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            _ => {}
+        }
+        flush_text(&mut pending, &mut out);
+        out.push(event);
+    }
+    flush_text(&mut pending, &mut out);
+    out
+}
+
+/// Emit buffered text events, splitting out wiki-links when the joined text has any.
+/// pulldown-cmark delivers brackets as separate text events, so they are joined first.
+fn flush_text<'a>(pending: &mut Vec<Event<'a>>, out: &mut Vec<Event<'a>>) {
+    let text: String = pending
+        .iter()
+        .filter_map(|event| match event {
+            Event::Text(text) => Some(text.as_ref()),
+            _ => None,
+        })
+        .collect();
+    if !WIKI_LINK_RE.is_match(&text) {
+        out.append(pending);
+        return;
+    }
+    pending.clear();
+
+    let mut cursor = 0;
+    for caps in WIKI_LINK_RE.captures_iter(&text) {
+        let whole = caps.get(0).unwrap();
+        let target = caps[1].trim();
+        if target == "toc" && caps.get(2).is_none() {
+            continue;
+        }
+        if whole.start() > cursor {
+            out.push(Event::Text(CowStr::from(
+                text[cursor..whole.start()].to_string(),
+            )));
+        }
+        let label = caps.get(2).map_or(target, |label| label.as_str().trim());
+        let mut tag = String::from("<a class=\"wikilink\" data-wikilink=\"");
+        let _ = escape_html(&mut tag, target);
+        tag.push_str("\">");
+        out.push(Event::InlineHtml(CowStr::from(tag)));
+        out.push(Event::Text(CowStr::from(label.to_string())));
+        out.push(Event::InlineHtml(CowStr::from("</a>")));
+        cursor = whole.end();
+    }
+    if cursor < text.len() {
+        out.push(Event::Text(CowStr::from(text[cursor..].to_string())));
+    }
+}
+
+fn unescape_attr(value: &str) -> String {
+    value
+        .replace("&quot;", "\"")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+fn split_anchor(target: &str) -> (&str, Option<&str>) {
+    match target.split_once('#') {
+        Some((path, anchor)) => (path, Some(anchor)),
+        None => (target, None),
+    }
+}
+
+/// Rewrite links to `.md` files and wiki-links in every document's HTML, record
+/// the documents each one links to, and report the links pointing at missing
+/// documents or headings
+pub fn resolve_internal_links(
+    mut documents: Vec<Document>,
+) -> (Vec<Document>, Vec<UnresolvedLink>) {
+    let index = LinkIndex::new(&documents);
+
+    let mut updates = Vec::new();
+    let mut unresolved = Vec::new();
+    for (i, source) in documents.iter().enumerate() {
+        let has_md_links = source.content.contains(".md");
+        let has_wiki_links = source.content.contains("data-wikilink=");
+        // No internal links to resolve
+        if !has_md_links && !has_wiki_links {
+            continue;
+        }
+
+        let mut targets: Vec<usize> = Vec::new();
+        let mut content = source.content.clone();
+        if has_md_links {
+            content = HREF_RE
+                .replace_all(&content, |caps: &Captures| {
+                    let href = &caps[1];
+                    match index.resolve_href(href, source) {
+                        Ok(Some((target, anchor))) => {
+                            targets.push(target);
+                            format!("href=\"{}\"", index.url(source, target, anchor))
+                        }
+                        Ok(None) => caps[0].to_string(),
+                        Err(reason) => {
+                            unresolved.push(UnresolvedLink {
+                                source: source.file_path.clone(),
+                                href: href.to_string(),
+                                reason,
+                            });
+                            caps[0].to_string()
+                        }
+                    }
+                })
+                .into_owned();
+        }
+        if has_wiki_links {
+            content = WIKI_ANCHOR_RE
+                .replace_all(&content, |caps: &Captures| {
+                    let target = unescape_attr(&caps[1]);
+                    match index.resolve_wiki_link(&target, source) {
+                        Ok((doc, anchor)) => {
+                            targets.push(doc);
+                            let mut tag = String::from("<a class=\"wikilink\" href=\"");
+                            let _ = escape_href(&mut tag, &index.url(source, doc, anchor));
+                            tag.push_str("\">");
+                            tag
+                        }
+                        Err(reason) => {
+                            unresolved.push(UnresolvedLink {
+                                source: source.file_path.clone(),
+                                href: format!("[[{target}]]"),
+                                reason,
+                            });
+                            // Keep the target so the link resolves once the page exists
+                            let mut tag = String::from(
+                                "<a class=\"wikilink wikilink-missing\" data-wikilink=\"",
+                            );
+                            let _ = escape_html(&mut tag, &target);
+                            tag.push_str("\">");
+                            tag
+                        }
+                    }
+                })
+                .into_owned();
+        }
+
+        targets.sort_unstable();
+        targets.dedup();
+        let links = targets
+            .into_iter()
+            .filter(|&t| t != i)
+            .map(|t| documents[t].file_path.clone())
+            .collect();
+        updates.push((i, content, links));
+    }
+
+    for (i, content, links) in updates {
+        documents[i].content = content;
+        documents[i].links = links;
+    }
+    (documents, unresolved)
+}
+
+/// Lookup tables over the document set
+struct LinkIndex<'a> {
+    documents: &'a [Document],
+    by_path: HashMap<&'a str, usize>,
+    by_key: HashMap<(String, &'a str), usize>,
+    by_name: HashMap<String, Vec<usize>>,
+}
+
+impl<'a> LinkIndex<'a> {
+    fn new(documents: &'a [Document]) -> Self {
+        let mut by_name: HashMap<String, Vec<usize>> = HashMap::new();
+        for (i, doc) in documents.iter().enumerate() {
+            let names = wiki_names(
+                &doc.file_path,
+                &doc.base_name,
+                doc.front_matter.title.as_deref(),
+            );
+            for name in names {
+                by_name.entry(name).or_default().push(i);
+            }
+        }
+        for candidates in by_name.values_mut() {
+            candidates.sort_by(|&a, &b| documents[a].file_path.cmp(&documents[b].file_path));
+            candidates.dedup();
+        }
+
+        Self {
+            documents,
+            by_path: documents
+                .iter()
+                .enumerate()
+                .map(|(i, doc)| (doc.file_path.as_str(), i))
+                .collect(),
+            by_key: documents
+                .iter()
+                .enumerate()
+                .map(|(i, doc)| ((get_translation_key(doc), doc.language.as_str()), i))
+                .collect(),
+            by_name,
+        }
+    }
+
+    /// `Ok(None)` leaves the href untouched: external URLs, plain anchors and non-Markdown files
+    fn resolve_href<'h>(
+        &self,
+        href: &'h str,
+        source: &Document,
+    ) -> Result<Option<(usize, Option<&'h str>)>, String> {
+        if href.contains("://") || href.starts_with("//") || href.starts_with("mailto:") {
+            return Ok(None);
+        }
+        let (path, anchor) = split_anchor(href);
+        let path = percent_decode_str(path).decode_utf8_lossy();
+        if !path.ends_with(".md") {
+            return Ok(None);
+        }
+
+        let target_path = match path.strip_prefix('/') {
+            Some(root_relative) => normalize(Path::new(root_relative)),
+            None => normalize(
+                &Path::new(&source.file_path)
+                    .parent()
+                    .unwrap_or_else(|| Path::new(""))
+                    .join(path.as_ref()),
+            ),
+        };
+        let Some(&linked) = target_path.as_deref().and_then(|p| self.by_path.get(p)) else {
+            return Err("links to a document that does not exist".to_string());
+        };
+        self.choose_variant(linked, source, anchor)
+            .map(|target| Some((target, anchor)))
+    }
+
+    /// Resolve `target` or `target#anchor` by document name, preferring documents
+    /// in the source's language, then in the default language
+    fn resolve_wiki_link<'t>(
+        &self,
+        target: &'t str,
+        source: &Document,
+    ) -> Result<(usize, Option<&'t str>), String> {
+        let (name, anchor) = split_anchor(target);
+        let default_language = I18nManager::default_language();
+        let linked = self
+            .by_name
+            .get(&wiki_name(name))
+            .and_then(|candidates| {
+                let in_language = |language: &str| {
+                    candidates
+                        .iter()
+                        .find(|&&i| self.documents[i].language == language)
+                };
+                in_language(&source.language)
+                    .or_else(|| in_language(default_language))
+                    .or(candidates.first())
+                    .copied()
+            })
+            .ok_or_else(|| "does not match any document title or name".to_string())?;
+        self.choose_variant(linked, source, anchor)
+            .map(|target| (target, anchor))
+    }
+
+    /// Prefer the reader's language, unless only the linked variant has the anchor
+    fn choose_variant(
+        &self,
+        linked: usize,
+        source: &Document,
+        anchor: Option<&str>,
+    ) -> Result<usize, String> {
+        let localized = self
+            .by_key
+            .get(&(
+                get_translation_key(&self.documents[linked]),
+                source.language.as_str(),
+            ))
+            .copied()
+            .unwrap_or(linked);
+        match anchor {
+            Some(anchor) if !anchor.is_empty() => {
+                if has_id(&self.documents[localized], anchor) {
+                    Ok(localized)
+                } else if has_id(&self.documents[linked], anchor) {
+                    Ok(linked)
+                } else {
+                    Err(format!(
+                        "links to a heading that does not exist in {}",
+                        self.documents[linked].file_path
+                    ))
+                }
+            }
+            _ => Ok(localized),
+        }
+    }
+
+    fn url(&self, source: &Document, target: usize, anchor: Option<&str>) -> String {
+        let mut url =
+            calculate_relative_path(&source.file_path, &document_url(&self.documents[target]));
+        if let Some(anchor) = anchor {
+            url.push('#');
+            url.push_str(anchor);
+        }
+        url
+    }
+}
+
+/// Anchors are matched percent-decoded, as pulldown-cmark encodes non-ASCII
+/// characters in hrefs (`#%C3%BCberblick` for `#überblick`)
+fn has_id(document: &Document, id: &str) -> bool {
+    let id = percent_decode_str(id).decode_utf8_lossy();
+    document.content.contains(&format!(" id=\"{id}\""))
+}
+
+/// Resolve `.` and `..` without touching the filesystem; `None` when the path
+/// escapes the content directory
+fn normalize(path: &Path) -> Option<String> {
+    let mut parts: Vec<String> = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_string_lossy().to_string()),
+            Component::ParentDir => {
+                parts.pop()?;
+            }
+            _ => {}
+        }
+    }
+    Some(parts.join("/"))
+}
+
+/// Documents linking to `document`, in file path order
+pub fn backlinks<'a>(document: &Document, documents: &'a [Document]) -> Vec<&'a Document> {
+    let mut sources: Vec<&Document> = documents
+        .iter()
+        .filter(|doc| doc.links.contains(&document.file_path))
+        .collect();
+    sources.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+    sources
+}
+
+/// Site-wide link graph: every document and the links between them
+#[derive(Debug, Serialize)]
+pub struct LinkGraph {
+    pub nodes: Vec<LinkGraphNode>,
+    pub links: Vec<LinkGraphEdge>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LinkGraphNode {
+    /// Source path, relative to the content directory
+    pub id: String,
+    pub title: Option<String>,
+    pub url: String,
+    pub language: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LinkGraphEdge {
+    pub source: String,
+    pub target: String,
+}
+
+/// Build the link graph from the links recorded by [`resolve_internal_links`]
+pub fn link_graph(documents: &[Document]) -> LinkGraph {
+    let mut sorted: Vec<&Document> = documents.iter().collect();
+    sorted.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+    LinkGraph {
+        nodes: sorted
+            .iter()
+            .map(|doc| LinkGraphNode {
+                id: doc.file_path.clone(),
+                title: doc.front_matter.title.clone(),
+                url: document_url(doc),
+                language: doc.language.clone(),
+            })
+            .collect(),
+        links: sorted
+            .iter()
+            .flat_map(|doc| {
+                doc.links.iter().map(|target| LinkGraphEdge {
+                    source: doc.file_path.clone(),
+                    target: target.clone(),
+                })
+            })
+            .collect(),
+    }
+}
+
+/// Write the link graph as JSON to `graph.json` in the output directory
+pub fn generate_link_graph(
+    documents: &[Document],
+    output_dir: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serde_json::to_string_pretty(&link_graph(documents))?;
+    let mut file = File::create(output_dir.join(LINK_GRAPH_PATH))?;
+    file.write_all(json.as_bytes())?;
+    Ok(())
+}
+
+/// Render links to other sites with the configured attributes. Links to the
+/// site's own domains, relative links and email links are left unchanged.
+pub fn apply_external_link_policy<'a>(
+    events: Vec<Event<'a>>,
+    policy: &ExternalLinksConfig,
+) -> Vec<Event<'a>> {
+    if !policy.is_enabled() {
+        return events;
+    }
+    events
+        .into_iter()
+        .map(|event| match event {
+            Event::Start(Tag::Link {
+                link_type,
+                ref dest_url,
+                ref title,
+                ..
+            }) if link_type != LinkType::Email && is_external(dest_url, policy) => {
+                Event::InlineHtml(CowStr::from(external_link_tag(dest_url, title, policy)))
+            }
+            other => other,
+        })
+        .collect()
+}
+
+/// Lowercase host of an absolute `http(s)` or protocol-relative URL
+pub fn link_host(url: &str) -> Option<String> {
+    let rest = url
+        .strip_prefix("//")
+        .or_else(|| url.split_once("://").map(|(_, rest)| rest))?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host = authority.rsplit('@').next()?;
+    let host = host.split(':').next()?;
+    if host.is_empty() {
+        None
+    } else {
+        Some(host.to_lowercase())
+    }
+}
+
+fn is_external(url: &str, policy: &ExternalLinksConfig) -> bool {
+    let lower = url.to_lowercase();
+    if !(lower.starts_with("http://") || lower.starts_with("https://") || lower.starts_with("//")) {
+        return false;
+    }
+    let Some(host) = link_host(url) else {
+        return false;
+    };
+    !policy.internal_domains.iter().any(|domain| {
+        let domain = domain.trim_start_matches('.').to_lowercase();
+        host == domain || host.ends_with(&format!(".{domain}"))
+    })
+}
+
+fn external_link_tag(url: &str, title: &str, policy: &ExternalLinksConfig) -> String {
+    let mut tag = String::from("<a href=\"");
+    let _ = escape_href(&mut tag, url);
+    tag.push('"');
+    let attributes = [
+        ("title", Some(title).filter(|t| !t.is_empty())),
+        ("class", policy.class.as_deref()),
+        ("rel", policy.rel.as_deref()),
+        ("target", policy.target.as_deref()),
+    ];
+    for (name, value) in attributes {
+        if let Some(value) = value {
+            tag.push_str(&format!(" {name}=\""));
+            let _ = escape_html(&mut tag, value);
+            tag.push('"');
+        }
+    }
+    tag.push('>');
+    tag
+}
@@ -1,20 +1,61 @@
 use crate::error::{IoError, IoErrorKind, KrikError, KrikResult, MarkdownError, MarkdownErrorKind};
-use crate::generator::ast_parser::{generate_toc_from_headings, parse_markdown_ast};
+use crate::generator::ast_parser::{
+    generate_toc_with_options, parse_markdown_ast_with_options, Footnote, MarkdownOptions,
+};
+use crate::generator::math::MathError;
 use crate::parser::{
     extract_language_from_filename, parse_markdown_with_frontmatter_for_file, Document,
 };
+use crate::site::{FootnoteStyle, IgnoreRules, SiteConfig};
 use rayon::prelude::*;
 use regex::Regex;
 use std::path::Path;
 use tracing::{debug, info, warn};
 use walkdir::WalkDir;
 
-/// Scan files in the source directory and parse markdown documents
-pub fn scan_files(source_dir: &Path, documents: &mut Vec<Document>) -> KrikResult<()> {
+/// Paragraph rendered from a `[[toc]]` line, replaced by an inline table of contents
+const TOC_MARKER: &str = "<p>[[toc]]</p>";
+
+/// HTML of a document together with the data collected while rendering it
+#[derive(Debug, Clone, Default)]
+pub struct RenderedMarkdown {
+    pub html: String,
+    /// Table of contents, empty unless requested with `toc: true`
+    pub toc: String,
+    /// Footnotes in display order
+    pub footnotes: Vec<Footnote>,
+}
+
+/// Scan files in the source directory and the mounted directories and parse
+/// markdown documents. Documents in the source directory take precedence over
+/// mounted ones with the same path.
+pub fn scan_files(
+    source_dir: &Path,
+    site_config: &SiteConfig,
+    documents: &mut Vec<Document>,
+) -> KrikResult<()> {
     info!("Starting file scan in: {}", source_dir.display());
 
-    let entries = collect_markdown_files(source_dir);
-    let results = process_files_parallel(&entries, source_dir);
+    let mut entries = collect_markdown_files(source_dir, &site_config.ignore);
+    for mount in &site_config.mounts {
+        info!(
+            "Scanning mount {} at {}",
+            mount.dir.display(),
+            mount.target_path().display()
+        );
+        entries.extend(collect_markdown_files(&mount.dir, &mount.ignore));
+    }
+    let mut results = process_files_parallel(&entries, source_dir, site_config);
+    results.dedup_by(|duplicate, kept| {
+        let is_duplicate = duplicate.0 == kept.0;
+        if is_duplicate {
+            warn!(
+                "Skipping mounted {}: the path is already taken",
+                duplicate.0
+            );
+        }
+        is_duplicate
+    });
     let scan_stats = collect_results(results, documents);
 
     info!(
@@ -27,34 +68,121 @@ pub fn scan_files(source_dir: &Path, documents: &mut Vec<Document>) -> KrikResul
 /// Convert markdown content to HTML with optional TOC generation
 /// Uses AST-based parsing for consistent heading IDs and robust processing
 pub fn markdown_to_html(markdown: &str, with_toc: bool, title: Option<&str>) -> (String, String) {
-    let result = parse_markdown_ast(markdown);
-    let toc_html = if with_toc {
-        generate_toc_from_headings(&result.headings, title)
+    let (rendered, _) = render_markdown(markdown, with_toc, title, &MarkdownOptions::default());
+    (rendered.html, rendered.toc)
+}
+
+/// Convert markdown content to HTML with optional TOC generation and per-document options
+///
+/// Fails with `MarkdownErrorKind::InvalidMath` when a math expression cannot be
+/// converted to MathML.
+pub fn markdown_to_html_with_options(
+    markdown: &str,
+    with_toc: bool,
+    title: Option<&str>,
+    options: &MarkdownOptions,
+) -> KrikResult<(String, String)> {
+    let rendered = render_markdown_checked(markdown, with_toc, title, options)?;
+    Ok((rendered.html, rendered.toc))
+}
+
+fn render_markdown_checked(
+    markdown: &str,
+    with_toc: bool,
+    title: Option<&str>,
+    options: &MarkdownOptions,
+) -> KrikResult<RenderedMarkdown> {
+    let (rendered, math_errors) = render_markdown(markdown, with_toc, title, options);
+    match math_errors.into_iter().next() {
+        Some(error) => Err(math_error(error, options)),
+        None => Ok(rendered),
+    }
+}
+
+fn render_markdown(
+    markdown: &str,
+    with_toc: bool,
+    title: Option<&str>,
+    options: &MarkdownOptions,
+) -> (RenderedMarkdown, Vec<MathError>) {
+    let result = parse_markdown_ast_with_options(markdown, options);
+
+    let mut footnotes: Vec<Footnote> = result
+        .footnotes
+        .into_values()
+        .filter(|f| f.number > 0)
+        .collect();
+    footnotes.sort_by_key(|f| f.number);
+
+    let has_marker = result.html_content.contains(TOC_MARKER);
+    let toc_html = if with_toc || has_marker {
+        generate_toc_with_options(&result.headings, title, &options.toc)
     } else {
         String::new()
     };
-    (result.html_content, toc_html)
+    let html = if has_marker {
+        result.html_content.replace(
+            TOC_MARKER,
+            &format!("<nav class=\"toc-inline\">\n{toc_html}\n</nav>"),
+        )
+    } else {
+        result.html_content
+    };
+
+    let rendered = RenderedMarkdown {
+        html,
+        toc: if with_toc { toc_html } else { String::new() },
+        footnotes,
+    };
+    (rendered, result.math_errors)
+}
+
+fn math_error(error: MathError, options: &MarkdownOptions) -> KrikError {
+    KrikError::Markdown(Box::new(MarkdownError {
+        kind: MarkdownErrorKind::InvalidMath(error.message),
+        file: options.source_path.clone().unwrap_or_default(),
+        line: Some(options.line_offset + error.line),
+        column: None,
+        context: format!("Converting math to MathML: {}", error.tex),
+    }))
 }
 
 /// Parse a single markdown file given the site `source_dir` and the file's absolute path
-pub fn parse_single_file(source_dir: &Path, path: &Path) -> KrikResult<Document> {
-    let rel_path = calculate_relative_path(source_dir, path);
+pub fn parse_single_file(
+    source_dir: &Path,
+    site_config: &SiteConfig,
+    path: &Path,
+) -> KrikResult<Document> {
+    let rel_path = site_relative_path(source_dir, site_config, path);
     let content = read_file_content(path)?;
     let (frontmatter, markdown_content) = parse_markdown_with_frontmatter_for_file(&content, path)?;
 
     validate_not_draft(&frontmatter, path)?;
 
     let (base_name, language) = extract_file_metadata(path)?;
-    let (html_content, toc_html) = process_markdown_content(&markdown_content, &frontmatter);
+    let options = MarkdownOptions {
+        content_dir: Some(source_dir.to_path_buf()),
+        source_path: Some(path.to_path_buf()),
+        source_root: Some(site_config.root_dir(source_dir, path).to_path_buf()),
+        line_offset: front_matter_lines(&content, &markdown_content),
+        ..MarkdownOptions::new(site_config, &language)
+    };
+    let rendered =
+        process_markdown_content_with_options(&markdown_content, &frontmatter, &options)?;
 
-    Ok(create_document(
+    let mut document = create_document(
         frontmatter,
-        html_content,
+        rendered.html,
         rel_path,
         language,
         base_name,
-        toc_html,
-    ))
+        rendered.toc,
+    );
+    document.footnotes = rendered.footnotes;
+    document.mount_dir = site_config
+        .mount_containing(path)
+        .map(|mount| mount.dir.clone());
+    Ok(document)
 }
 
 /// Generate table of contents and process content for TOC-enabled documents
@@ -113,12 +241,6 @@ pub fn remove_duplicate_title(content: &str, title: Option<&str>) -> String {
     content.to_string()
 }
 
-/// Process footnotes to add return navigation
-pub fn process_footnotes(content: &str) -> String {
-    // For now, just return the content as-is since pulldown-cmark handles footnotes
-    content.to_string()
-}
-
 /// Statistics for file scanning operations
 #[derive(Debug, Default)]
 pub struct ScanStats {
@@ -127,11 +249,12 @@ pub struct ScanStats {
     pub errors: usize,
 }
 
-/// Collect all markdown files from the source directory
-fn collect_markdown_files(source_dir: &Path) -> Vec<walkdir::DirEntry> {
+/// Collect all markdown files from the source directory, skipping ignored paths
+fn collect_markdown_files(source_dir: &Path, ignore: &IgnoreRules) -> Vec<walkdir::DirEntry> {
     WalkDir::new(source_dir)
         .follow_links(true)
         .into_iter()
+        .filter_entry(|e| !ignore.is_ignored(e.path()))
         .filter_map(|e| e.ok())
         .filter(|e| e.path().is_file())
         .filter(|e| e.path().extension().is_some_and(|ext| ext == "md"))
@@ -142,24 +265,31 @@ fn collect_markdown_files(source_dir: &Path) -> Vec<walkdir::DirEntry> {
 fn process_files_parallel(
     entries: &[walkdir::DirEntry],
     source_dir: &Path,
+    site_config: &SiteConfig,
 ) -> Vec<(String, Result<Document, KrikError>)> {
     let mut results: Vec<(String, Result<Document, KrikError>)> = entries
         .par_iter()
         .map(|entry| {
             let path = entry.path();
-            let rel_path = calculate_relative_path(source_dir, path);
-            let result = process_single_markdown_file(path, &rel_path);
+            let rel_path = site_relative_path(source_dir, site_config, path);
+            let result = process_single_markdown_file(source_dir, path, &rel_path, site_config);
             (rel_path, result)
         })
         .collect();
 
-    // Sort by path to keep deterministic order when pushing into documents
+    // Sort by path to keep deterministic order when pushing into documents; the
+    // sort is stable, so source directory files stay ahead of mounted duplicates
     results.sort_by(|a, b| a.0.cmp(&b.0));
     results
 }
 
 /// Process a single markdown file and return a Document
-fn process_single_markdown_file(path: &Path, rel_path: &str) -> Result<Document, KrikError> {
+fn process_single_markdown_file(
+    source_dir: &Path,
+    path: &Path,
+    rel_path: &str,
+    site_config: &SiteConfig,
+) -> Result<Document, KrikError> {
     debug!("Processing file: {}", path.display());
 
     let content = read_file_content(path)?;
@@ -168,16 +298,29 @@ fn process_single_markdown_file(path: &Path, rel_path: &str) -> Result<Document,
     validate_not_draft(&frontmatter, path)?;
 
     let (base_name, language) = extract_file_metadata(path)?;
-    let (html_content, toc_html) = process_markdown_content(&markdown_content, &frontmatter);
+    let options = MarkdownOptions {
+        content_dir: Some(source_dir.to_path_buf()),
+        source_path: Some(path.to_path_buf()),
+        source_root: Some(site_config.root_dir(source_dir, path).to_path_buf()),
+        line_offset: front_matter_lines(&content, &markdown_content),
+        ..MarkdownOptions::new(site_config, &language)
+    };
+    let rendered =
+        process_markdown_content_with_options(&markdown_content, &frontmatter, &options)?;
 
-    Ok(create_document(
+    let mut document = create_document(
         frontmatter,
-        html_content,
+        rendered.html,
         rel_path.to_string(),
         language,
         base_name,
-        toc_html,
-    ))
+        rendered.toc,
+    );
+    document.footnotes = rendered.footnotes;
+    document.mount_dir = site_config
+        .mount_containing(path)
+        .map(|mount| mount.dir.clone());
+    Ok(document)
 }
 
 /// Collect results from file processing and update documents vector
@@ -217,6 +360,14 @@ pub fn calculate_relative_path(source_dir: &Path, path: &Path) -> String {
         .unwrap_or_else(|| path.to_string_lossy().to_string())
 }
 
+/// Path of a content or mounted file relative to the site root
+fn site_relative_path(source_dir: &Path, site_config: &SiteConfig, path: &Path) -> String {
+    match site_config.site_path(source_dir, path) {
+        Some(relative) => relative.to_string_lossy().to_string(),
+        None => calculate_relative_path(source_dir, path),
+    }
+}
+
 /// Read file content with error handling
 fn read_file_content(path: &Path) -> KrikResult<String> {
     std::fs::read_to_string(path).map_err(|e| {
@@ -258,18 +409,82 @@ pub fn extract_file_metadata(path: &Path) -> KrikResult<(String, String)> {
     extract_language_from_filename(&filename_without_ext)
 }
 
+/// Number of lines before the Markdown body, so errors point at lines in the source file
+fn front_matter_lines(content: &str, markdown_content: &str) -> usize {
+    content[..content.len() - markdown_content.len()]
+        .matches('\n')
+        .count()
+}
+
 /// Process markdown content and generate HTML with optional TOC
 pub fn process_markdown_content(
     markdown_content: &str,
     frontmatter: &crate::parser::FrontMatter,
 ) -> (String, String) {
-    let with_toc = frontmatter
+    let (rendered, _) = render_markdown(
+        markdown_content,
+        toc_enabled(frontmatter),
+        frontmatter.title.as_deref(),
+        &page_options(frontmatter, &MarkdownOptions::default()),
+    );
+    (rendered.html, rendered.toc)
+}
+
+/// Process markdown content with per-document options into HTML, TOC and footnotes
+pub fn process_markdown_content_with_options(
+    markdown_content: &str,
+    frontmatter: &crate::parser::FrontMatter,
+    options: &MarkdownOptions,
+) -> KrikResult<RenderedMarkdown> {
+    render_markdown_checked(
+        markdown_content,
+        toc_enabled(frontmatter),
+        frontmatter.title.as_deref(),
+        &page_options(frontmatter, options),
+    )
+}
+
+/// Apply `toc_min`, `toc_max`, `toc_numbered` and `footnotes` front matter over the
+/// site-wide settings
+fn page_options(
+    frontmatter: &crate::parser::FrontMatter,
+    options: &MarkdownOptions,
+) -> MarkdownOptions {
+    let mut options = options.clone();
+    let level = |key: &str| {
+        frontmatter
+            .extra
+            .get(key)
+            .and_then(|v| v.as_u64())
+            .map(|v| v.clamp(1, 6) as u8)
+    };
+    if let Some(min) = level("toc_min") {
+        options.toc.min = min;
+    }
+    if let Some(max) = level("toc_max") {
+        options.toc.max = max;
+    }
+    if let Some(numbered) = frontmatter
         .extra
-        .get("toc")
+        .get("toc_numbered")
         .and_then(|v| v.as_bool())
-        .unwrap_or(false);
+    {
+        options.toc.numbered = numbered;
+    }
+    match frontmatter.extra.get("footnotes").and_then(|v| v.as_str()) {
+        Some("endnotes") => options.footnotes = FootnoteStyle::Endnotes,
+        Some("sidenotes") => options.footnotes = FootnoteStyle::Sidenotes,
+        _ => {}
+    }
+    options
+}
 
-    markdown_to_html(markdown_content, with_toc, frontmatter.title.as_deref())
+fn toc_enabled(frontmatter: &crate::parser::FrontMatter) -> bool {
+    frontmatter
+        .extra
+        .get("toc")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
 }
 
 /// Create a Document with the provided components
@@ -292,6 +507,10 @@ pub fn create_document(
         } else {
             Some(toc_html)
         },
+        footnotes: Vec::new(),
+        links: Vec::new(),
+        resources: Vec::new(),
+        mount_dir: None,
     }
 }
 
@@ -0,0 +1,136 @@
+//! Build-time conversion of `$...$` and `$$...$$` math to MathML
+
+use latex2mathml::{latex_to_mathml, DisplayStyle};
+use pulldown_cmark::{CowStr, Event};
+
+/// Delimiters accepted after `\middle`; the converter panics on anything else
+const MIDDLE_DELIMITERS: &[&str] = &[
+    "|",
+    "/",
+    "(",
+    ")",
+    "[",
+    "]",
+    "<",
+    ">",
+    ".",
+    "\\|",
+    "\\{",
+    "\\}",
+    "\\langle",
+    "\\rangle",
+    "\\lfloor",
+    "\\rfloor",
+    "\\lceil",
+    "\\rceil",
+    "\\uparrow",
+    "\\downarrow",
+    "\\updownarrow",
+    "\\Uparrow",
+    "\\Downarrow",
+    "\\mid",
+];
+
+/// A TeX expression that could not be converted
+#[derive(Debug, Clone)]
+pub struct MathError {
+    /// 1-based line in the Markdown passed to the parser
+    pub line: usize,
+    pub tex: String,
+    pub message: String,
+}
+
+/// Convert TeX to a MathML `<math>` element
+pub fn tex_to_mathml(tex: &str, display: bool) -> Result<String, String> {
+    let style = if display {
+        DisplayStyle::Block
+    } else {
+        DisplayStyle::Inline
+    };
+    // The converter panics on some malformed input: reject what is known to
+    // trigger that up front, and still turn any other panic into an error
+    check_middle_delimiters(tex)?;
+    let mathml = std::panic::catch_unwind(|| latex_to_mathml(tex.trim(), style))
+        .map_err(|_| "unsupported or malformed expression".to_string())?
+        .map_err(|e| e.to_string())?;
+
+    // Unknown commands and missing arguments are embedded in the output instead
+    match mathml.split_once("[PARSE ERROR: ") {
+        Some((_, rest)) => Err(rest.split(']').next().unwrap_or(rest).to_string()),
+        None => Ok(mathml),
+    }
+}
+
+/// Check that every `\middle` is followed by a delimiter, optionally in braces
+fn check_middle_delimiters(tex: &str) -> Result<(), String> {
+    let mut rest = tex;
+    while let Some(start) = rest.find('\\') {
+        let command = command_at(&rest[start..]);
+        rest = &rest[start + command.len()..];
+        if command != "\\middle" {
+            continue;
+        }
+        let argument = rest.trim_start();
+        let delimiter = match argument.strip_prefix('{') {
+            Some(group) => group.split_once('}').map_or("", |(inner, _)| inner.trim()),
+            None if argument.starts_with('\\') => command_at(argument),
+            None => argument.get(..1).unwrap_or(""),
+        };
+        if !MIDDLE_DELIMITERS.contains(&delimiter) {
+            return Err(format!(
+                "\\middle must be followed by a delimiter such as |, found '{delimiter}'"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The command at the start of `input`: a backslash followed by letters, or by
+/// one other character (`\\`, `\|`)
+fn command_at(input: &str) -> &str {
+    let name = &input[1..];
+    let len = name
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(name.len());
+    let len = if len == 0 {
+        name.chars().next().map_or(0, char::len_utf8)
+    } else {
+        len
+    };
+    &input[..1 + len]
+}
+
+/// Replace a math event with its MathML rendering.
+///
+/// Invalid TeX is recorded in `errors` and rendered as an escaped
+/// `<span class="math-error">` so the rest of the page still renders.
+pub fn render_math_event<'a>(
+    event: Event<'a>,
+    markdown: &str,
+    offset: usize,
+    errors: &mut Vec<MathError>,
+) -> Event<'a> {
+    let (tex, display) = match &event {
+        Event::InlineMath(tex) => (tex, false),
+        Event::DisplayMath(tex) => (tex, true),
+        _ => return event,
+    };
+
+    match tex_to_mathml(tex, display) {
+        Ok(mathml) => Event::InlineHtml(CowStr::from(mathml)),
+        Err(message) => {
+            errors.push(MathError {
+                line: markdown[..offset].matches('\n').count() + 1,
+                tex: tex.to_string(),
+                message,
+            });
+            let delimiter = if display { "$$" } else { "$" };
+            Event::InlineHtml(CowStr::from(format!(
+                "<span class=\"math-error\">{delimiter}{}{delimiter}</span>",
+                tex.replace('&', "&amp;")
+                    .replace('<', "&lt;")
+                    .replace('>', "&gt;")
+            )))
+        }
+    }
+}
@@ -0,0 +1,409 @@
+//! CSS/JS/HTML minification and bundling
+//!
+//! Stylesheets and scripts are minified when `minify` is set in the `[assets]`
+//! table, and theme assets can be concatenated into bundles listed in
+//! `theme.toml`. Development builds (`kk server`) skip minification and give
+//! bundles a line-based source map pointing back to their member files instead.
+//! Rendered pages are minified when `minify` is set in the `[html]` table.
+
+use crate::site::AssetConfig;
+use minifier::js::{self, Operation, ReservedChar, Token, Tokens};
+use std::path::Path;
+use tracing::warn;
+
+/// Extensions of files that are minified
+pub const MINIFIABLE_EXTENSIONS: &[&str] = &["css", "js"];
+
+/// Elements whose contents are copied verbatim by the HTML minifier
+const RAW_ELEMENTS: &[&str] = &["pre", "code", "textarea", "script", "style"];
+
+/// Elements around which whitespace is never rendered
+const BLOCK_ELEMENTS: &[&str] = &[
+    "!doctype",
+    "address",
+    "article",
+    "aside",
+    "base",
+    "blockquote",
+    "body",
+    "caption",
+    "col",
+    "colgroup",
+    "dd",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "li",
+    "link",
+    "main",
+    "meta",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "summary",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "title",
+    "tr",
+    "ul",
+];
+
+/// Base64 digits used by source map VLQ encoding
+const BASE64_DIGITS: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// How stylesheets and scripts are processed in a build
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AssetProcessing {
+    /// Minify CSS and JS files
+    pub minify: bool,
+    /// Write source maps for theme bundles
+    pub source_maps: bool,
+}
+
+impl AssetProcessing {
+    /// Minify as configured in production builds; write source maps instead in
+    /// development builds
+    pub fn new(config: &AssetConfig, dev: bool) -> Self {
+        Self {
+            minify: config.minify && !dev,
+            source_maps: dev,
+        }
+    }
+}
+
+/// Return true for CSS and JS files that are not already minified (`*.min.js`)
+pub fn is_minifiable(path: &Path) -> bool {
+    let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    let lower = file_name.to_lowercase();
+    MINIFIABLE_EXTENSIONS
+        .iter()
+        .any(|ext| lower.ends_with(&format!(".{ext}")) && !lower.ends_with(&format!(".min.{ext}")))
+}
+
+/// Minify a stylesheet or script; the source is returned unchanged (with a
+/// warning) when it cannot be tokenized
+pub fn minify(path: &Path, source: &str) -> String {
+    let is_css = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("css"));
+    let minified = if is_css {
+        minifier::css::minify(source).map(|css| css.to_string().trim().to_string())
+    } else {
+        minify_js(source)
+    };
+    minified.unwrap_or_else(|e| {
+        warn!("Could not minify {}: {}", path.display(), e);
+        source.to_string()
+    })
+}
+
+/// Minify a script, keeping a line break wherever one may end a statement
+/// through automatic semicolon insertion (`let a = 1\nlet b = 2`)
+fn minify_js(source: &str) -> Result<String, &'static str> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut line_break = false;
+    for token in js::tokenize(source)?.into_iter() {
+        if token.is_comment() {
+            continue;
+        }
+        if let Some(c) = token.get_char().filter(|c| c.is_white_character()) {
+            line_break |= c == ReservedChar::Backline;
+            continue;
+        }
+        if line_break
+            && tokens
+                .last()
+                .is_some_and(|previous| !joins_lines(previous, &token))
+        {
+            tokens.push(Token::Char(ReservedChar::Backline));
+        }
+        line_break = false;
+        tokens.push(token);
+    }
+    Ok(Tokens::from(tokens).to_string().trim().to_string())
+}
+
+/// Return true when a line break between `previous` and `next` can never end a
+/// statement, because `previous` needs an operand or `next` continues an
+/// expression
+fn joins_lines(previous: &Token, next: &Token) -> bool {
+    use ReservedChar::*;
+    let continued = match previous {
+        Token::Char(c) => matches!(
+            c,
+            OpenParenthese
+                | OpenBracket
+                | OpenCurlyBrace
+                | Comma
+                | SemiColon
+                | Colon
+                | Dot
+                | QuestionMark
+                | EqualSign
+                | ExclamationMark
+                | Pipe
+                | Ampersand
+                | LessThan
+                | SuperiorThan
+                | Star
+                | Slash
+                | Modulo
+        ),
+        Token::Condition(_) => true,
+        // `a++` and `a--` may end a statement
+        Token::Operation(op) => !matches!(op, Operation::Addition | Operation::Subtract),
+        _ => false,
+    };
+    let continues = match next {
+        Token::Char(c) => matches!(
+            c,
+            CloseParenthese
+                | CloseBracket
+                | CloseCurlyBrace
+                | Comma
+                | SemiColon
+                | Colon
+                | Dot
+                | QuestionMark
+                | EqualSign
+                | Pipe
+                | Ampersand
+        ),
+        Token::Condition(_) => true,
+        Token::Operation(op) => op.is_assign() || *op == Operation::Equal,
+        _ => false,
+    };
+    continued || continues
+}
+
+/// A bundle of concatenated files
+pub struct Bundle {
+    /// Concatenated contents, ending with a `sourceMappingURL` comment when a
+    /// source map was requested
+    pub contents: String,
+    /// Source map (version 3) mapping each line back to its member file
+    pub source_map: Option<String>,
+}
+
+/// Concatenate `members` (path relative to the bundle's directory, contents) into
+/// the bundle `name`, optionally with a source map written next to it as
+/// `<name>.map`
+pub fn bundle(name: &str, members: &[(String, String)], source_map: bool) -> Bundle {
+    let mut contents = String::new();
+    let mut mappings = Vec::new();
+    for (index, (_, source)) in members.iter().enumerate() {
+        for line in 0..source.lines().count() {
+            mappings.push((index, line));
+        }
+        contents.push_str(source);
+        if !source.is_empty() && !source.ends_with('\n') {
+            contents.push('\n');
+        }
+    }
+    if !source_map {
+        return Bundle {
+            contents,
+            source_map: None,
+        };
+    }
+
+    let file_name = name.rsplit('/').next().unwrap_or(name);
+    if file_name.ends_with(".css") {
+        contents.push_str(&format!("/*# sourceMappingURL={file_name}.map */\n"));
+    } else {
+        contents.push_str(&format!("//# sourceMappingURL={file_name}.map\n"));
+    }
+    let map = serde_json::json!({
+        "version": 3,
+        "file": file_name,
+        "sources": members.iter().map(|(path, _)| path).collect::<Vec<_>>(),
+        "names": [],
+        "mappings": encode_mappings(&mappings),
+    });
+    Bundle {
+        contents,
+        source_map: Some(map.to_string()),
+    }
+}
+
+/// Encode one segment per generated line, mapping its first column to the start
+/// of `(source index, source line)`
+fn encode_mappings(lines: &[(usize, usize)]) -> String {
+    let mut mappings = String::new();
+    let (mut previous_source, mut previous_line) = (0i64, 0i64);
+    for (generated, &(source, line)) in lines.iter().enumerate() {
+        if generated > 0 {
+            mappings.push(';');
+        }
+        let (source, line) = (source as i64, line as i64);
+        encode_vlq(0, &mut mappings);
+        encode_vlq(source - previous_source, &mut mappings);
+        encode_vlq(line - previous_line, &mut mappings);
+        encode_vlq(0, &mut mappings);
+        previous_source = source;
+        previous_line = line;
+    }
+    mappings
+}
+
+/// Base64 VLQ encoding of a signed value
+fn encode_vlq(value: i64, out: &mut String) {
+    let mut vlq = if value < 0 {
+        ((-value) << 1) | 1
+    } else {
+        value << 1
+    };
+    loop {
+        let mut digit = vlq & 0b11111;
+        vlq >>= 5;
+        if vlq > 0 {
+            digit |= 0b100000;
+        }
+        out.push(BASE64_DIGITS[digit as usize] as char);
+        if vlq == 0 {
+            break;
+        }
+    }
+}
+
+/// Minify rendered HTML without changing how it displays.
+///
+/// Comments (except conditional comments) are dropped, whitespace runs collapse
+/// to one space and whitespace next to block-level tags is removed. Tags are
+/// kept as written, and the contents of `<pre>`, `<code>`, `<textarea>`,
+/// `<script>` and `<style>` are left untouched.
+pub fn minify_html(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    // A whitespace run is pending, and whether the last token was a block-level tag
+    let mut space = false;
+    let mut after_block = true;
+
+    while let Some(first) = rest.chars().next() {
+        if first.is_ascii_whitespace() {
+            space = true;
+            rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace());
+            continue;
+        }
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment.find("-->").map_or(rest.len(), |i| i + 7);
+            if rest.starts_with("<!--[") {
+                if space && !after_block {
+                    out.push(' ');
+                }
+                space = false;
+                out.push_str(&rest[..end]);
+                after_block = false;
+            }
+            rest = &rest[end..];
+            continue;
+        }
+
+        if let Some((name, closing, len)) = parse_tag(rest) {
+            let block = BLOCK_ELEMENTS.contains(&name.as_str());
+            if space && !block && !after_block {
+                out.push(' ');
+            }
+            space = false;
+            let tag = &rest[..len];
+            out.push_str(tag);
+            rest = &rest[len..];
+            after_block = block;
+
+            if !closing && !tag.ends_with("/>") && RAW_ELEMENTS.contains(&name.as_str()) {
+                let end = find_closing_tag(rest, &name).unwrap_or(rest.len());
+                if end > 0 {
+                    out.push_str(&rest[..end]);
+                    rest = &rest[end..];
+                    after_block = false;
+                }
+            }
+            continue;
+        }
+
+        // Text up to the next whitespace or tag
+        if space && !after_block {
+            out.push(' ');
+        }
+        space = false;
+        let start = first.len_utf8();
+        let end = rest[start..]
+            .find(|c: char| c == '<' || c.is_ascii_whitespace())
+            .map_or(rest.len(), |i| start + i);
+        out.push_str(&rest[..end]);
+        rest = &rest[end..];
+        after_block = false;
+    }
+    out
+}
+
+/// Byte offset of the first `</name>` in `input`, matching the name case-insensitively
+/// and only when it is followed by `>`, whitespace or `/` (so `</pre` does not match
+/// `</prefix>`)
+fn find_closing_tag(input: &str, name: &str) -> Option<usize> {
+    input.match_indices("</").map(|(i, _)| i).find(|&i| {
+        let after = &input.as_bytes()[i + 2..];
+        after.len() > name.len()
+            && after[..name.len()].eq_ignore_ascii_case(name.as_bytes())
+            && matches!(
+                after[name.len()],
+                b'>' | b'/' | b' ' | b'\t' | b'\n' | b'\r' | b'\x0c'
+            )
+    })
+}
+
+/// Lowercase name, closing flag and byte length of the tag at the start of `input`
+fn parse_tag(input: &str) -> Option<(String, bool, usize)> {
+    let after = input.strip_prefix('<')?;
+    let (closing, after) = match after.strip_prefix('/') {
+        Some(after) => (true, after),
+        None => (false, after),
+    };
+    let name_len = after
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '!' || c == '-'))
+        .unwrap_or(after.len());
+    let name = &after[..name_len];
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '!') {
+        return None;
+    }
+
+    let mut quote = None;
+    for (i, c) in input.char_indices().skip(1) {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '>' => return Some((name.to_ascii_lowercase(), closing, i + 1)),
+            None => {}
+        }
+    }
+    None
+}
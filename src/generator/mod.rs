@@ -4,24 +4,46 @@
 //!
 //! - `core`: Main SiteGenerator struct and orchestration
 //! - `markdown`: Markdown processing and content parsing  
+//! - `math`: TeX math to MathML conversion
 //! - `ast_parser`: AST-based parsing for TOC and footnotes
 //! - `assets`: Asset copying and file management
+//! - `compress`: Precompressed gzip and brotli siblings of text output
+//! - `bundles`: Page bundles with co-located resources
+//! - `code_blocks`: Fenced code block attributes, includes and line markup
 //! - `templates`: HTML template rendering and page generation
 //! - `feeds`: Atom feed generation
+//! - `footnotes`: Footnote numbering, backlinks and sidenotes
+//! - `highlight`: Build-time syntax highlighting for code blocks
+//! - `image_metadata`: Stripping of EXIF/XMP metadata and GPS detection in images
+//! - `images`: Responsive image variants with `srcset` and a content-hash cache
+//! - `links`: Rewriting of links between Markdown documents to output URLs
+//! - `minify`: CSS/JS/HTML minification and theme asset bundles with source maps
 //! - `sitemap`: XML sitemap generation
 //! - `robots`: robots.txt generation
 //! - `pdf`: PDF generation using pandoc and typst
+//! - `typography`: Locale-aware quotes, dashes and spacing
 
 pub mod assets;
 pub mod ast_parser;
+pub mod bundles;
+pub mod code_blocks;
+pub mod compress;
 pub mod core;
 pub mod feeds;
+pub mod footnotes;
+pub mod highlight;
+pub mod image_metadata;
+pub mod images;
+pub mod links;
 pub mod markdown;
+pub mod math;
+pub mod minify;
 pub mod pdf;
 pub mod pipeline;
 pub mod robots;
 pub mod sitemap;
 pub mod templates;
+pub mod typography;
 
 // Re-export the main SiteGenerator for backwards compatibility
 pub use core::SiteGenerator;
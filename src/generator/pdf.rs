@@ -1,6 +1,9 @@
 use crate::error::{
     GenerationError, GenerationErrorKind, IoError, IoErrorKind, KrikError, KrikResult,
 };
+use crate::generator::ast_parser::callouts_to_markdown;
+use crate::generator::code_blocks::expand_includes;
+use crate::generator::typography::localize_markdown;
 use crate::parser::Document;
 use crate::site::SiteConfig;
 use chrono::Utc;
@@ -90,13 +93,16 @@ impl PdfGenerator {
         )?;
 
         // Run pandoc with typst engine on the temporary file
-        let mut cmd = Command::new(&self.pandoc_path.clone().unwrap());
+        let mut cmd = Command::new(self.pandoc_path.clone().unwrap());
         cmd.arg(&temp_md_file)
-            .arg("--from=gfm")
+            // Same $...$ / $$...$$ math syntax as the HTML pipeline
+            .arg("--from=gfm+tex_math_dollars")
             .arg("--pdf-engine=typst")
             .arg("--output")
             .arg(output_path)
             .arg("--standalone")
+            .arg("--metadata")
+            .arg(format!("lang={document_language}"))
             .current_dir(source_root);
 
         // Execute pandoc
@@ -156,11 +162,20 @@ impl PdfGenerator {
             }
         }
 
-        // Add the main content
-        filtered_content.push_str(&content_with_fixed_paths);
+        // Add the main content with the document language's typography and callout titles
+        let content_with_includes = expand_includes(
+            &content_with_fixed_paths,
+            input_path,
+            site_config.root_dir(source_root, input_path),
+        );
+        let content_with_callouts = callouts_to_markdown(&content_with_includes, document_language);
+        filtered_content.push_str(&localize_markdown(
+            &content_with_callouts,
+            document_language,
+        ));
 
         // Add appendix with download information (only if base_url is configured)
-        if let Some(base_url) = site_config.get_base_url() {
+        if let Some(base_url) = site_config.for_language(document_language).get_base_url() {
             let absolute_pdf_url = self.generate_absolute_pdf_url(output_path, &base_url);
 
             filtered_content.push_str("\n\n---\n\n");
@@ -407,7 +422,7 @@ impl PdfGenerator {
         let results: Mutex<Vec<PathBuf>> = Mutex::new(Vec::with_capacity(pdf_documents.len()));
 
         pdf_documents.par_iter().for_each(|document| {
-            let input_path = source_dir.join(&document.file_path);
+            let input_path = site_config.content_root(source_dir, document).file();
             let output_path = self.determine_pdf_output_path(document, output_dir);
 
             match self.generate_pdf_from_file(
@@ -1,20 +1,26 @@
-use crate::error::{GenerationError, GenerationErrorKind, KrikError, KrikResult};
+use crate::error::{
+    GenerationError, GenerationErrorKind, IoError, IoErrorKind, KrikError, KrikResult,
+};
+use crate::generator::minify::AssetProcessing;
 use crate::parser::Document;
 use crate::site::SiteConfig;
 use crate::theme::Theme;
 use chrono::{DateTime, Utc};
 use std::path::Path;
+use tracing::warn;
 
 /// Phase: scan the content directory and build the in-memory document list
 pub struct ScanPhase;
 
 impl ScanPhase {
-    pub fn scan(&self, source_dir: &Path) -> KrikResult<Vec<Document>> {
+    pub fn scan(&self, source_dir: &Path, site_config: &SiteConfig) -> KrikResult<Vec<Document>> {
         let mut documents = Vec::new();
-        super::markdown::scan_files(source_dir, &mut documents).map_err(|e| match e {
-            KrikError::Generation(gen_err) => KrikError::Generation(gen_err),
-            other => other,
-        })?;
+        super::markdown::scan_files(source_dir, site_config, &mut documents).map_err(
+            |e| match e {
+                KrikError::Generation(gen_err) => KrikError::Generation(gen_err),
+                other => other,
+            },
+        )?;
         Ok(documents)
     }
 }
@@ -24,24 +30,58 @@ pub struct TransformPhase;
 
 impl TransformPhase {
     /// Apply non-rendering transformations and return new immutable documents
-    /// Currently: set missing dates from file modification time when available
-    pub fn transform(&self, documents: Vec<Document>, source_dir: &Path) -> Vec<Document> {
+    /// Currently: set missing dates from file modification time when available and
+    /// list the resources of page bundles
+    pub fn transform(
+        &self,
+        documents: Vec<Document>,
+        source_dir: &Path,
+        site_config: &SiteConfig,
+    ) -> Vec<Document> {
         documents
             .into_iter()
             .map(|mut doc| {
+                let root = site_config.content_root(source_dir, &doc);
+                let file = root.file();
+                let resources = super::bundles::bundle_resources(root.dir, root.path, root.ignore);
                 if doc.front_matter.date.is_none() {
-                    let file_path = source_dir.join(&doc.file_path);
-                    if let Ok(metadata) = std::fs::metadata(&file_path) {
+                    if let Ok(metadata) = std::fs::metadata(file) {
                         if let Ok(modified) = metadata.modified() {
                             let dt: DateTime<Utc> = modified.into();
                             doc.front_matter.date = Some(dt);
                         }
                     }
                 }
+                doc.resources = resources;
                 doc
             })
             .collect()
     }
+
+    /// Rewrite links to `.md` files and wiki-links into output URLs. Unresolved links
+    /// are logged as warnings, or fail the build when `strict` is set.
+    pub fn resolve_links(
+        &self,
+        documents: Vec<Document>,
+        strict: bool,
+    ) -> KrikResult<Vec<Document>> {
+        let (documents, unresolved) = super::links::resolve_internal_links(documents);
+        if unresolved.is_empty() {
+            return Ok(documents);
+        }
+        if strict {
+            return Err(KrikError::Generation(Box::new(GenerationError {
+                kind: GenerationErrorKind::BrokenLinks(
+                    unresolved.iter().map(|link| link.to_string()).collect(),
+                ),
+                context: "Resolving links between documents (--strict)".to_string(),
+            })));
+        }
+        for link in &unresolved {
+            warn!("Broken internal link in {}", link);
+        }
+        Ok(documents)
+    }
 }
 
 /// Phase: render HTML for pages and index using the theme
@@ -107,9 +147,59 @@ impl EmitPhase {
         &self,
         source_dir: &Path,
         theme: &Theme,
+        site_config: &SiteConfig,
+        processing: &AssetProcessing,
         output_dir: &Path,
     ) -> KrikResult<()> {
-        super::assets::copy_non_markdown_files(source_dir, output_dir).map_err(|e| {
+        // Static files first, so content files at the same path take precedence
+        let static_dir = super::assets::static_dir(source_dir);
+        super::assets::copy_static_files(&static_dir, output_dir, &site_config.ignore).map_err(
+            |e| {
+                KrikError::Generation(Box::new(GenerationError {
+                    kind: GenerationErrorKind::AssetCopyError {
+                        source: static_dir.clone(),
+                        target: output_dir.to_path_buf(),
+                        error: std::io::Error::new(
+                            std::io::ErrorKind::Other,
+                            format!("Static file copy failed: {e}"),
+                        ),
+                    },
+                    context: "Copying static files".to_string(),
+                }))
+            },
+        )?;
+
+        let options = super::assets::CopyOptions {
+            strip_metadata: site_config.images.strip_metadata,
+            minify: processing.minify,
+        };
+
+        // Mounted files next, so content files at the same path take precedence
+        for mount in &site_config.mounts {
+            let target_dir = output_dir.join(mount.target_path());
+            super::assets::copy_non_markdown_files(&mount.dir, &target_dir, options, &mount.ignore)
+                .map_err(|e| {
+                    KrikError::Generation(Box::new(GenerationError {
+                        kind: GenerationErrorKind::AssetCopyError {
+                            source: mount.dir.clone(),
+                            target: target_dir.clone(),
+                            error: std::io::Error::new(
+                                std::io::ErrorKind::Other,
+                                format!("Mounted asset copy failed: {e}"),
+                            ),
+                        },
+                        context: "Copying mounted assets".to_string(),
+                    }))
+                })?;
+        }
+
+        super::assets::copy_non_markdown_files(
+            source_dir,
+            output_dir,
+            options,
+            &site_config.ignore,
+        )
+        .map_err(|e| {
             KrikError::Generation(Box::new(GenerationError {
                 kind: GenerationErrorKind::AssetCopyError {
                     source: source_dir.to_path_buf(),
@@ -138,6 +228,60 @@ impl EmitPhase {
         })
     }
 
+    /// Write resized variants of content and mounted images when `[images]` is enabled
+    pub fn emit_images(
+        &self,
+        source_dir: &Path,
+        site_config: &SiteConfig,
+        output_dir: &Path,
+    ) -> KrikResult<()> {
+        for mount in &site_config.mounts {
+            super::images::process_images(
+                &mount.dir,
+                &output_dir.join(mount.target_path()),
+                &super::images::mount_config(source_dir, &site_config.images),
+                &mount.ignore,
+            )?;
+        }
+        super::images::process_images(
+            source_dir,
+            output_dir,
+            &site_config.images,
+            &site_config.ignore,
+        )
+    }
+
+    /// Write the syntax highlighting stylesheet when build-time highlighting is enabled
+    pub fn emit_highlight_css(
+        &self,
+        site_config: &SiteConfig,
+        output_dir: &Path,
+    ) -> KrikResult<()> {
+        if !site_config.highlight.enabled {
+            return Ok(());
+        }
+        let css_path = output_dir.join(super::highlight::HIGHLIGHT_CSS_PATH);
+        if let Some(parent) = css_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                KrikError::Generation(Box::new(GenerationError {
+                    kind: GenerationErrorKind::OutputDirError(e),
+                    context: format!("Creating directory: {}", parent.display()),
+                }))
+            })?;
+        }
+        std::fs::write(
+            &css_path,
+            super::highlight::highlight_css(&site_config.highlight),
+        )
+        .map_err(|e| {
+            KrikError::Io(Box::new(IoError {
+                kind: IoErrorKind::WriteFailed(e),
+                path: css_path.clone(),
+                context: "Writing syntax highlighting stylesheet".to_string(),
+            }))
+        })
+    }
+
     pub fn emit_feed(
         &self,
         documents: &[Document],
@@ -168,6 +312,19 @@ impl EmitPhase {
         })
     }
 
+    /// Write the JSON graph of links between documents
+    pub fn emit_link_graph(&self, documents: &[Document], output_dir: &Path) -> KrikResult<()> {
+        super::links::generate_link_graph(documents, output_dir).map_err(|e| {
+            KrikError::Generation(Box::new(GenerationError {
+                kind: GenerationErrorKind::OutputDirError(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    format!("Link graph generation failed: {e}"),
+                )),
+                context: "Generating JSON link graph".to_string(),
+            }))
+        })
+    }
+
     pub fn emit_robots(&self, site_config: &SiteConfig, output_dir: &Path) -> KrikResult<()> {
         super::robots::generate_robots(site_config, output_dir).map_err(|e| {
             KrikError::Generation(Box::new(GenerationError {
@@ -1,3 +1,4 @@
+use crate::generator::templates::paths::get_translation_key;
 use crate::parser::Document;
 use crate::site::SiteConfig;
 use chrono::Utc;
@@ -12,8 +13,8 @@ pub fn generate_sitemap(
     site_config: &SiteConfig,
     output_dir: &Path,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    // Group documents by base name to find language variants
-    let document_groups = group_documents_by_base_name(documents);
+    // Group documents by translation key to find language variants
+    let document_groups = group_documents_by_translation_key(documents);
     let sitemap_content = generate_sitemap_xml(documents, &document_groups, site_config)?;
 
     // Write sitemap file
@@ -58,22 +59,20 @@ fn generate_sitemap_xml(
         sitemap.push_str("  </url>\n");
     }
 
-    // Add document entries (one per base name, not per language)
-    let mut processed_base_names: HashSet<String> = HashSet::new();
+    // Add document entries (one per translation key, not per language)
+    let mut processed_keys: HashSet<String> = HashSet::new();
 
     for document in documents {
-        if should_include_in_sitemap(document)
-            && !processed_base_names.contains(&document.base_name)
-        {
-            processed_base_names.insert(document.base_name.clone());
-
-            // Get all language variants for this base name
-            if let Some(language_variants) = document_groups.get(&document.base_name) {
+        let key = get_translation_key(document);
+        if should_include_in_sitemap(document) && !processed_keys.contains(&key) {
+            // Get all language variants for this translation key
+            if let Some(language_variants) = document_groups.get(&key) {
                 sitemap.push_str(&generate_sitemap_entry_for_group(
                     language_variants,
                     site_config,
                 )?);
             }
+            processed_keys.insert(key);
         }
     }
 
@@ -82,13 +81,18 @@ fn generate_sitemap_xml(
     Ok(sitemap)
 }
 
-/// Group documents by base name to find language variants
-fn group_documents_by_base_name(documents: &[Document]) -> HashMap<String, Vec<&Document>> {
+/// Group documents by translation key to find language variants
+pub fn group_documents_by_translation_key(
+    documents: &[Document],
+) -> HashMap<String, Vec<&Document>> {
     let mut groups: HashMap<String, Vec<&Document>> = HashMap::new();
 
     for doc in documents {
         if should_include_in_sitemap(doc) {
-            groups.entry(doc.base_name.clone()).or_default().push(doc);
+            groups
+                .entry(get_translation_key(doc))
+                .or_default()
+                .push(doc);
         }
     }
 
@@ -161,7 +165,7 @@ fn generate_document_url(document: &Document, site_config: &SiteConfig) -> Strin
     let mut path = std::path::PathBuf::from(&document.file_path);
     path.set_extension("html");
 
-    if let Some(ref base_url) = site_config.base_url {
+    if let Some(base_url) = site_config.for_language(&document.language).base_url {
         format!(
             "{}/{}",
             base_url.trim_end_matches('/'),
@@ -5,25 +5,37 @@ use serde_json::json;
 use std::collections::HashMap;
 use tera::Context;
 
-use super::paths::{calculate_relative_path, get_base_path};
+use super::paths::{calculate_relative_path, get_translation_key};
 
+/// Add site-wide variables, resolved with the `[languages.<code>]` overrides for `language`
 pub fn add_site_context(
     context: &mut Context,
     site_config: &SiteConfig,
     language: &str,
     file_path: &str,
 ) {
+    let site_config = site_config.for_language(language);
     context.insert("site_title", &site_config.get_site_title());
+    if let Some(ref description) = site_config.description {
+        context.insert("site_description", description);
+    }
     context.insert("file_path", file_path);
     if let Some(ref base_url) = site_config.base_url {
         context.insert("base_url", base_url);
     }
     let assets_path = calculate_relative_path(file_path, "/assets");
     let home_path = calculate_relative_path(file_path, "/index.html");
-    let feed_path = calculate_relative_path(file_path, "/feed.xml");
+    let feed_path = calculate_relative_path(file_path, &feed_file_path(&site_config, language));
     context.insert("assets_path", &assets_path);
     context.insert("home_path", &home_path);
     context.insert("feed_path", &feed_path);
+    if site_config.highlight.enabled {
+        let highlight_css_path = calculate_relative_path(
+            file_path,
+            &format!("/{}", crate::generator::highlight::HIGHLIGHT_CSS_PATH),
+        );
+        context.insert("highlight_css_path", &highlight_css_path);
+    }
     context.insert("lang", language);
 }
 
@@ -42,10 +54,10 @@ pub fn add_language_context(
     document: &Document,
     all_documents: &[Document],
 ) {
-    let base_path = get_base_path(std::path::Path::new(&document.file_path));
+    let translation_key = get_translation_key(document);
     let mut available_translations: Vec<_> = all_documents
         .iter()
-        .filter(|doc| get_base_path(std::path::Path::new(&doc.file_path)) == base_path)
+        .filter(|doc| get_translation_key(doc) == translation_key)
         .map(|doc| {
             let mut translation = HashMap::new();
             translation.insert("lang", doc.language.clone());
@@ -86,29 +98,103 @@ pub fn add_sidebar_context(context: &mut Context, all_documents: &[Document]) {
     context.insert("sidebar_pages", &pages);
 }
 
+/// Add the page navigation for `language`.
+///
+/// Each page is linked in `language` when translated; otherwise the default-language
+/// version is used and its link is flagged with `not_translated = "true"`.
 pub fn add_page_links_context(
     context: &mut Context,
     all_documents: &[Document],
     current_file_path: &str,
+    language: &str,
 ) {
-    let mut filtered_docs: Vec<_> = all_documents
-        .iter()
-        .filter(|doc| !is_post(doc) && doc.language == "en")
-        .collect();
+    let default_lang = I18nManager::default_language();
+    let mut chosen: HashMap<String, &Document> = HashMap::new();
+    for doc in all_documents.iter().filter(|doc| !is_post(doc)) {
+        if doc.language != language && doc.language != default_lang {
+            continue;
+        }
+        let key = get_translation_key(doc);
+        match chosen.get(&key) {
+            Some(existing) if existing.language == language => {}
+            _ => {
+                chosen.insert(key, doc);
+            }
+        }
+    }
+    let mut filtered_docs: Vec<_> = chosen.into_values().collect();
     filtered_docs.sort_by(|a, b| {
         a.front_matter
             .title
             .as_deref()
             .unwrap_or("")
             .cmp(b.front_matter.title.as_deref().unwrap_or(""))
+            .then_with(|| a.file_path.cmp(&b.file_path))
     });
     let page_links: Vec<HashMap<String, String>> = filtered_docs
         .iter()
-        .map(|doc| create_page_link(doc, current_file_path))
+        .map(|doc| {
+            let mut link = create_page_link(doc, current_file_path);
+            let not_translated = doc.language != language;
+            link.insert("not_translated".to_string(), not_translated.to_string());
+            link
+        })
+        .collect();
+    context.insert("page_links", &page_links);
+}
+
+/// Add `backlinks`: the documents linking to this one, as `title`/`url` pairs
+pub fn add_backlinks_context(
+    context: &mut Context,
+    document: &Document,
+    all_documents: &[Document],
+) {
+    let backlinks: Vec<HashMap<String, String>> =
+        crate::generator::links::backlinks(document, all_documents)
+            .into_iter()
+            .map(|doc| create_page_link(doc, &document.file_path))
+            .collect();
+    context.insert("backlinks", &backlinks);
+}
+
+/// Replace the automatic `page_links` with the configured menu for `language`, if any
+pub fn add_menu_context(
+    context: &mut Context,
+    site_config: &SiteConfig,
+    language: &str,
+    current_file_path: &str,
+) {
+    let Some(menu) = site_config.for_language(language).menu else {
+        return;
+    };
+    let page_links: Vec<HashMap<String, String>> = menu
+        .iter()
+        .map(|item| {
+            let url = if item.url.starts_with('/') {
+                calculate_relative_path(current_file_path, &item.url)
+            } else {
+                item.url.clone()
+            };
+            let mut link = HashMap::new();
+            link.insert("title".to_string(), item.title.clone());
+            link.insert("url".to_string(), url);
+            link.insert("not_translated".to_string(), "false".to_string());
+            link
+        })
         .collect();
+    context.insert("menu", &page_links);
     context.insert("page_links", &page_links);
 }
 
+/// Site-absolute path of the Atom feed for `language`
+pub fn feed_file_path(site_config: &SiteConfig, language: &str) -> String {
+    if site_config.feed_languages().iter().any(|l| l == language) {
+        format!("/feed.{language}.xml")
+    } else {
+        "/feed.xml".to_string()
+    }
+}
+
 pub fn create_post_object(
     document: &Document,
     current_file_path: &str,
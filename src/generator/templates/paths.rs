@@ -1,3 +1,4 @@
+use crate::parser::Document;
 use pathdiff::diff_paths;
 use std::path::{Path, PathBuf};
 
@@ -46,3 +47,20 @@ pub fn get_base_path(path: &Path) -> String {
         format!("{parent}/{base_stem}")
     }
 }
+
+/// Key that links language variants of the same document.
+///
+/// Uses the `translation_key` front matter field when present, so translations can
+/// live under different filenames (e.g. `chi-siamo.it.md` and `about.md`). Falls back
+/// to the filename base path (`pages/about`) otherwise.
+pub fn get_translation_key(document: &Document) -> String {
+    document
+        .front_matter
+        .extra
+        .get("translation_key")
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| get_base_path(Path::new(&document.file_path)))
+}
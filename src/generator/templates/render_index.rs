@@ -1,4 +1,5 @@
 use crate::error::{KrikError, KrikResult, TemplateError, TemplateErrorKind};
+use crate::generator::minify::minify_html;
 use crate::i18n::I18nManager;
 use crate::parser::Document;
 use crate::site::SiteConfig;
@@ -9,8 +10,10 @@ use std::io::Write;
 use std::path::Path;
 use tera::Context;
 
-use super::context::{add_page_links_context, add_site_context, create_post_object, is_post};
-use super::paths::get_base_path;
+use super::context::{
+    add_menu_context, add_page_links_context, add_site_context, create_post_object, is_post,
+};
+use super::paths::get_translation_key;
 
 pub fn generate_index(
     documents: &[Document],
@@ -26,15 +29,18 @@ pub fn generate_index(
         "index.html",
     );
 
-    let site_description = "Latest posts and articles".to_string();
+    let site_description = site_config
+        .for_language(I18nManager::default_language())
+        .description
+        .unwrap_or_else(|| "Latest posts and articles".to_string());
     context.insert("site_description", &site_description);
 
-    // Choose one document per post base path, prefer default language if available
+    // Choose one document per translation key, prefer default language if available
     let default_lang = I18nManager::default_language();
     use std::collections::HashMap;
     let mut chosen: HashMap<String, &Document> = HashMap::new();
     for doc in documents.iter().filter(|d| is_post(d)) {
-        let base = get_base_path(Path::new(&doc.file_path));
+        let base = get_translation_key(doc);
         match chosen.get(&base) {
             None => {
                 chosen.insert(base, doc);
@@ -61,10 +67,21 @@ pub fn generate_index(
         .collect();
     context.insert("posts", &posts);
 
-    add_page_links_context(&mut context, documents, "index.html");
+    add_page_links_context(
+        &mut context,
+        documents,
+        "index.html",
+        I18nManager::default_language(),
+    );
+    add_menu_context(
+        &mut context,
+        site_config,
+        I18nManager::default_language(),
+        "index.html",
+    );
 
     // Rendering can be parallelized if needed, but index is a single template render.
-    let rendered = theme
+    let mut rendered = theme
         .templates
         .render("index.html", &context)
         .map_err(|e| {
@@ -74,6 +91,9 @@ pub fn generate_index(
                 context: "Rendering index page".to_string(),
             }))
         })?;
+    if site_config.html.minify {
+        rendered = minify_html(&rendered);
+    }
     let index_path = output_dir.join("index.html");
     let mut file = File::create(&index_path)?;
     file.write_all(rendered.as_bytes())?;
@@ -1,4 +1,5 @@
 use crate::error::{KrikError, KrikResult, TemplateError, TemplateErrorKind};
+use crate::generator::minify::minify_html;
 use crate::parser::Document;
 use crate::site::SiteConfig;
 use crate::theme::Theme;
@@ -8,8 +9,8 @@ use std::path::Path;
 use tera::Context;
 
 use super::context::{
-    add_language_context, add_navigation_context, add_page_links_context, add_sidebar_context,
-    add_site_context, generate_description,
+    add_backlinks_context, add_language_context, add_menu_context, add_navigation_context,
+    add_page_links_context, add_sidebar_context, add_site_context, generate_description,
 };
 use super::paths::determine_output_path;
 use super::select::determine_template_name;
@@ -50,7 +51,10 @@ pub fn generate_page(
     output_dir: &Path,
 ) -> KrikResult<()> {
     let context = build_page_context(document, all_documents, site_config);
-    let rendered_content = render_template(theme, document, &context)?;
+    let mut rendered_content = render_template(theme, document, &context)?;
+    if site_config.html.minify {
+        rendered_content = minify_html(&rendered_content);
+    }
     write_output_file(document, output_dir, &rendered_content)
 }
 
@@ -94,7 +98,7 @@ pub fn create_base_context(document: &Document) -> Context {
     context
 }
 
-/// Add processed content (without duplicate title, with footnotes) to context
+/// Add processed content (without duplicate title) and footnotes to context
 pub fn add_processed_content(context: &mut Context, document: &Document) {
     let content_without_title = crate::generator::markdown::remove_duplicate_title(
         &document.content,
@@ -106,17 +110,12 @@ pub fn add_processed_content(context: &mut Context, document: &Document) {
         context.insert("toc", toc_html);
     }
 
-    // footnotes pass-through for now
-    let processed_content = crate::generator::markdown::process_footnotes(
-        context
-            .get("content")
-            .and_then(|v| v.as_str())
-            .unwrap_or(""),
-    );
-    context.insert("content", &processed_content);
+    // Footnotes are rendered into the content; the data is exposed for custom layouts
+    context.insert("footnotes", &document.footnotes);
+    context.insert("resources", &document.resources);
 }
 
-/// Add all context helpers (site, navigation, language, sidebar, page links)
+/// Add all context helpers (site, navigation, language, sidebar, page links, backlinks, menu)
 pub fn add_all_contexts(
     context: &mut Context,
     document: &Document,
@@ -132,7 +131,19 @@ pub fn add_all_contexts(
     add_navigation_context(context, document);
     add_language_context(context, document, all_documents);
     add_sidebar_context(context, all_documents);
-    add_page_links_context(context, all_documents, &document.file_path);
+    add_page_links_context(
+        context,
+        all_documents,
+        &document.file_path,
+        &document.language,
+    );
+    add_backlinks_context(context, document, all_documents);
+    add_menu_context(
+        context,
+        site_config,
+        &document.language,
+        &document.file_path,
+    );
 }
 
 /// Render the template with the given context
@@ -0,0 +1,206 @@
+//! Locale-aware typography applied on top of pulldown-cmark's smart punctuation
+//!
+//! Smart punctuation always produces English curly quotes. For languages with their
+//! own conventions the quotes, dashes and spacing are rewritten after parsing, using
+//! the document language detected from the filename.
+
+use pulldown_cmark::{CowStr, Event, Parser, Tag, TagEnd};
+use std::ops::Range;
+
+/// No-break space, used before `:` and around spaced dashes
+const NBSP: char = '\u{00A0}';
+/// Narrow no-break space, used before `;!?` and inside guillemets
+const NNBSP: char = '\u{202F}';
+
+/// Typographic conventions for a single language
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypographyRules {
+    pub open_quote: &'static str,
+    pub close_quote: &'static str,
+    /// French spacing: no-break spaces before `;:!?` and inside guillemets
+    pub french_spacing: bool,
+    /// Use a spaced en dash (` – `) instead of a spaced em dash (` — `)
+    pub spaced_en_dash: bool,
+}
+
+impl TypographyRules {
+    /// Rules for `language`, or `None` when the default smart punctuation already fits
+    pub fn for_language(language: &str) -> Option<Self> {
+        match language {
+            "it" => Some(Self {
+                open_quote: "«",
+                close_quote: "»",
+                french_spacing: false,
+                spaced_en_dash: true,
+            }),
+            "fr" => Some(Self {
+                open_quote: "«",
+                close_quote: "»",
+                french_spacing: true,
+                spaced_en_dash: true,
+            }),
+            "de" => Some(Self {
+                open_quote: "„",
+                close_quote: "“",
+                french_spacing: false,
+                spaced_en_dash: true,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Rewrite text that already went through smart punctuation
+    pub fn apply(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut chars = text.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '“' => {
+                    out.push_str(self.open_quote);
+                    if self.french_spacing {
+                        while chars.peek() == Some(&' ') {
+                            chars.next();
+                        }
+                        out.push(NNBSP);
+                    }
+                }
+                '”' => {
+                    if self.french_spacing {
+                        while out.ends_with(' ') {
+                            out.pop();
+                        }
+                        out.push(NNBSP);
+                    }
+                    out.push_str(self.close_quote);
+                }
+                ';' | '!' | '?' | ':'
+                    if self.french_spacing
+                        && (out.ends_with(' ')
+                            || (follows_word(&out) && ends_clause(chars.peek()))) =>
+                {
+                    if out.ends_with(' ') {
+                        out.pop();
+                    }
+                    out.push(if c == ':' { NBSP } else { NNBSP });
+                    out.push(c);
+                }
+                '—' if self.spaced_en_dash && out.ends_with(' ') && chars.peek() == Some(&' ') => {
+                    out.pop();
+                    out.push(NBSP);
+                    out.push('–');
+                }
+                _ => out.push(c),
+            }
+        }
+
+        out
+    }
+}
+
+/// Whether the text ends with a word, so punctuation written right after it
+/// (`Bonjour!`) still gets its no-break space
+fn follows_word(text: &str) -> bool {
+    text.chars()
+        .next_back()
+        .is_some_and(|c| c.is_alphanumeric() || matches!(c, '»' | ')' | ']' | '’'))
+}
+
+/// Whether punctuation followed by `next` ends a clause, rather than being part of
+/// a time, URL or query (`12:30`, `https://`, `page?id=1`)
+fn ends_clause(next: Option<&char>) -> bool {
+    next.map_or(true, |&c| {
+        c.is_whitespace() || matches!(c, ';' | ':' | '!' | '?')
+    })
+}
+
+/// Apply the typography rules for `language` to the text events of a parsed document.
+///
+/// Adjacent text events are merged first so rules that look at neighbouring characters
+/// (quotes followed by spaces, spaces before punctuation) see the whole run. Code blocks
+/// are left untouched.
+pub fn localize_events<'a>(events: Vec<Event<'a>>, language: &str) -> Vec<Event<'a>> {
+    let Some(rules) = TypographyRules::for_language(language) else {
+        return events;
+    };
+
+    let mut out = Vec::with_capacity(events.len());
+    let mut run: Option<String> = None;
+    let mut in_code_block = false;
+
+    for event in events {
+        match event {
+            Event::Text(text) if !in_code_block => {
+                run.get_or_insert_with(String::new).push_str(&text);
+            }
+            other => {
+                if let Some(text) = run.take() {
+                    out.push(Event::Text(CowStr::from(rules.apply(&text))));
+                }
+                match other {
+                    Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+                    Event::End(TagEnd::CodeBlock) => in_code_block = false,
+                    _ => {}
+                }
+                out.push(other);
+            }
+        }
+    }
+    if let Some(text) = run.take() {
+        out.push(Event::Text(CowStr::from(rules.apply(&text))));
+    }
+
+    out
+}
+
+/// Apply the typography rules for `language` to Markdown source, for renderers
+/// (such as pandoc) that do not go through the HTML pipeline.
+///
+/// Only plain text runs are rewritten; code, HTML and runs containing escapes or
+/// entities are kept verbatim so the Markdown structure cannot change.
+pub fn localize_markdown(markdown: &str, language: &str) -> String {
+    let Some(rules) = TypographyRules::for_language(language) else {
+        return markdown.to_string();
+    };
+
+    let parser = Parser::new_ext(markdown, super::ast_parser::markdown_parser_options());
+    let mut runs: Vec<(Range<usize>, String)> = Vec::new();
+    let mut in_code_block = false;
+    let mut last_was_text = false;
+
+    for (event, range) in parser.into_offset_iter() {
+        match event {
+            Event::Text(text) if !in_code_block => {
+                match runs.last_mut() {
+                    Some((run_range, run_text))
+                        if last_was_text && run_range.end == range.start =>
+                    {
+                        run_range.end = range.end;
+                        run_text.push_str(&text);
+                    }
+                    _ => runs.push((range, text.to_string())),
+                }
+                last_was_text = true;
+                continue;
+            }
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            _ => {}
+        }
+        last_was_text = false;
+    }
+
+    let mut out = String::with_capacity(markdown.len());
+    let mut cursor = 0;
+    for (range, text) in runs {
+        let source = &markdown[range.clone()];
+        if range.start < cursor || source.contains('\\') || source.contains('&') {
+            continue;
+        }
+        out.push_str(&markdown[cursor..range.start]);
+        out.push_str(&rules.apply(&text));
+        cursor = range.end;
+    }
+    out.push_str(&markdown[cursor..]);
+    out
+}
@@ -1,7 +1,9 @@
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 
-pub const DEFAULT_LANGUAGE: &'static str = "en";
+pub mod status;
+
+pub const DEFAULT_LANGUAGE: &str = "en";
 
 pub static SUPPORTED_LANGUAGES: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
     HashMap::from([
@@ -293,6 +295,336 @@ impl I18nManager {
             ("generated_at", "zu") => "Kudaliwe ngo".to_string(),
             ("generated_at", _) => "Generated at".to_string(),
 
+            // Callout note
+            ("callout_note", "af") => "Nota".to_string(),
+            ("callout_note", "am") => "ማስታወሻ".to_string(),
+            ("callout_note", "ar") => "ملاحظة".to_string(),
+            ("callout_note", "az") => "Qeyd".to_string(),
+            ("callout_note", "bg") => "Бележка".to_string(),
+            ("callout_note", "bn") => "নোট".to_string(),
+            ("callout_note", "bs") => "Napomena".to_string(),
+            ("callout_note", "ca") => "Nota".to_string(),
+            ("callout_note", "cs") => "Poznámka".to_string(),
+            ("callout_note", "cy") => "Nodyn".to_string(),
+            ("callout_note", "da") => "Bemærk".to_string(),
+            ("callout_note", "de") => "Hinweis".to_string(),
+            ("callout_note", "el") => "Σημείωση".to_string(),
+            ("callout_note", "es") => "Nota".to_string(),
+            ("callout_note", "et") => "Märkus".to_string(),
+            ("callout_note", "eu") => "Oharra".to_string(),
+            ("callout_note", "fa") => "یادداشت".to_string(),
+            ("callout_note", "fi") => "Huomautus".to_string(),
+            ("callout_note", "fr") => "Remarque".to_string(),
+            ("callout_note", "gl") => "Nota".to_string(),
+            ("callout_note", "gu") => "નોંધ".to_string(),
+            ("callout_note", "he") => "הערה".to_string(),
+            ("callout_note", "hi") => "नोट".to_string(),
+            ("callout_note", "hr") => "Napomena".to_string(),
+            ("callout_note", "hu") => "Megjegyzés".to_string(),
+            ("callout_note", "id") => "Catatan".to_string(),
+            ("callout_note", "is") => "Athugasemd".to_string(),
+            ("callout_note", "it") => "Nota".to_string(),
+            ("callout_note", "ja") => "注記".to_string(),
+            ("callout_note", "kn") => "ಟಿಪ್ಪಣಿ".to_string(),
+            ("callout_note", "ko") => "참고".to_string(),
+            ("callout_note", "lt") => "Pastaba".to_string(),
+            ("callout_note", "lv") => "Piezīme".to_string(),
+            ("callout_note", "mk") => "Забелешка".to_string(),
+            ("callout_note", "ml") => "കുറിപ്പ്".to_string(),
+            ("callout_note", "mr") => "टीप".to_string(),
+            ("callout_note", "ms") => "Nota".to_string(),
+            ("callout_note", "nl") => "Opmerking".to_string(),
+            ("callout_note", "no") => "Merk".to_string(),
+            ("callout_note", "pa") => "ਨੋਟ".to_string(),
+            ("callout_note", "pl") => "Uwaga".to_string(),
+            ("callout_note", "pt") => "Nota".to_string(),
+            ("callout_note", "ro") => "Notă".to_string(),
+            ("callout_note", "ru") => "Примечание".to_string(),
+            ("callout_note", "si") => "සටහන".to_string(),
+            ("callout_note", "sk") => "Poznámka".to_string(),
+            ("callout_note", "sl") => "Opomba".to_string(),
+            ("callout_note", "sq") => "Shënim".to_string(),
+            ("callout_note", "sr") => "Напомена".to_string(),
+            ("callout_note", "sv") => "Obs".to_string(),
+            ("callout_note", "sw") => "Kumbuka".to_string(),
+            ("callout_note", "ta") => "குறிப்பு".to_string(),
+            ("callout_note", "te") => "గమనిక".to_string(),
+            ("callout_note", "th") => "หมายเหตุ".to_string(),
+            ("callout_note", "tl") => "Tala".to_string(),
+            ("callout_note", "tr") => "Not".to_string(),
+            ("callout_note", "uk") => "Примітка".to_string(),
+            ("callout_note", "ur") => "نوٹ".to_string(),
+            ("callout_note", "vi") => "Ghi chú".to_string(),
+            ("callout_note", "xh") => "Qaphela".to_string(),
+            ("callout_note", "yo") => "Àkíyèsí".to_string(),
+            ("callout_note", "zh") => "注意".to_string(),
+            ("callout_note", "zu") => "Inothi".to_string(),
+            ("callout_note", _) => "Note".to_string(),
+
+            // Callout tip
+            ("callout_tip", "af") => "Wenk".to_string(),
+            ("callout_tip", "am") => "ጠቃሚ ምክር".to_string(),
+            ("callout_tip", "ar") => "نصيحة".to_string(),
+            ("callout_tip", "az") => "Məsləhət".to_string(),
+            ("callout_tip", "bg") => "Съвет".to_string(),
+            ("callout_tip", "bn") => "পরামর্শ".to_string(),
+            ("callout_tip", "bs") => "Savjet".to_string(),
+            ("callout_tip", "ca") => "Consell".to_string(),
+            ("callout_tip", "cs") => "Tip".to_string(),
+            ("callout_tip", "cy") => "Awgrym".to_string(),
+            ("callout_tip", "da") => "Tip".to_string(),
+            ("callout_tip", "de") => "Tipp".to_string(),
+            ("callout_tip", "el") => "Συμβουλή".to_string(),
+            ("callout_tip", "es") => "Consejo".to_string(),
+            ("callout_tip", "et") => "Nõuanne".to_string(),
+            ("callout_tip", "eu") => "Aholkua".to_string(),
+            ("callout_tip", "fa") => "نکته".to_string(),
+            ("callout_tip", "fi") => "Vinkki".to_string(),
+            ("callout_tip", "fr") => "Astuce".to_string(),
+            ("callout_tip", "gl") => "Consello".to_string(),
+            ("callout_tip", "gu") => "ટીપ".to_string(),
+            ("callout_tip", "he") => "טיפ".to_string(),
+            ("callout_tip", "hi") => "सुझाव".to_string(),
+            ("callout_tip", "hr") => "Savjet".to_string(),
+            ("callout_tip", "hu") => "Tipp".to_string(),
+            ("callout_tip", "id") => "Tips".to_string(),
+            ("callout_tip", "is") => "Ábending".to_string(),
+            ("callout_tip", "it") => "Suggerimento".to_string(),
+            ("callout_tip", "ja") => "ヒント".to_string(),
+            ("callout_tip", "kn") => "ಸಲಹೆ".to_string(),
+            ("callout_tip", "ko") => "팁".to_string(),
+            ("callout_tip", "lt") => "Patarimas".to_string(),
+            ("callout_tip", "lv") => "Padoms".to_string(),
+            ("callout_tip", "mk") => "Совет".to_string(),
+            ("callout_tip", "ml") => "നുറുങ്ങ്".to_string(),
+            ("callout_tip", "mr") => "सूचना".to_string(),
+            ("callout_tip", "ms") => "Petua".to_string(),
+            ("callout_tip", "nl") => "Tip".to_string(),
+            ("callout_tip", "no") => "Tips".to_string(),
+            ("callout_tip", "pa") => "ਸੁਝਾਅ".to_string(),
+            ("callout_tip", "pl") => "Wskazówka".to_string(),
+            ("callout_tip", "pt") => "Dica".to_string(),
+            ("callout_tip", "ro") => "Sfat".to_string(),
+            ("callout_tip", "ru") => "Совет".to_string(),
+            ("callout_tip", "si") => "ඉඟිය".to_string(),
+            ("callout_tip", "sk") => "Tip".to_string(),
+            ("callout_tip", "sl") => "Nasvet".to_string(),
+            ("callout_tip", "sq") => "Këshillë".to_string(),
+            ("callout_tip", "sr") => "Савет".to_string(),
+            ("callout_tip", "sv") => "Tips".to_string(),
+            ("callout_tip", "sw") => "Dokezo".to_string(),
+            ("callout_tip", "ta") => "உதவிக்குறிப்பு".to_string(),
+            ("callout_tip", "te") => "చిట్కా".to_string(),
+            ("callout_tip", "th") => "เคล็ดลับ".to_string(),
+            ("callout_tip", "tl") => "Tip".to_string(),
+            ("callout_tip", "tr") => "İpucu".to_string(),
+            ("callout_tip", "uk") => "Порада".to_string(),
+            ("callout_tip", "ur") => "مشورہ".to_string(),
+            ("callout_tip", "vi") => "Mẹo".to_string(),
+            ("callout_tip", "xh") => "Icebiso".to_string(),
+            ("callout_tip", "yo") => "Ìmọ̀ràn".to_string(),
+            ("callout_tip", "zh") => "提示".to_string(),
+            ("callout_tip", "zu") => "Ithiphu".to_string(),
+            ("callout_tip", _) => "Tip".to_string(),
+
+            // Callout important
+            ("callout_important", "af") => "Belangrik".to_string(),
+            ("callout_important", "am") => "አስፈላጊ".to_string(),
+            ("callout_important", "ar") => "مهم".to_string(),
+            ("callout_important", "az") => "Vacib".to_string(),
+            ("callout_important", "bg") => "Важно".to_string(),
+            ("callout_important", "bn") => "গুরুত্বপূর্ণ".to_string(),
+            ("callout_important", "bs") => "Važno".to_string(),
+            ("callout_important", "ca") => "Important".to_string(),
+            ("callout_important", "cs") => "Důležité".to_string(),
+            ("callout_important", "cy") => "Pwysig".to_string(),
+            ("callout_important", "da") => "Vigtigt".to_string(),
+            ("callout_important", "de") => "Wichtig".to_string(),
+            ("callout_important", "el") => "Σημαντικό".to_string(),
+            ("callout_important", "es") => "Importante".to_string(),
+            ("callout_important", "et") => "Oluline".to_string(),
+            ("callout_important", "eu") => "Garrantzitsua".to_string(),
+            ("callout_important", "fa") => "مهم".to_string(),
+            ("callout_important", "fi") => "Tärkeää".to_string(),
+            ("callout_important", "fr") => "Important".to_string(),
+            ("callout_important", "gl") => "Importante".to_string(),
+            ("callout_important", "gu") => "મહત્વપૂર્ણ".to_string(),
+            ("callout_important", "he") => "חשוב".to_string(),
+            ("callout_important", "hi") => "महत्वपूर्ण".to_string(),
+            ("callout_important", "hr") => "Važno".to_string(),
+            ("callout_important", "hu") => "Fontos".to_string(),
+            ("callout_important", "id") => "Penting".to_string(),
+            ("callout_important", "is") => "Mikilvægt".to_string(),
+            ("callout_important", "it") => "Importante".to_string(),
+            ("callout_important", "ja") => "重要".to_string(),
+            ("callout_important", "kn") => "ಮುಖ್ಯ".to_string(),
+            ("callout_important", "ko") => "중요".to_string(),
+            ("callout_important", "lt") => "Svarbu".to_string(),
+            ("callout_important", "lv") => "Svarīgi".to_string(),
+            ("callout_important", "mk") => "Важно".to_string(),
+            ("callout_important", "ml") => "പ്രധാനം".to_string(),
+            ("callout_important", "mr") => "महत्त्वाचे".to_string(),
+            ("callout_important", "ms") => "Penting".to_string(),
+            ("callout_important", "nl") => "Belangrijk".to_string(),
+            ("callout_important", "no") => "Viktig".to_string(),
+            ("callout_important", "pa") => "ਮਹੱਤਵਪੂਰਨ".to_string(),
+            ("callout_important", "pl") => "Ważne".to_string(),
+            ("callout_important", "pt") => "Importante".to_string(),
+            ("callout_important", "ro") => "Important".to_string(),
+            ("callout_important", "ru") => "Важно".to_string(),
+            ("callout_important", "si") => "වැදගත්".to_string(),
+            ("callout_important", "sk") => "Dôležité".to_string(),
+            ("callout_important", "sl") => "Pomembno".to_string(),
+            ("callout_important", "sq") => "E rëndësishme".to_string(),
+            ("callout_important", "sr") => "Важно".to_string(),
+            ("callout_important", "sv") => "Viktigt".to_string(),
+            ("callout_important", "sw") => "Muhimu".to_string(),
+            ("callout_important", "ta") => "முக்கியம்".to_string(),
+            ("callout_important", "te") => "ముఖ్యం".to_string(),
+            ("callout_important", "th") => "สำคัญ".to_string(),
+            ("callout_important", "tl") => "Mahalaga".to_string(),
+            ("callout_important", "tr") => "Önemli".to_string(),
+            ("callout_important", "uk") => "Важливо".to_string(),
+            ("callout_important", "ur") => "اہم".to_string(),
+            ("callout_important", "vi") => "Quan trọng".to_string(),
+            ("callout_important", "xh") => "Kubalulekile".to_string(),
+            ("callout_important", "yo") => "Pàtàkì".to_string(),
+            ("callout_important", "zh") => "重要".to_string(),
+            ("callout_important", "zu") => "Kubalulekile".to_string(),
+            ("callout_important", _) => "Important".to_string(),
+
+            // Callout warning
+            ("callout_warning", "af") => "Waarskuwing".to_string(),
+            ("callout_warning", "am") => "ማስጠንቀቂያ".to_string(),
+            ("callout_warning", "ar") => "تحذير".to_string(),
+            ("callout_warning", "az") => "Xəbərdarlıq".to_string(),
+            ("callout_warning", "bg") => "Предупреждение".to_string(),
+            ("callout_warning", "bn") => "সতর্কতা".to_string(),
+            ("callout_warning", "bs") => "Upozorenje".to_string(),
+            ("callout_warning", "ca") => "Advertència".to_string(),
+            ("callout_warning", "cs") => "Varování".to_string(),
+            ("callout_warning", "cy") => "Rhybudd".to_string(),
+            ("callout_warning", "da") => "Advarsel".to_string(),
+            ("callout_warning", "de") => "Warnung".to_string(),
+            ("callout_warning", "el") => "Προειδοποίηση".to_string(),
+            ("callout_warning", "es") => "Advertencia".to_string(),
+            ("callout_warning", "et") => "Hoiatus".to_string(),
+            ("callout_warning", "eu") => "Abisua".to_string(),
+            ("callout_warning", "fa") => "هشدار".to_string(),
+            ("callout_warning", "fi") => "Varoitus".to_string(),
+            ("callout_warning", "fr") => "Avertissement".to_string(),
+            ("callout_warning", "gl") => "Aviso".to_string(),
+            ("callout_warning", "gu") => "ચેતવણી".to_string(),
+            ("callout_warning", "he") => "אזהרה".to_string(),
+            ("callout_warning", "hi") => "चेतावनी".to_string(),
+            ("callout_warning", "hr") => "Upozorenje".to_string(),
+            ("callout_warning", "hu") => "Figyelmeztetés".to_string(),
+            ("callout_warning", "id") => "Peringatan".to_string(),
+            ("callout_warning", "is") => "Viðvörun".to_string(),
+            ("callout_warning", "it") => "Avviso".to_string(),
+            ("callout_warning", "ja") => "警告".to_string(),
+            ("callout_warning", "kn") => "ಎಚ್ಚರಿಕೆ".to_string(),
+            ("callout_warning", "ko") => "경고".to_string(),
+            ("callout_warning", "lt") => "Įspėjimas".to_string(),
+            ("callout_warning", "lv") => "Brīdinājums".to_string(),
+            ("callout_warning", "mk") => "Предупредување".to_string(),
+            ("callout_warning", "ml") => "മുന്നറിയിപ്പ്".to_string(),
+            ("callout_warning", "mr") => "चेतावणी".to_string(),
+            ("callout_warning", "ms") => "Amaran".to_string(),
+            ("callout_warning", "nl") => "Waarschuwing".to_string(),
+            ("callout_warning", "no") => "Advarsel".to_string(),
+            ("callout_warning", "pa") => "ਚੇਤਾਵਨੀ".to_string(),
+            ("callout_warning", "pl") => "Ostrzeżenie".to_string(),
+            ("callout_warning", "pt") => "Aviso".to_string(),
+            ("callout_warning", "ro") => "Avertisment".to_string(),
+            ("callout_warning", "ru") => "Предупреждение".to_string(),
+            ("callout_warning", "si") => "අවවාදය".to_string(),
+            ("callout_warning", "sk") => "Varovanie".to_string(),
+            ("callout_warning", "sl") => "Opozorilo".to_string(),
+            ("callout_warning", "sq") => "Paralajmërim".to_string(),
+            ("callout_warning", "sr") => "Упозорење".to_string(),
+            ("callout_warning", "sv") => "Varning".to_string(),
+            ("callout_warning", "sw") => "Onyo".to_string(),
+            ("callout_warning", "ta") => "எச்சரிக்கை".to_string(),
+            ("callout_warning", "te") => "హెచ్చరిక".to_string(),
+            ("callout_warning", "th") => "คำเตือน".to_string(),
+            ("callout_warning", "tl") => "Babala".to_string(),
+            ("callout_warning", "tr") => "Uyarı".to_string(),
+            ("callout_warning", "uk") => "Попередження".to_string(),
+            ("callout_warning", "ur") => "انتباہ".to_string(),
+            ("callout_warning", "vi") => "Cảnh báo".to_string(),
+            ("callout_warning", "xh") => "Isilumkiso".to_string(),
+            ("callout_warning", "yo") => "Ìkìlọ̀".to_string(),
+            ("callout_warning", "zh") => "警告".to_string(),
+            ("callout_warning", "zu") => "Isexwayiso".to_string(),
+            ("callout_warning", _) => "Warning".to_string(),
+
+            // Callout caution
+            ("callout_caution", "af") => "Versigtig".to_string(),
+            ("callout_caution", "am") => "ጥንቃቄ".to_string(),
+            ("callout_caution", "ar") => "تنبيه".to_string(),
+            ("callout_caution", "az") => "Ehtiyat".to_string(),
+            ("callout_caution", "bg") => "Внимание".to_string(),
+            ("callout_caution", "bn") => "সাবধান".to_string(),
+            ("callout_caution", "bs") => "Oprez".to_string(),
+            ("callout_caution", "ca") => "Precaució".to_string(),
+            ("callout_caution", "cs") => "Pozor".to_string(),
+            ("callout_caution", "cy") => "Gofal".to_string(),
+            ("callout_caution", "da") => "Forsigtig".to_string(),
+            ("callout_caution", "de") => "Vorsicht".to_string(),
+            ("callout_caution", "el") => "Προσοχή".to_string(),
+            ("callout_caution", "es") => "Precaución".to_string(),
+            ("callout_caution", "et") => "Ettevaatust".to_string(),
+            ("callout_caution", "eu") => "Kontuz".to_string(),
+            ("callout_caution", "fa") => "احتیاط".to_string(),
+            ("callout_caution", "fi") => "Varo".to_string(),
+            ("callout_caution", "fr") => "Attention".to_string(),
+            ("callout_caution", "gl") => "Precaución".to_string(),
+            ("callout_caution", "gu") => "સાવધાન".to_string(),
+            ("callout_caution", "he") => "זהירות".to_string(),
+            ("callout_caution", "hi") => "सावधानी".to_string(),
+            ("callout_caution", "hr") => "Oprez".to_string(),
+            ("callout_caution", "hu") => "Vigyázat".to_string(),
+            ("callout_caution", "id") => "Perhatian".to_string(),
+            ("callout_caution", "is") => "Varúð".to_string(),
+            ("callout_caution", "it") => "Attenzione".to_string(),
+            ("callout_caution", "ja") => "注意".to_string(),
+            ("callout_caution", "kn") => "ಜಾಗ್ರತೆ".to_string(),
+            ("callout_caution", "ko") => "주의".to_string(),
+            ("callout_caution", "lt") => "Atsargiai".to_string(),
+            ("callout_caution", "lv") => "Uzmanību".to_string(),
+            ("callout_caution", "mk") => "Внимание".to_string(),
+            ("callout_caution", "ml") => "ജാഗ്രത".to_string(),
+            ("callout_caution", "mr") => "सावधान".to_string(),
+            ("callout_caution", "ms") => "Awas".to_string(),
+            ("callout_caution", "nl") => "Let op".to_string(),
+            ("callout_caution", "no") => "Forsiktig".to_string(),
+            ("callout_caution", "pa") => "ਸਾਵਧਾਨ".to_string(),
+            ("callout_caution", "pl") => "Przestroga".to_string(),
+            ("callout_caution", "pt") => "Cuidado".to_string(),
+            ("callout_caution", "ro") => "Atenție".to_string(),
+            ("callout_caution", "ru") => "Осторожно".to_string(),
+            ("callout_caution", "si") => "පරිස්සම් වන්න".to_string(),
+            ("callout_caution", "sk") => "Pozor".to_string(),
+            ("callout_caution", "sl") => "Previdno".to_string(),
+            ("callout_caution", "sq") => "Kujdes".to_string(),
+            ("callout_caution", "sr") => "Опрез".to_string(),
+            ("callout_caution", "sv") => "Försiktighet".to_string(),
+            ("callout_caution", "sw") => "Tahadhari".to_string(),
+            ("callout_caution", "ta") => "கவனம்".to_string(),
+            ("callout_caution", "te") => "జాగ్రత్త".to_string(),
+            ("callout_caution", "th") => "ข้อควรระวัง".to_string(),
+            ("callout_caution", "tl") => "Mag-ingat".to_string(),
+            ("callout_caution", "tr") => "Dikkat".to_string(),
+            ("callout_caution", "uk") => "Обережно".to_string(),
+            ("callout_caution", "ur") => "احتیاط".to_string(),
+            ("callout_caution", "vi") => "Thận trọng".to_string(),
+            ("callout_caution", "xh") => "Lumka".to_string(),
+            ("callout_caution", "yo") => "Ìṣọ́ra".to_string(),
+            ("callout_caution", "zh") => "小心".to_string(),
+            ("callout_caution", "zu") => "Qaphela".to_string(),
+            ("callout_caution", _) => "Caution".to_string(),
+
             _ => key.to_string(),
         }
     }
@@ -0,0 +1,441 @@
+//! Translation coverage and freshness reporting (`kk i18n status`)
+
+use crate::error::{IoError, IoErrorKind, KrikError, KrikResult};
+use crate::generator::markdown::{calculate_relative_path, create_document, extract_file_metadata};
+use crate::generator::templates::paths::get_translation_key;
+use crate::i18n::I18nManager;
+use crate::parser::{parse_markdown_with_frontmatter_for_file, Document};
+use crate::site::SiteConfig;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::process::Command;
+use tracing::debug;
+use walkdir::WalkDir;
+
+/// Full translation status for a content directory
+#[derive(Debug, Default, Serialize)]
+pub struct TranslationStatusReport {
+    pub default_language: String,
+    pub documents: usize,
+    pub languages: Vec<LanguageCoverage>,
+    pub missing: Vec<MissingTranslations>,
+    pub outdated: Vec<OutdatedTranslation>,
+    pub unknown_freshness: Vec<UnknownFreshness>,
+    pub mismatches: Vec<MetadataMismatch>,
+}
+
+/// Per-language coverage numbers
+#[derive(Debug, Serialize)]
+pub struct LanguageCoverage {
+    pub language: String,
+    pub language_name: String,
+    pub translated: usize,
+    pub missing: usize,
+    pub coverage: f64,
+}
+
+/// Languages missing for a single translation key
+#[derive(Debug, Serialize)]
+pub struct MissingTranslations {
+    pub translation_key: String,
+    pub source: String,
+    pub missing_languages: Vec<String>,
+}
+
+/// A translation whose source document changed after it
+#[derive(Debug, Serialize)]
+pub struct OutdatedTranslation {
+    pub translation_key: String,
+    pub source: String,
+    pub translation: String,
+    pub language: String,
+    pub source_modified: DateTime<Utc>,
+    pub translation_modified: DateTime<Utc>,
+    /// Where the timestamps came from: `updated`, `git` or `mtime`
+    pub timestamp_source: String,
+}
+
+/// A translation whose timestamps come from different places than its source's,
+/// so whether it is outdated cannot be told
+#[derive(Debug, Serialize)]
+pub struct UnknownFreshness {
+    pub translation_key: String,
+    pub source: String,
+    pub translation: String,
+    pub language: String,
+    /// Where the source's timestamp came from: `updated`, `git` or `mtime`
+    pub source_timestamp: String,
+    /// Where the translation's timestamp came from
+    pub translation_timestamp: String,
+}
+
+/// A front matter field that differs between a source and its translation
+#[derive(Debug, Serialize)]
+pub struct MetadataMismatch {
+    pub translation_key: String,
+    pub field: String,
+    pub source: String,
+    pub translation: String,
+    pub expected: String,
+    pub found: String,
+}
+
+/// Build the translation status report for all Markdown files under `content_dir`.
+///
+/// Documents are grouped by translation key. The default-language variant is the
+/// source for each group; when it is missing, the first variant by path is used.
+pub fn translation_status(content_dir: &Path) -> KrikResult<TranslationStatusReport> {
+    debug!(
+        "Collecting translation status in: {}",
+        content_dir.display()
+    );
+
+    if !content_dir.exists() {
+        return Err(KrikError::Io(Box::new(IoError {
+            kind: IoErrorKind::NotFound,
+            path: content_dir.to_path_buf(),
+            context: "Content directory not found".to_string(),
+        })));
+    }
+
+    let site_config = SiteConfig::load_from_path(content_dir).unwrap_or_default();
+    let documents = collect_documents(content_dir, &site_config);
+    let default_language = I18nManager::default_language().to_string();
+
+    let mut groups: BTreeMap<String, Vec<&Document>> = BTreeMap::new();
+    for doc in &documents {
+        groups
+            .entry(get_translation_key(doc))
+            .or_default()
+            .push(doc);
+    }
+
+    let mut languages: BTreeSet<String> = documents.iter().map(|d| d.language.clone()).collect();
+    languages.insert(default_language.clone());
+
+    let mut report = TranslationStatusReport {
+        default_language: default_language.clone(),
+        documents: groups.len(),
+        ..Default::default()
+    };
+
+    let mut translated_counts: BTreeMap<&str, usize> = BTreeMap::new();
+
+    for (key, variants) in &groups {
+        let source = variants
+            .iter()
+            .find(|d| d.language == default_language)
+            .unwrap_or(&variants[0]);
+
+        for doc in variants {
+            *translated_counts.entry(doc.language.as_str()).or_default() += 1;
+        }
+
+        let missing_languages: Vec<String> = languages
+            .iter()
+            .filter(|lang| !variants.iter().any(|d| &d.language == *lang))
+            .cloned()
+            .collect();
+        if !missing_languages.is_empty() {
+            report.missing.push(MissingTranslations {
+                translation_key: key.clone(),
+                source: source.file_path.clone(),
+                missing_languages,
+            });
+        }
+
+        let source_modified = last_modified(content_dir, &site_config, source);
+        for translation in variants.iter().filter(|d| d.file_path != source.file_path) {
+            check_metadata(key, source, translation, &mut report.mismatches);
+
+            let Some(((source_time, source_kind), (translation_time, kind))) =
+                source_modified.zip(last_modified(content_dir, &site_config, translation))
+            else {
+                continue;
+            };
+            // An `updated` date is not comparable with a commit or file time
+            if source_kind != kind {
+                report.unknown_freshness.push(UnknownFreshness {
+                    translation_key: key.clone(),
+                    source: source.file_path.clone(),
+                    translation: translation.file_path.clone(),
+                    language: translation.language.clone(),
+                    source_timestamp: source_kind.to_string(),
+                    translation_timestamp: kind.to_string(),
+                });
+            } else if source_time > translation_time {
+                report.outdated.push(OutdatedTranslation {
+                    translation_key: key.clone(),
+                    source: source.file_path.clone(),
+                    translation: translation.file_path.clone(),
+                    language: translation.language.clone(),
+                    source_modified: source_time,
+                    translation_modified: translation_time,
+                    timestamp_source: kind.to_string(),
+                });
+            }
+        }
+    }
+
+    let total = groups.len();
+    report.languages = languages
+        .iter()
+        .map(|lang| {
+            let translated = translated_counts.get(lang.as_str()).copied().unwrap_or(0);
+            LanguageCoverage {
+                language: lang.clone(),
+                language_name: I18nManager::get_language_name(lang),
+                translated,
+                missing: total - translated,
+                coverage: if total == 0 {
+                    100.0
+                } else {
+                    translated as f64 * 100.0 / total as f64
+                },
+            }
+        })
+        .collect();
+
+    Ok(report)
+}
+
+/// Render the report as a human-readable coverage table followed by details
+pub fn format_status_table(report: &TranslationStatusReport) -> String {
+    let mut out = String::new();
+
+    out.push_str(&format!(
+        "Translation coverage ({} documents, default language: {})\n\n",
+        report.documents, report.default_language
+    ));
+    out.push_str(&format!(
+        "{:<24} {:>10} {:>8} {:>9}\n",
+        "Language", "Translated", "Missing", "Coverage"
+    ));
+    for lang in &report.languages {
+        out.push_str(&format!(
+            "{:<24} {:>10} {:>8} {:>8.1}%\n",
+            format!("{} ({})", lang.language, lang.language_name),
+            lang.translated,
+            lang.missing,
+            lang.coverage
+        ));
+    }
+
+    if !report.missing.is_empty() {
+        out.push_str("\nMissing translations:\n");
+        for m in &report.missing {
+            out.push_str(&format!(
+                "  - {} ({}): {}\n",
+                m.translation_key,
+                m.source,
+                m.missing_languages.join(", ")
+            ));
+        }
+    }
+
+    if !report.outdated.is_empty() {
+        out.push_str("\nOutdated translations:\n");
+        for o in &report.outdated {
+            out.push_str(&format!(
+                "  - {} is older than {} ({} < {}, via {})\n",
+                o.translation,
+                o.source,
+                o.translation_modified.format("%Y-%m-%d %H:%M"),
+                o.source_modified.format("%Y-%m-%d %H:%M"),
+                o.timestamp_source
+            ));
+        }
+    }
+
+    if !report.unknown_freshness.is_empty() {
+        out.push_str("\nTranslations of unknown freshness:\n");
+        for u in &report.unknown_freshness {
+            out.push_str(&format!(
+                "  - {} cannot be compared with {} ({} vs {})\n",
+                u.translation, u.source, u.translation_timestamp, u.source_timestamp
+            ));
+        }
+    }
+
+    if !report.mismatches.is_empty() {
+        out.push_str("\nMetadata mismatches:\n");
+        for m in &report.mismatches {
+            out.push_str(&format!(
+                "  - {}: '{}' is {} but {} has {}\n",
+                m.translation, m.field, m.found, m.source, m.expected
+            ));
+        }
+    }
+
+    out
+}
+
+/// Parse front matter of every non-draft Markdown file, including those of
+/// mounted directories, without rendering HTML
+fn collect_documents(content_dir: &Path, site_config: &SiteConfig) -> Vec<Document> {
+    let mut documents = Vec::new();
+    let roots = std::iter::once((content_dir, &site_config.ignore)).chain(
+        site_config
+            .mounts
+            .iter()
+            .map(|mount| (mount.dir.as_path(), &mount.ignore)),
+    );
+
+    for entry in roots.flat_map(|(root, ignore)| {
+        WalkDir::new(root)
+            .follow_links(true)
+            .into_iter()
+            .filter_entry(move |e| !ignore.is_ignored(e.path()))
+            .filter_map(|e| e.ok())
+    }) {
+        let path = entry.path();
+        if !path.is_file() || path.extension().map_or(true, |ext| ext != "md") {
+            continue;
+        }
+
+        let Ok(content) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        let Ok((front_matter, _)) = parse_markdown_with_frontmatter_for_file(&content, path) else {
+            continue;
+        };
+        if front_matter.draft.unwrap_or(false) {
+            continue;
+        }
+        let Ok((base_name, language)) = extract_file_metadata(path) else {
+            continue;
+        };
+
+        let mut document = create_document(
+            front_matter,
+            String::new(),
+            site_config
+                .site_path(content_dir, path)
+                .map(|relative| relative.to_string_lossy().to_string())
+                .unwrap_or_else(|| calculate_relative_path(content_dir, path)),
+            language,
+            base_name,
+            String::new(),
+        );
+        document.mount_dir = site_config
+            .mount_containing(path)
+            .map(|mount| mount.dir.clone());
+        documents.push(document);
+    }
+
+    // Stable sort, so content files win over mounted files with the same path
+    documents.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+    documents.dedup_by(|duplicate, kept| duplicate.file_path == kept.file_path);
+    documents
+}
+
+/// Compare the front matter fields that translations are expected to share
+fn check_metadata(
+    key: &str,
+    source: &Document,
+    translation: &Document,
+    mismatches: &mut Vec<MetadataMismatch>,
+) {
+    let mut push = |field: &str, expected: String, found: String| {
+        mismatches.push(MetadataMismatch {
+            translation_key: key.to_string(),
+            field: field.to_string(),
+            source: source.file_path.clone(),
+            translation: translation.file_path.clone(),
+            expected,
+            found,
+        });
+    };
+
+    let date = |d: &Document| {
+        d.front_matter
+            .date
+            .map(|dt| dt.to_rfc3339())
+            .unwrap_or_else(|| "unset".to_string())
+    };
+    if source.front_matter.date != translation.front_matter.date {
+        push("date", date(source), date(translation));
+    }
+
+    let tags = |d: &Document| {
+        let mut tags = d.front_matter.tags.clone().unwrap_or_default();
+        tags.sort();
+        tags
+    };
+    if tags(source) != tags(translation) {
+        push(
+            "tags",
+            format!("[{}]", tags(source).join(", ")),
+            format!("[{}]", tags(translation).join(", ")),
+        );
+    }
+
+    let pdf = |d: &Document| d.front_matter.pdf.unwrap_or(false);
+    if pdf(source) != pdf(translation) {
+        push("pdf", pdf(source).to_string(), pdf(translation).to_string());
+    }
+}
+
+/// Last modification time of a document: `updated` front matter, then git, then mtime
+fn last_modified(
+    content_dir: &Path,
+    site_config: &SiteConfig,
+    document: &Document,
+) -> Option<(DateTime<Utc>, &'static str)> {
+    if let Some(updated) = document
+        .front_matter
+        .extra
+        .get("updated")
+        .and_then(|v| v.as_str())
+        .and_then(parse_updated)
+    {
+        return Some((updated, "updated"));
+    }
+
+    let path = site_config.content_root(content_dir, document).file();
+    if let Some(committed) = git_commit_time(&path) {
+        return Some((committed, "git"));
+    }
+
+    std::fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .ok()
+        .map(|t| (t.into(), "mtime"))
+}
+
+/// Accept RFC 3339 timestamps or plain `YYYY-MM-DD` dates
+fn parse_updated(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .map(|dt| dt.and_utc())
+        })
+}
+
+/// Timestamp of the last commit touching `path`, if it is tracked by git
+fn git_commit_time(path: &Path) -> Option<DateTime<Utc>> {
+    let dir = path.parent()?;
+    let output = Command::new("git")
+        .arg("log")
+        .arg("-1")
+        .arg("--format=%ct")
+        .arg("--")
+        .arg(path)
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let secs: i64 = String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .ok()?;
+    DateTime::from_timestamp(secs, 0)
+}
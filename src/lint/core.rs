@@ -1,10 +1,13 @@
 use crate::error::{IoError, IoErrorKind, KrikError, KrikResult};
+use crate::generator::image_metadata;
+use crate::generator::links::{self, wiki_link_targets, wiki_name};
 use crate::lint::report_generator::LintReport;
 use crate::parser::{extract_language_from_filename, parse_markdown_with_frontmatter_for_file};
+use crate::site::SiteConfig;
 use chrono::Utc;
 use once_cell::sync::Lazy;
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use tracing::debug;
 use walkdir::WalkDir;
@@ -22,6 +25,8 @@ pub fn lint_content(content_dir: &Path) -> KrikResult<LintReport> {
     }
 
     let mut report = LintReport::default();
+    let site_config = SiteConfig::load_from_path(content_dir).unwrap_or_default();
+    let strip_metadata = site_config.images.strip_metadata;
 
     // Precompiled regex
     static MD_LINK_REGEX: Lazy<Regex> =
@@ -31,14 +36,36 @@ pub fn lint_content(content_dir: &Path) -> KrikResult<LintReport> {
     let mut seen_slugs: HashMap<(String, String, String), Vec<PathBuf>> = HashMap::new();
     // Track duplicate titles: (relative_parent_dir, normalized_title, language) -> Vec<paths>
     let mut seen_titles: HashMap<(String, String, String), Vec<PathBuf>> = HashMap::new();
+    // Wiki-link names of every document, and the wiki-links to check against them
+    let mut wiki_names: HashSet<String> = HashSet::new();
+    let mut wiki_links: Vec<(PathBuf, String)> = Vec::new();
 
     for entry in WalkDir::new(content_dir)
         .follow_links(true)
         .into_iter()
+        .filter_entry(|e| !site_config.ignore.is_ignored(e.path()))
         .filter_map(|e| e.ok())
     {
         let path = entry.path();
 
+        // Published photos should not reveal where they were taken
+        if path.is_file() && is_strippable_image(path) {
+            if let Ok(bytes) = std::fs::read(path) {
+                if image_metadata::has_gps_data(&bytes) {
+                    let hint = if strip_metadata {
+                        "removed from the published copy by `strip_metadata`"
+                    } else {
+                        "set `strip_metadata = true` under [images] in site.toml to remove it"
+                    };
+                    report.warnings.push(format!(
+                        "{}: image contains GPS location data; {hint}",
+                        path.display()
+                    ));
+                }
+            }
+            continue;
+        }
+
         // Only lint markdown files
         if !path.is_file() || path.extension().map_or(true, |ext| ext != "md") {
             continue;
@@ -64,9 +91,17 @@ pub fn lint_content(content_dir: &Path) -> KrikResult<LintReport> {
         };
 
         match parse_markdown_with_frontmatter_for_file(&content, path) {
-            Ok((front, _markdown)) => {
+            Ok((front, markdown)) => {
                 process_file_frontmatter(path, &front, &mut report, content_dir)?;
                 track_duplicates(path, &front, content_dir, &mut seen_slugs, &mut seen_titles)?;
+                track_wiki_links(
+                    path,
+                    &front,
+                    &markdown,
+                    content_dir,
+                    &mut wiki_names,
+                    &mut wiki_links,
+                );
             }
             Err(e) => {
                 report.errors.push(format!("{e}"));
@@ -93,6 +128,16 @@ pub fn lint_content(content_dir: &Path) -> KrikResult<LintReport> {
     // Check for duplicates
     check_duplicates(&mut report, seen_slugs, seen_titles);
 
+    for (path, target) in wiki_links {
+        if !wiki_names.contains(&wiki_name(&target)) {
+            report.warnings.push(format!(
+                "{}: wiki-link '[[{}]]' does not match any document title or name",
+                path.display(),
+                target
+            ));
+        }
+    }
+
     Ok(report)
 }
 
@@ -167,6 +212,47 @@ fn process_file_frontmatter(
         }
     }
 
+    // Validate TOC level range and numbering if present
+    for key in ["toc_min", "toc_max"] {
+        if let Some(level) = front.extra.get(key) {
+            if !level.as_u64().is_some_and(|l| (1..=6).contains(&l)) {
+                report.warnings.push(format!(
+                    "{}: '{}' should be a heading level from 1 to 6",
+                    path.display(),
+                    key
+                ));
+            }
+        }
+    }
+    if let Some(numbered) = front.extra.get("toc_numbered") {
+        if !numbered.is_bool() {
+            report.warnings.push(format!(
+                "{}: 'toc_numbered' should be a boolean",
+                path.display()
+            ));
+        }
+    }
+
+    // Validate footnote style if present
+    if let Some(style) = front.extra.get("footnotes") {
+        if !matches!(style.as_str(), Some("endnotes" | "sidenotes")) {
+            report.warnings.push(format!(
+                "{}: 'footnotes' should be 'endnotes' or 'sidenotes'",
+                path.display()
+            ));
+        }
+    }
+
+    // Validate translation_key type if present
+    if let Some(key_val) = front.extra.get("translation_key") {
+        if key_val.as_str().map_or(true, |s| s.trim().is_empty()) {
+            report.warnings.push(format!(
+                "{}: 'translation_key' should be a non-empty string",
+                path.display()
+            ));
+        }
+    }
+
     // Validate date presence/validity for posts (recommended)
     let is_post = path.to_string_lossy().contains(&format!(
         "{}posts{}",
@@ -222,7 +308,13 @@ fn process_file_frontmatter(
     let known_keys = [
         "layout",
         "toc",
+        "toc_min",
+        "toc_max",
+        "toc_numbered",
+        "footnotes",
         "description", // extras commonly used
+        "translation_key",
+        "updated",
     ];
     for key in front.extra.keys() {
         if !known_keys.contains(&key.as_str()) {
@@ -308,6 +400,40 @@ fn track_duplicates(
     Ok(())
 }
 
+fn is_strippable_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            image_metadata::STRIPPABLE_EXTENSIONS.contains(&ext.to_lowercase().as_str())
+        })
+}
+
+/// Record the names a document can be wiki-linked by and the wiki-links it contains
+fn track_wiki_links(
+    path: &Path,
+    front: &crate::parser::FrontMatter,
+    markdown: &str,
+    content_dir: &Path,
+    wiki_names: &mut HashSet<String>,
+    wiki_links: &mut Vec<(PathBuf, String)>,
+) {
+    let relative = path.strip_prefix(content_dir).unwrap_or(path);
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if let Ok((base_name, _)) = extract_language_from_filename(&stem) {
+        wiki_names.extend(links::wiki_names(
+            &relative.to_string_lossy(),
+            &base_name,
+            front.title.as_deref(),
+        ));
+    }
+    for target in wiki_link_targets(markdown) {
+        wiki_links.push((path.to_path_buf(), target));
+    }
+}
+
 /// Check for duplicate slugs and titles
 fn check_duplicates(
     report: &mut LintReport,
@@ -1,4 +1,5 @@
 use crate::error::KrikResult;
+use crate::site::SiteConfig;
 use futures_util::stream::{self, StreamExt};
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -89,10 +90,14 @@ fn collect_links_from_files(content_dir: &Path) -> KrikResult<Vec<LinkToCheck>>
 
     debug!("Scanning files for links in: {}", content_dir.display());
     let mut files_scanned = 0;
+    let ignore = SiteConfig::load_from_path(content_dir)
+        .map(|config| config.ignore)
+        .unwrap_or_default();
 
     for entry in WalkDir::new(content_dir)
         .follow_links(true)
         .into_iter()
+        .filter_entry(|e| !ignore.is_ignored(e.path()))
         .filter_map(|e| e.ok())
     {
         let path = entry.path();
@@ -3,7 +3,7 @@ use crate::i18n::I18nManager;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Front matter metadata extracted from the YAML header of Markdown files.
 ///
@@ -60,6 +60,15 @@ pub struct Document {
     pub base_name: String,
     /// Generated table of contents HTML (if TOC is enabled)
     pub toc: Option<String>,
+    /// Footnotes in display order, for templates
+    pub footnotes: Vec<crate::generator::ast_parser::Footnote>,
+    /// Source paths of the documents this one links to, filled in when links are resolved
+    pub links: Vec<String>,
+    /// Files co-located with a page bundle's `index.md`, filled in when documents are transformed
+    pub resources: Vec<crate::generator::bundles::Resource>,
+    /// Mounted directory the source file was read from; `None` for the content directory
+    #[serde(skip)]
+    pub mount_dir: Option<PathBuf>,
 }
 
 /// Parses a Markdown document with YAML front matter.
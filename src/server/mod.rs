@@ -12,8 +12,11 @@ pub mod static_files;
 pub mod watcher;
 pub mod websocket;
 
+use crate::generator::assets::static_dir;
+use crate::generator::compress::compress_output;
 use live_reload::*;
 use net::get_network_interfaces;
+use static_files::serve_static_files;
 use watcher::start_watcher;
 use websocket::*;
 
@@ -23,6 +26,7 @@ pub struct DevServer {
     theme_dir: Option<PathBuf>,
     port: u16,
     live_reload: bool,
+    compress: bool,
     reload_tx: broadcast::Sender<()>,
 }
 
@@ -33,6 +37,7 @@ impl DevServer {
         theme_dir: Option<PathBuf>,
         port: u16,
         live_reload: bool,
+        compress: bool,
     ) -> Self {
         let (reload_tx, _) = broadcast::channel(100);
 
@@ -42,6 +47,7 @@ impl DevServer {
             theme_dir,
             port,
             live_reload,
+            compress,
             reload_tx,
         }
     }
@@ -62,8 +68,7 @@ impl DevServer {
         // Build routes based on live_reload setting
         if self.live_reload {
             // Setup with WebSocket for live reload
-            let static_route = warp::fs::dir(output_dir.clone())
-                .or(warp::path::end().and(warp::fs::file(output_dir.join("index.html"))));
+            let static_route = serve_static_files(output_dir.clone());
 
             let reload_tx = self.reload_tx.clone();
             let ws_route =
@@ -95,8 +100,7 @@ impl DevServer {
             warp::serve(routes).run(([0, 0, 0, 0], self.port)).await;
         } else {
             // Setup without WebSocket for static serving only
-            let static_route = warp::fs::dir(output_dir.clone())
-                .or(warp::path::end().and(warp::fs::file(output_dir.join("index.html"))));
+            let static_route = serve_static_files(output_dir.clone());
 
             info!("🚀 Krik development server started!");
             info!("📁 Serving: {}", self.output_dir.display());
@@ -123,8 +127,9 @@ impl DevServer {
     }
 
     fn generate_site(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let generator =
+        let mut generator =
             SiteGenerator::new(&self.input_dir, &self.output_dir, self.theme_dir.as_ref())?;
+        generator.set_dev(true)?;
         generator.generate_site()?;
 
         // Conditionally inject live reload script into HTML files
@@ -132,6 +137,11 @@ impl DevServer {
             inject_live_reload_script(&self.output_dir, self.port)?;
         }
 
+        // Compress after injection so the siblings include the live reload script
+        if self.compress {
+            compress_output(&self.input_dir, &self.output_dir)?;
+        }
+
         Ok(())
     }
 
@@ -142,17 +152,9 @@ impl DevServer {
         let reload_tx = self.reload_tx.clone();
         let port = self.port;
         let live_reload = self.live_reload;
+        let compress = self.compress;
 
         tokio::spawn(async move {
-            let (tx, mut rx) = tokio::sync::mpsc::channel(100);
-            start_watcher(input_dir.clone(), theme_dir.clone(), tx).await;
-            // Canonicalize watched roots to compare against canonical event paths
-            let canonical_input_dir =
-                std::fs::canonicalize(&input_dir).unwrap_or(input_dir.clone());
-            let canonical_theme_dir = theme_dir
-                .as_ref()
-                .and_then(|t| std::fs::canonicalize(t).ok());
-
             // Persistent generator to preserve document cache across changes
             let mut generator =
                 match SiteGenerator::new(&input_dir, &output_dir, theme_dir.as_ref()) {
@@ -162,6 +164,32 @@ impl DevServer {
                         return;
                     }
                 };
+
+            let (tx, mut rx) = tokio::sync::mpsc::channel(100);
+            let static_dir = static_dir(&input_dir);
+            let mount_dirs: Vec<PathBuf> = generator
+                .site_config
+                .mounts
+                .iter()
+                .map(|mount| mount.dir.clone())
+                .collect();
+            start_watcher(
+                input_dir.clone(),
+                theme_dir.clone(),
+                static_dir.clone(),
+                mount_dirs.clone(),
+                tx,
+            )
+            .await;
+            // Canonicalize watched roots to compare against canonical event paths
+            let canonical_input_dir =
+                std::fs::canonicalize(&input_dir).unwrap_or(input_dir.clone());
+            let canonical_theme_dir = theme_dir
+                .as_ref()
+                .and_then(|t| std::fs::canonicalize(t).ok());
+            if let Err(e) = generator.set_dev(true) {
+                error!("failed to process theme assets in watcher: {}", e);
+            }
             if let Err(e) = generator.scan_files() {
                 error!("initial scan failed in watcher: {}", e);
                 // continue anyway; incremental may rescan as needed
@@ -201,6 +229,13 @@ impl DevServer {
                     }
                 }
 
+                // Drop paths matched by `.krikignore` or `exclude`
+                batched.retain(|path, _| !generator.site_config.is_ignored(path));
+                if batched.is_empty() {
+                    debug!("all changed paths are ignored");
+                    continue;
+                }
+
                 // Log the batched set
                 if !batched.is_empty() {
                     let mut dbg_paths: Vec<String> = batched
@@ -218,8 +253,10 @@ impl DevServer {
                 // Run incremental for the batched unique paths using persistent generator/cache
                 let mut did_anything = false;
                 for (path, is_remove) in batched.into_iter() {
-                    // Only handle changes under input_dir or theme_dir
+                    // Only handle changes under input_dir, static_dir, mounts or theme_dir
                     let relevant = path.starts_with(&canonical_input_dir)
+                        || path.starts_with(&static_dir)
+                        || mount_dirs.iter().any(|dir| path.starts_with(dir))
                         || canonical_theme_dir
                             .as_ref()
                             .map(|t| path.starts_with(t))
@@ -267,6 +304,13 @@ impl DevServer {
                     }
                 }
 
+                // Refresh precompressed siblings of the files that changed
+                if compress {
+                    if let Err(e) = compress_output(&input_dir, &output_dir) {
+                        error!("❌ Error precompressing output: {}", e);
+                    }
+                }
+
                 info!("✅ Incremental build complete");
                 let _ = reload_tx.send(());
             }
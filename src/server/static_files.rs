@@ -1,4 +1,8 @@
-use std::path::Path;
+use crate::generator::compress::{is_fresh, sibling_path, ENCODINGS};
+use std::path::{Path, PathBuf};
+use warp::http::header::{CONTENT_ENCODING, CONTENT_TYPE, VARY};
+use warp::http::Response;
+use warp::path::Tail;
 use warp::Filter;
 
 pub fn serve_static_files(
@@ -6,7 +10,89 @@ pub fn serve_static_files(
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
     let output_dir = output_dir.as_ref().to_path_buf();
 
+    // Serve `.br`/`.gz` siblings written by `--compress` to clients that accept them
+    let root = output_dir.clone();
+    let precompressed = warp::get()
+        .and(warp::path::tail())
+        .and(warp::header::optional::<String>("accept-encoding"))
+        .and_then(move |tail: Tail, accept_encoding: Option<String>| {
+            serve_precompressed(root.clone(), tail, accept_encoding)
+        });
+
     // Serve files from output directory
-    warp::fs::dir(output_dir.clone())
+    precompressed
+        .or(warp::fs::dir(output_dir.clone()))
         .or(warp::path::end().and(warp::fs::file(output_dir.join("index.html"))))
 }
+
+async fn serve_precompressed(
+    output_dir: PathBuf,
+    tail: Tail,
+    accept_encoding: Option<String>,
+) -> Result<Response<Vec<u8>>, warp::Rejection> {
+    let accept_encoding = accept_encoding.unwrap_or_default();
+    let path = resolve_path(&output_dir, tail.as_str()).ok_or_else(warp::reject::not_found)?;
+    let content_type = content_type(&path).ok_or_else(warp::reject::not_found)?;
+
+    for (encoding, extension) in ENCODINGS {
+        let sibling = sibling_path(&path, extension);
+        if !accepts(&accept_encoding, encoding) || !is_fresh(&path, &sibling) {
+            continue;
+        }
+        let Ok(body) = tokio::fs::read(&sibling).await else {
+            continue;
+        };
+        return Response::builder()
+            .header(CONTENT_TYPE, content_type)
+            .header(CONTENT_ENCODING, *encoding)
+            .header(VARY, "Accept-Encoding")
+            .body(body)
+            .map_err(|_| warp::reject::not_found());
+    }
+    Err(warp::reject::not_found())
+}
+
+/// Map a request path to a file in the output directory, using `index.html` for
+/// directories and rejecting paths that leave the output directory
+fn resolve_path(output_dir: &Path, tail: &str) -> Option<PathBuf> {
+    let mut path = output_dir.to_path_buf();
+    for segment in tail.split('/').filter(|s| !s.is_empty()) {
+        if segment == ".." || segment.contains('\\') {
+            return None;
+        }
+        path.push(segment);
+    }
+    if path.is_dir() {
+        path.push("index.html");
+    }
+    path.is_file().then_some(path)
+}
+
+/// Return true when an `Accept-Encoding` header allows `encoding`
+fn accepts(accept_encoding: &str, encoding: &str) -> bool {
+    accept_encoding.split(',').any(|item| {
+        let mut parts = item.split(';').map(str::trim);
+        let name = parts.next().unwrap_or_default();
+        let rejected = parts.any(|param| {
+            param
+                .strip_prefix("q=")
+                .and_then(|q| q.parse::<f32>().ok())
+                .is_some_and(|q| q == 0.0)
+        });
+        name.eq_ignore_ascii_case(encoding) && !rejected
+    })
+}
+
+/// Content type of the files that can have precompressed siblings
+fn content_type(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    Some(match extension.as_str() {
+        "html" => "text/html; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" => "text/javascript; charset=utf-8",
+        "xml" => "application/xml",
+        "json" => "application/json",
+        "svg" => "image/svg+xml",
+        _ => return None,
+    })
+}
@@ -4,7 +4,13 @@ use std::time::Duration;
 use tokio::sync::mpsc::Sender;
 use tracing::{debug, error};
 
-pub async fn start_watcher(input_dir: PathBuf, theme_dir: Option<PathBuf>, tx: Sender<Event>) {
+pub async fn start_watcher(
+    input_dir: PathBuf,
+    theme_dir: Option<PathBuf>,
+    static_dir: PathBuf,
+    mount_dirs: Vec<PathBuf>,
+    tx: Sender<Event>,
+) {
     tokio::task::spawn_blocking(move || {
         let mut watcher =
             match notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
@@ -45,6 +51,24 @@ pub async fn start_watcher(input_dir: PathBuf, theme_dir: Option<PathBuf>, tx: S
                 );
             }
         }
+        if static_dir.is_dir() {
+            if let Err(e) = watcher.watch(&static_dir, RecursiveMode::Recursive) {
+                error!(
+                    "failed to watch static directory {}: {}",
+                    static_dir.display(),
+                    e
+                );
+            }
+        }
+        for mount_dir in &mount_dirs {
+            if let Err(e) = watcher.watch(mount_dir, RecursiveMode::Recursive) {
+                error!(
+                    "failed to watch mounted directory {}: {}",
+                    mount_dir.display(),
+                    e
+                );
+            }
+        }
 
         // Block this thread; notify uses blocking callbacks
         loop {
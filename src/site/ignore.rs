@@ -0,0 +1,98 @@
+//! Content ignore patterns
+//!
+//! Files in the content directory can be left out of the build with
+//! gitignore-style patterns, read from a `.krikignore` file in the content
+//! directory and from the `exclude` list in `site.toml`. Ignored files are not
+//! scanned as documents, copied as assets, linted or watched by `kk server`.
+
+use crate::error::{ConfigError, ConfigErrorKind, KrikError, KrikResult};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::{Path, PathBuf};
+
+/// Name of the ignore file in the content directory
+pub const IGNORE_FILE: &str = ".krikignore";
+
+/// Compiled `.krikignore` and `exclude` patterns of a content directory
+#[derive(Debug, Clone)]
+pub struct IgnoreRules {
+    root: PathBuf,
+    canonical_root: Option<PathBuf>,
+    matcher: Gitignore,
+}
+
+impl Default for IgnoreRules {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::new(),
+            canonical_root: None,
+            matcher: Gitignore::empty(),
+        }
+    }
+}
+
+impl IgnoreRules {
+    /// Compile the patterns of `content_dir/.krikignore` (when present) and
+    /// `exclude`, both relative to `content_dir`
+    pub fn load(content_dir: &Path, exclude: &[String]) -> KrikResult<Self> {
+        let mut builder = GitignoreBuilder::new(content_dir);
+        let ignore_file = content_dir.join(IGNORE_FILE);
+        if let Ok(contents) = std::fs::read_to_string(&ignore_file) {
+            for line in contents.lines() {
+                builder
+                    .add_line(Some(ignore_file.clone()), line)
+                    .map_err(|e| invalid_pattern(IGNORE_FILE, line, &e, Some(&ignore_file)))?;
+            }
+        }
+        for pattern in exclude {
+            builder
+                .add_line(None, pattern)
+                .map_err(|e| invalid_pattern("exclude", pattern, &e, None))?;
+        }
+        let matcher = builder
+            .build()
+            .map_err(|e| invalid_pattern("exclude", "", &e, None))?;
+        Ok(Self {
+            root: content_dir.to_path_buf(),
+            canonical_root: std::fs::canonicalize(content_dir).ok(),
+            matcher,
+        })
+    }
+
+    /// Return true when `path` (absolute, or relative to the content directory)
+    /// or one of its parent directories matches an ignore pattern
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        if self.matcher.is_empty() {
+            return false;
+        }
+        let relative = self
+            .canonical_root
+            .as_deref()
+            .and_then(|root| path.strip_prefix(root).ok())
+            .or_else(|| path.strip_prefix(&self.root).ok())
+            .unwrap_or(path);
+        if relative.has_root() || relative.as_os_str().is_empty() {
+            return false;
+        }
+        let is_dir = self.root.join(relative).is_dir();
+        self.matcher
+            .matched_path_or_any_parents(relative, is_dir)
+            .is_ignore()
+    }
+}
+
+fn invalid_pattern(
+    field: &str,
+    pattern: &str,
+    error: &ignore::Error,
+    path: Option<&Path>,
+) -> KrikError {
+    KrikError::Config(Box::new(ConfigError {
+        kind: ConfigErrorKind::InvalidValue {
+            field: field.to_string(),
+            expected: "a gitignore-style glob".to_string(),
+            found: pattern.to_string(),
+        },
+        path: path.map(Path::to_path_buf),
+        context: format!("Compiling ignore patterns: {error}"),
+    }))
+}
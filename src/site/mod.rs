@@ -1,32 +1,282 @@
 use crate::error::{ConfigError, ConfigErrorKind, KrikError, KrikResult};
+use crate::parser::Document;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+pub mod ignore;
+pub mod mounts;
+
+pub use ignore::IgnoreRules;
+pub use mounts::{ContentRoot, Mount};
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct SiteConfig {
     pub title: Option<String>,
+    pub description: Option<String>,
     pub base_url: Option<String>,
     pub theme: Option<String>,
+    /// Navigation menu; replaces the automatic page list when set
+    pub menu: Option<Vec<MenuItem>>,
+    /// Per-language overrides from `[languages.<code>]` tables
+    #[serde(default)]
+    pub languages: HashMap<String, LanguageConfig>,
+    /// Build-time syntax highlighting from the `[highlight]` table
+    #[serde(default)]
+    pub highlight: HighlightConfig,
+    /// Heading IDs and anchor links from the `[headings]` table
+    #[serde(default)]
+    pub headings: HeadingConfig,
+    /// Table of contents defaults from the `[toc]` table
+    #[serde(default)]
+    pub toc: TocConfig,
+    /// Footnote rendering from the `[footnotes]` table
+    #[serde(default)]
+    pub footnotes: FootnoteConfig,
+    /// Attributes of links to other sites from the `[external_links]` table
+    #[serde(default)]
+    pub external_links: ExternalLinksConfig,
+    /// Responsive image processing from the `[images]` table
+    #[serde(default)]
+    pub images: ImageConfig,
+    /// CSS/JS processing from the `[assets]` table
+    #[serde(default)]
+    pub assets: AssetConfig,
+    /// Rendered page processing from the `[html]` table
+    #[serde(default)]
+    pub html: HtmlConfig,
+    /// Gitignore-style patterns, relative to the content directory, of files to
+    /// leave out of the build (in addition to `.krikignore`)
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Compiled `.krikignore` and `exclude` patterns, set by `load_from_path`
+    #[serde(skip)]
+    pub ignore: IgnoreRules,
+    /// Directories mounted into the site from `[[mounts]]` tables
+    #[serde(default)]
+    pub mounts: Vec<Mount>,
+}
+
+/// Processing of rendered HTML pages
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct HtmlConfig {
+    /// Collapse template whitespace and drop comments in pages and the index,
+    /// leaving `<pre>`, `<code>`, `<textarea>`, `<script>` and `<style>` untouched
+    /// (turned off by `kk server`)
+    pub minify: bool,
+}
+
+/// Processing of stylesheets and scripts
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct AssetConfig {
+    /// Minify CSS and JS from the theme and the content directory (skipped by
+    /// `kk server`, which writes source maps for theme bundles instead)
+    pub minify: bool,
+}
+
+/// Resizing of local images into responsive variants
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImageConfig {
+    /// Resize images referenced from Markdown and render them with `srcset`
+    pub enabled: bool,
+    /// Widths of the resized variants, in pixels; widths not smaller than the
+    /// original image are skipped
+    pub widths: Vec<u32>,
+    /// `sizes` attribute of responsive images
+    pub sizes: String,
+    /// Also encode every width of PNG images as lossless WebP, offered through
+    /// `<picture>`; JPEG images are left as JPEG, which is smaller for photos
+    pub webp: bool,
+    /// JPEG quality of resized variants (1-100); PNG and WebP are lossless
+    pub quality: u8,
+    /// Add `loading="lazy"` and `decoding="async"`
+    pub lazy: bool,
+    /// Cache of resized variants, relative to the parent of the content directory
+    pub cache_dir: String,
+    /// Remove EXIF, XMP and IPTC metadata (such as GPS positions) from copied
+    /// JPEG, PNG and WebP files, keeping only the orientation
+    pub strip_metadata: bool,
+}
+
+impl Default for ImageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            widths: vec![480, 960, 1600],
+            sizes: "100vw".to_string(),
+            webp: false,
+            quality: 80,
+            lazy: true,
+            cache_dir: ".krik-cache/images".to_string(),
+            strip_metadata: false,
+        }
+    }
+}
+
+/// How links to other sites are rendered in Markdown content
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExternalLinksConfig {
+    /// `rel` attribute, e.g. `"noopener noreferrer nofollow"`
+    pub rel: Option<String>,
+    /// `target` attribute, e.g. `"_blank"`
+    pub target: Option<String>,
+    /// CSS class added to external links, e.g. `"external"`
+    pub class: Option<String>,
+    /// Domains (and their subdomains) treated as internal; the host of
+    /// `base_url` is always internal
+    pub internal_domains: Vec<String>,
+}
+
+impl ExternalLinksConfig {
+    /// Whether any attribute is configured
+    pub fn is_enabled(&self) -> bool {
+        self.rel.is_some() || self.target.is_some() || self.class.is_some()
+    }
+}
+
+/// Footnote settings; pages override the style with `footnotes` front matter
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FootnoteConfig {
+    pub style: FootnoteStyle,
+}
+
+/// Where footnote definitions are rendered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FootnoteStyle {
+    /// Numbered list at the end of the content with backlinks to each reference
+    #[default]
+    Endnotes,
+    /// Notes in the margin next to their first reference
+    Sidenotes,
+}
+
+/// Table of contents settings; pages override them with `toc_min`, `toc_max`
+/// and `toc_numbered` front matter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TocConfig {
+    /// Shallowest heading level listed (1-6)
+    pub min: u8,
+    /// Deepest heading level listed (1-6)
+    pub max: u8,
+    /// Prefix entries with section numbers (1, 1.1, 1.2, ...)
+    pub numbered: bool,
+}
+
+impl Default for TocConfig {
+    fn default() -> Self {
+        Self {
+            min: 1,
+            max: 6,
+            numbered: false,
+        }
+    }
+}
+
+/// Settings for heading IDs and anchor links
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HeadingConfig {
+    /// How heading text is turned into an `id`
+    pub slug: SlugStrategy,
+    /// Add a `#` link next to each heading, shown on hover
+    pub anchors: bool,
+}
+
+/// Strategy for deriving heading IDs from heading text
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SlugStrategy {
+    /// Keep letters and digits from any script (`Überblick` → `überblick`)
+    #[default]
+    Unicode,
+    /// Transliterate to ASCII (`Überblick` → `uberblick`, `日本語` → `ri-ben-yu`)
+    Transliterate,
+}
+
+/// Settings for build-time syntax highlighting of fenced code blocks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HighlightConfig {
+    /// Highlight code blocks while rendering Markdown; off by default so
+    /// existing sites keep client-side highlighting
+    pub enabled: bool,
+    /// Highlighting theme used in light mode
+    pub theme: String,
+    /// Highlighting theme used in dark mode
+    pub dark_theme: String,
+    /// Fence languages to highlight; empty means every known language
+    pub languages: Vec<String>,
+    /// How to render code blocks in unknown or disabled languages
+    pub fallback: HighlightFallback,
+}
+
+impl Default for HighlightConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            theme: "InspiredGitHub".to_string(),
+            dark_theme: "base16-ocean.dark".to_string(),
+            languages: Vec::new(),
+            fallback: HighlightFallback::Client,
+        }
+    }
+}
+
+/// Rendering of code blocks that are not highlighted at build time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HighlightFallback {
+    /// Keep the `language-*` class so the theme's client-side highlighter can run
+    Client,
+    /// Emit plain escaped code without any highlighting
+    Plain,
+}
+
+/// A single navigation menu entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MenuItem {
+    pub title: String,
+    /// Site-absolute path (e.g. `/pages/about.html`) or external URL
+    pub url: String,
+}
+
+/// Site settings that can be overridden for a single language
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct LanguageConfig {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub base_url: Option<String>,
+    pub menu: Option<Vec<MenuItem>>,
 }
 
 impl SiteConfig {
     pub fn load_from_path<P: AsRef<Path>>(site_dir: P) -> KrikResult<Self> {
         let site_dir = site_dir.as_ref();
 
-        // Try loading from root directory first
-        let config_path = site_dir.join("site.toml");
-        if let Some(config) = Self::try_load_config(&config_path)? {
-            return Ok(config);
-        }
+        // Try loading from root directory first, then from the content directory
+        let content_dir = site_dir.join("content");
+        let (mut config, content_dir) =
+            if let Some(config) = Self::try_load_config(&site_dir.join("site.toml"))? {
+                (config, site_dir)
+            } else if let Some(config) = Self::try_load_config(&content_dir.join("site.toml"))? {
+                (config, content_dir.as_path())
+            } else {
+                // Default configuration
+                (Self::default(), site_dir)
+            };
 
-        // Try loading from content directory
-        let content_config_path = site_dir.join("content").join("site.toml");
-        if let Some(config) = Self::try_load_config(&content_config_path)? {
-            return Ok(config);
+        config.ignore = IgnoreRules::load(content_dir, &config.exclude)?;
+        for mount in &mut config.mounts {
+            mount.resolve(content_dir)?;
         }
-
-        // Return default configuration
-        Ok(Self::default())
+        Ok(config)
     }
 
     fn try_load_config(config_path: &Path) -> KrikResult<Option<Self>> {
@@ -64,6 +314,65 @@ impl SiteConfig {
         Ok(None)
     }
 
+    /// The mount whose directory contains `path`
+    pub fn mount_containing(&self, path: &Path) -> Option<&Mount> {
+        self.mounts
+            .iter()
+            .find(|mount| path.starts_with(&mount.dir))
+    }
+
+    /// The content directory `source_dir` or the mounted directory holding `path`
+    pub fn root_dir<'a>(&'a self, source_dir: &'a Path, path: &Path) -> &'a Path {
+        self.mount_containing(path)
+            .map_or(source_dir, |mount| mount.dir.as_path())
+    }
+
+    /// Return true when `path` is matched by the ignore patterns of the content
+    /// directory or of the mount containing it
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        match self.mount_containing(path) {
+            Some(mount) => mount.ignore.is_ignored(path),
+            None => self.ignore.is_ignored(path),
+        }
+    }
+
+    /// Path of a file in `source_dir` or in a mounted directory relative to the
+    /// site root, e.g. `docs/setup.md` for `setup.md` mounted at `docs`
+    pub fn site_path(&self, source_dir: &Path, path: &Path) -> Option<PathBuf> {
+        match self.mount_containing(path) {
+            Some(mount) => mount.site_path(path),
+            None => path.strip_prefix(source_dir).ok().map(Path::to_path_buf),
+        }
+    }
+
+    /// The directory `document` was read from: the mount recorded when it was
+    /// scanned, or `source_dir`
+    pub fn content_root<'a>(
+        &'a self,
+        source_dir: &'a Path,
+        document: &'a Document,
+    ) -> ContentRoot<'a> {
+        let mounted = document.mount_dir.as_deref().and_then(|dir| {
+            let mount = self.mounts.iter().find(|mount| mount.dir == dir)?;
+            let relative = Path::new(&document.file_path)
+                .strip_prefix(mount.target_path())
+                .ok()?;
+            Some((mount, relative.to_str()?))
+        });
+        match mounted {
+            Some((mount, path)) => ContentRoot {
+                dir: &mount.dir,
+                path,
+                ignore: &mount.ignore,
+            },
+            None => ContentRoot {
+                dir: source_dir,
+                path: &document.file_path,
+                ignore: &self.ignore,
+            },
+        }
+    }
+
     pub fn get_site_title(&self) -> String {
         self.title
             .clone()
@@ -73,4 +382,37 @@ impl SiteConfig {
     pub fn get_base_url(&self) -> Option<String> {
         self.base_url.clone()
     }
+
+    /// Resolve the configuration for `language`, applying its `[languages.<code>]`
+    /// overrides on top of the site-wide values.
+    pub fn for_language(&self, language: &str) -> SiteConfig {
+        let mut resolved = self.clone();
+        if let Some(overrides) = self.languages.get(language) {
+            if overrides.title.is_some() {
+                resolved.title = overrides.title.clone();
+            }
+            if overrides.description.is_some() {
+                resolved.description = overrides.description.clone();
+            }
+            if overrides.base_url.is_some() {
+                resolved.base_url = overrides.base_url.clone();
+            }
+            if overrides.menu.is_some() {
+                resolved.menu = overrides.menu.clone();
+            }
+        }
+        resolved
+    }
+
+    /// Languages (other than the default) that get their own Atom feed
+    pub fn feed_languages(&self) -> Vec<String> {
+        let mut languages: Vec<String> = self
+            .languages
+            .keys()
+            .filter(|lang| lang.as_str() != crate::i18n::DEFAULT_LANGUAGE)
+            .cloned()
+            .collect();
+        languages.sort();
+        languages
+    }
 }
@@ -0,0 +1,102 @@
+//! Content mounts
+//!
+//! Directories outside the content directory, such as a handbook checked out
+//! next to the site, can be mounted into it with `[[mounts]]` tables in
+//! `site.toml`. Their files are scanned, copied and watched as if they lived
+//! under `target` in the content directory, so `../handbook/setup.md` mounted
+//! at `docs` becomes the document `docs/setup.md`.
+
+use super::IgnoreRules;
+use crate::error::{ConfigError, ConfigErrorKind, KrikError, KrikResult};
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+
+/// A directory mounted into the site from a `[[mounts]]` table
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Mount {
+    /// Directory to mount, relative to the directory of `site.toml`
+    pub source: PathBuf,
+    /// Path of the mounted files within the site, e.g. `docs`
+    pub target: String,
+    /// Canonical mounted directory, set by `resolve`
+    #[serde(skip)]
+    pub dir: PathBuf,
+    /// Patterns of the mounted directory's own `.krikignore`, set by `resolve`
+    #[serde(skip)]
+    pub ignore: IgnoreRules,
+}
+
+/// Where the file behind a site-relative document path lives on disk
+#[derive(Debug, Clone, Copy)]
+pub struct ContentRoot<'a> {
+    /// Content directory or mounted directory holding the file
+    pub dir: &'a Path,
+    /// Path of the file relative to `dir`
+    pub path: &'a str,
+    /// Ignore patterns that apply within `dir`
+    pub ignore: &'a IgnoreRules,
+}
+
+impl ContentRoot<'_> {
+    /// Absolute path of the file
+    pub fn file(&self) -> PathBuf {
+        self.dir.join(self.path)
+    }
+}
+
+impl Mount {
+    /// Resolve `source` against `site_dir`, check `target` and load the mounted
+    /// directory's `.krikignore`
+    pub fn resolve(&mut self, site_dir: &Path) -> KrikResult<()> {
+        let source = site_dir.join(&self.source);
+        self.dir = std::fs::canonicalize(&source)
+            .ok()
+            .filter(|dir| dir.is_dir())
+            .ok_or_else(|| {
+                invalid_mount(
+                    "mounts.source",
+                    "an existing directory",
+                    &source.display().to_string(),
+                )
+            })?;
+
+        let target = self.target_path();
+        if target.as_os_str().is_empty()
+            || !target
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err(invalid_mount(
+                "mounts.target",
+                "a relative path within the site, e.g. \"docs\"",
+                &self.target,
+            ));
+        }
+
+        self.ignore = IgnoreRules::load(&self.dir, &[])?;
+        Ok(())
+    }
+
+    /// `target` without leading or trailing slashes
+    pub fn target_path(&self) -> &Path {
+        Path::new(self.target.trim_matches('/'))
+    }
+
+    /// Site-relative path of `path` when it lies in the mounted directory
+    pub fn site_path(&self, path: &Path) -> Option<PathBuf> {
+        let relative = path.strip_prefix(&self.dir).ok()?;
+        Some(self.target_path().join(relative))
+    }
+}
+
+fn invalid_mount(field: &str, expected: &str, found: &str) -> KrikError {
+    KrikError::Config(Box::new(ConfigError {
+        kind: ConfigErrorKind::InvalidValue {
+            field: field.to_string(),
+            expected: expected.to_string(),
+            found: found.to_string(),
+        },
+        path: None,
+        context: "Resolving content mounts".to_string(),
+    }))
+}
@@ -0,0 +1,267 @@
+//! Fingerprinted theme assets
+//!
+//! Theme assets are processed (Sass compiled, minified, bundled) once per build. Every file in
+//! a theme's `assets/` directory and every bundle is also published under a
+//! content-hashed name (`css/main.css` → `css/main.3f9a1c2b.css`), so caches can
+//! keep it forever and deploys never serve stale styles. The mapping is written
+//! to `assets/manifest.json` and exposed to templates through the `asset_url` and
+//! `asset_integrity` Tera functions.
+
+use super::sass::{self, CompiledStylesheet};
+use super::BundleConfig;
+use crate::error::{IoError, IoErrorKind, KrikError, KrikResult, ThemeError, ThemeErrorKind};
+use crate::generator::assets::is_ignored_asset;
+use crate::generator::minify::{self, AssetProcessing};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha384};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tera::{Tera, Value};
+use walkdir::WalkDir;
+
+/// Manifest file name, relative to the output `assets/` directory
+pub const MANIFEST_PATH: &str = "manifest.json";
+
+/// Number of hex digits of the content hash used in fingerprinted names
+const HASH_LENGTH: usize = 8;
+
+/// A fingerprinted theme asset
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AssetEntry {
+    /// Content-hashed path, relative to the `assets/` directory
+    pub path: String,
+    /// Subresource Integrity hash (`sha384-...`)
+    pub integrity: String,
+    /// Published contents, after minification or bundling
+    #[serde(skip)]
+    pub contents: Vec<u8>,
+}
+
+/// Theme assets keyed by their path relative to the `assets/` directory
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AssetManifest {
+    pub assets: BTreeMap<String, AssetEntry>,
+    /// Compiled Sass stylesheets keyed by their output path, with their source
+    #[serde(skip)]
+    pub stylesheets: BTreeMap<String, (PathBuf, CompiledStylesheet)>,
+}
+
+impl AssetManifest {
+    /// Process and hash every asset in a theme's `assets/` directory, compile its
+    /// Sass stylesheets, then build the configured bundles from the processed files
+    pub fn build(
+        asset_dir: &Path,
+        bundles: &[BundleConfig],
+        processing: &AssetProcessing,
+    ) -> KrikResult<Self> {
+        let mut manifest = Self::default();
+        let mut stylesheets = Vec::new();
+        for entry in WalkDir::new(asset_dir)
+            .follow_links(true)
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            let path = entry.path();
+            if !entry.file_type().is_file() || is_ignored_asset(path) {
+                continue;
+            }
+            if sass::is_sass(path) {
+                if !sass::is_partial(path) {
+                    stylesheets.push(path.to_path_buf());
+                }
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(asset_dir) else {
+                continue;
+            };
+            let mut bytes = fs::read(path).map_err(|e| {
+                KrikError::Io(Box::new(IoError {
+                    kind: IoErrorKind::ReadFailed(e),
+                    path: path.to_path_buf(),
+                    context: "Reading theme asset".to_string(),
+                }))
+            })?;
+            if processing.minify && minify::is_minifiable(path) {
+                if let Ok(source) = std::str::from_utf8(&bytes) {
+                    bytes = minify::minify(path, source).into_bytes();
+                }
+            }
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            manifest.insert(&relative, bytes);
+        }
+
+        // Compiled stylesheets take precedence over plain CSS files of the same name
+        for path in stylesheets {
+            manifest.compile_stylesheet(asset_dir, &path, processing)?;
+        }
+        manifest.build_bundles(asset_dir, bundles, processing)?;
+        Ok(manifest)
+    }
+
+    /// Recompile the stylesheets that loaded `changed` (a Sass entry point or
+    /// partial), or `changed` itself when it is a new entry point, then rebuild
+    /// the bundles
+    pub fn recompile_stylesheets(
+        &mut self,
+        asset_dir: &Path,
+        changed: &Path,
+        bundles: &[BundleConfig],
+        processing: &AssetProcessing,
+    ) -> KrikResult<()> {
+        let changed = fs::canonicalize(changed).unwrap_or_else(|_| changed.to_path_buf());
+        let mut sources: Vec<PathBuf> = self
+            .stylesheets
+            .values()
+            .filter(|(_, compiled)| compiled.dependencies.contains(&changed))
+            .map(|(source, _)| source.clone())
+            .collect();
+        if !sass::is_partial(&changed) && !sources.contains(&changed) {
+            sources.push(changed);
+        }
+        for source in sources {
+            self.compile_stylesheet(asset_dir, &source, processing)?;
+        }
+        self.build_bundles(asset_dir, bundles, processing)
+    }
+
+    /// Compile one Sass entry point and add it as `.css`
+    fn compile_stylesheet(
+        &mut self,
+        asset_dir: &Path,
+        path: &Path,
+        processing: &AssetProcessing,
+    ) -> KrikResult<()> {
+        let asset_dir = fs::canonicalize(asset_dir).unwrap_or_else(|_| asset_dir.to_path_buf());
+        let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let Ok(relative) = path.strip_prefix(&asset_dir) else {
+            return Ok(());
+        };
+        let output = sass::css_path(&relative.to_string_lossy().replace('\\', "/"));
+        let compiled = sass::compile(&asset_dir, &path, processing.minify)?;
+        self.insert(&output, compiled.css.clone().into_bytes());
+        self.stylesheets.insert(output, (path, compiled));
+        Ok(())
+    }
+
+    /// Concatenate the configured bundles from the processed member files
+    fn build_bundles(
+        &mut self,
+        asset_dir: &Path,
+        bundles: &[BundleConfig],
+        processing: &AssetProcessing,
+    ) -> KrikResult<()> {
+        for bundle in bundles {
+            let bundle_dir = Path::new(&bundle.output).parent().unwrap_or(Path::new(""));
+            let mut members = Vec::new();
+            for file in &bundle.files {
+                let entry = self.get(file).ok_or_else(|| {
+                    KrikError::Theme(Box::new(ThemeError {
+                        kind: ThemeErrorKind::AssetError(format!(
+                            "Bundle {} includes {}, which is not a theme asset",
+                            bundle.output, file
+                        )),
+                        theme_path: asset_dir.parent().unwrap_or(asset_dir).to_path_buf(),
+                        context: "Bundling theme assets".to_string(),
+                    }))
+                })?;
+                let relative = pathdiff::diff_paths(file.trim_start_matches('/'), bundle_dir)
+                    .map(|path| path.to_string_lossy().replace('\\', "/"))
+                    .unwrap_or_else(|| file.clone());
+                members.push((
+                    relative,
+                    String::from_utf8_lossy(&entry.contents).into_owned(),
+                ));
+            }
+            let output = minify::bundle(&bundle.output, &members, processing.source_maps);
+            if let Some(source_map) = output.source_map {
+                self.insert(&format!("{}.map", bundle.output), source_map.into_bytes());
+            }
+            self.insert(&bundle.output, output.contents.into_bytes());
+        }
+        Ok(())
+    }
+
+    /// Add an asset given its path relative to the `assets/` directory and its
+    /// published contents
+    pub fn insert(&mut self, path: &str, contents: Vec<u8>) {
+        self.assets.insert(
+            path.trim_start_matches('/').to_string(),
+            AssetEntry {
+                path: fingerprinted_path(path.trim_start_matches('/'), &contents),
+                integrity: integrity(&contents),
+                contents,
+            },
+        );
+    }
+
+    /// Look up an asset by its original path; a leading `/` is ignored
+    pub fn get(&self, path: &str) -> Option<&AssetEntry> {
+        self.assets.get(path.trim_start_matches('/'))
+    }
+}
+
+/// Insert a short content hash before the extension: `css/main.css` → `css/main.3f9a1c2b.css`
+pub fn fingerprinted_path(path: &str, bytes: &[u8]) -> String {
+    let hash: String = Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>()[..HASH_LENGTH]
+        .to_string();
+    let (dir, file_name) = match path.rfind('/') {
+        Some(slash) => path.split_at(slash + 1),
+        None => ("", path),
+    };
+    match file_name.rfind('.') {
+        Some(dot) if dot > 0 => format!("{dir}{}.{hash}{}", &file_name[..dot], &file_name[dot..]),
+        _ => format!("{dir}{file_name}.{hash}"),
+    }
+}
+
+/// Subresource Integrity value for the given contents
+pub fn integrity(bytes: &[u8]) -> String {
+    format!("sha384-{}", STANDARD.encode(Sha384::digest(bytes)))
+}
+
+/// Register the `asset_url` and `asset_integrity` template functions.
+///
+/// Both take the asset's path relative to the theme's `assets/` directory and fail
+/// the render when it is not a theme asset:
+///
+/// ```text
+/// <link rel="stylesheet" href="{{ assets_path }}/{{ asset_url(path="css/main.css") }}"
+///       integrity="{{ asset_integrity(path="css/main.css") }}">
+/// ```
+pub fn register_functions(tera: &mut Tera, manifest: &AssetManifest) {
+    let urls = manifest.clone();
+    tera.register_function(
+        "asset_url",
+        move |args: &HashMap<String, Value>| -> tera::Result<Value> {
+            lookup(&urls, "asset_url", args).map(|entry| Value::String(entry.path.clone()))
+        },
+    );
+    let hashes = manifest.clone();
+    tera.register_function(
+        "asset_integrity",
+        move |args: &HashMap<String, Value>| -> tera::Result<Value> {
+            lookup(&hashes, "asset_integrity", args)
+                .map(|entry| Value::String(entry.integrity.clone()))
+        },
+    );
+}
+
+fn lookup<'a>(
+    manifest: &'a AssetManifest,
+    function: &str,
+    args: &HashMap<String, Value>,
+) -> tera::Result<&'a AssetEntry> {
+    let path = args
+        .get("path")
+        .and_then(Value::as_str)
+        .ok_or_else(|| tera::Error::msg(format!("{function}: missing string argument `path`")))?;
+    manifest
+        .get(path)
+        .ok_or_else(|| tera::Error::msg(format!("{function}: '{path}' is not a theme asset")))
+}
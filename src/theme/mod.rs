@@ -1,11 +1,16 @@
 use crate::error::{
     ConfigError, ConfigErrorKind, KrikError, KrikResult, ThemeError, ThemeErrorKind,
 };
+use crate::generator::minify::AssetProcessing;
+use fingerprint::AssetManifest;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use tera::{Context, Tera};
 
+pub mod fingerprint;
+pub mod sass;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThemeConfig {
     pub name: String,
@@ -13,6 +18,18 @@ pub struct ThemeConfig {
     pub author: Option<String>,
     pub description: Option<String>,
     pub templates: HashMap<String, String>,
+    /// Asset bundles from `[[bundles]]` tables
+    #[serde(default)]
+    pub bundles: Vec<BundleConfig>,
+}
+
+/// Theme assets concatenated into one file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleConfig {
+    /// Path of the bundle relative to `assets/`, e.g. `css/bundle.css`
+    pub output: String,
+    /// Files relative to `assets/`, concatenated in this order
+    pub files: Vec<String>,
 }
 
 #[derive(Debug)]
@@ -20,6 +37,8 @@ pub struct Theme {
     pub config: ThemeConfig,
     pub templates: Tera,
     pub theme_path: PathBuf,
+    /// Content-hashed names of the files in `assets/`
+    pub assets: AssetManifest,
 }
 
 impl Theme {
@@ -101,10 +120,40 @@ index = "index"
             if let Ok(new_tera) = Tera::new(&format!("{}/**/*.html", templates_path.display())) {
                 let mut tera = new_tera;
                 tera.autoescape_on(vec![]);
+                fingerprint::register_functions(&mut tera, &self.assets);
                 self.templates = tera;
             }
         }
     }
+
+    /// Re-process and re-hash the files in `assets/` and update the asset template
+    /// functions. Call after theme assets change so templates link to the new names.
+    pub fn refresh_assets(&mut self, processing: &AssetProcessing) -> KrikResult<()> {
+        self.assets = AssetManifest::build(
+            &self.theme_path.join("assets"),
+            &self.config.bundles,
+            processing,
+        )?;
+        fingerprint::register_functions(&mut self.templates, &self.assets);
+        Ok(())
+    }
+
+    /// Recompile only the Sass stylesheets affected by a changed `.scss`/`.sass`
+    /// file (such as a partial) and rebuild the bundles
+    pub fn recompile_stylesheets(
+        &mut self,
+        changed: &Path,
+        processing: &AssetProcessing,
+    ) -> KrikResult<()> {
+        self.assets.recompile_stylesheets(
+            &self.theme_path.join("assets"),
+            changed,
+            &self.config.bundles,
+            processing,
+        )?;
+        fingerprint::register_functions(&mut self.templates, &self.assets);
+        Ok(())
+    }
 }
 
 /// Builder for configuring and constructing a Theme instance
@@ -204,7 +253,9 @@ impl ThemeBuilder {
             config,
             templates,
             theme_path,
+            assets: AssetManifest::default(),
         };
+        theme.refresh_assets(&AssetProcessing::default())?;
 
         // Optionally trigger an initial reload to ensure file-based templates are fresh
         if self.enable_reload {
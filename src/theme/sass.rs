@@ -0,0 +1,119 @@
+//! Sass/SCSS compilation of theme assets
+//!
+//! `.scss` and `.sass` files under a theme's `assets/` directory are compiled to
+//! `.css` with the pure-Rust `grass` compiler. Partials (`_colors.scss`) are only
+//! compiled through the stylesheets that load them; the files each stylesheet
+//! loads are recorded so the dev server can recompile just the stylesheets that
+//! depend on a changed partial.
+
+use crate::error::{KrikError, KrikResult, ThemeError, ThemeErrorKind};
+use std::cell::RefCell;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Extensions of Sass sources
+pub const SASS_EXTENSIONS: &[&str] = &["scss", "sass"];
+
+/// A compiled stylesheet and every file it loaded, including itself
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CompiledStylesheet {
+    pub css: String,
+    pub dependencies: Vec<PathBuf>,
+}
+
+/// File system for the compiler that records the files it reads
+#[derive(Debug, Default)]
+struct RecordingFs {
+    loaded: RefCell<Vec<PathBuf>>,
+}
+
+impl grass::Fs for RecordingFs {
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let bytes = std::fs::read(path)?;
+        let canonical = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let mut loaded = self.loaded.borrow_mut();
+        if !loaded.contains(&canonical) {
+            loaded.push(canonical);
+        }
+        Ok(bytes)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        std::fs::canonicalize(path)
+    }
+}
+
+/// Return true for `.scss` and `.sass` files
+pub fn is_sass(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| SASS_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// Return true for partials, which start with an underscore and produce no output
+pub fn is_partial(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('_'))
+}
+
+/// Output path of a stylesheet: `css/main.scss` → `css/main.css`
+pub fn css_path(path: &str) -> String {
+    match path.rfind('.') {
+        Some(dot) if !path[dot..].contains('/') => format!("{}.css", &path[..dot]),
+        _ => format!("{path}.css"),
+    }
+}
+
+/// Compile a Sass entry point, resolving `@use`/`@import` relative to the file
+/// and to the theme's `assets/` directory
+pub fn compile(asset_dir: &Path, path: &Path, compressed: bool) -> KrikResult<CompiledStylesheet> {
+    let fs = RecordingFs::default();
+    let style = if compressed {
+        grass::OutputStyle::Compressed
+    } else {
+        grass::OutputStyle::Expanded
+    };
+    let options = grass::Options::default()
+        .fs(&fs)
+        .load_path(asset_dir)
+        .style(style);
+    let css = grass::from_path(path, &options).map_err(|e| compile_error(asset_dir, path, *e))?;
+    Ok(CompiledStylesheet {
+        css,
+        dependencies: fs.loaded.into_inner(),
+    })
+}
+
+/// Map a compiler error to a `ThemeError` pointing at the offending file and line
+fn compile_error(asset_dir: &Path, entry: &Path, error: grass::Error) -> KrikError {
+    let (file, line, column, message) = match error.kind() {
+        grass::ErrorKind::ParseError { message, loc, .. } => (
+            PathBuf::from(loc.file.name()),
+            Some(loc.begin.line + 1),
+            Some(loc.begin.column + 1),
+            message,
+        ),
+        grass::ErrorKind::IoError(e) => (entry.to_path_buf(), None, None, e.to_string()),
+        grass::ErrorKind::FromUtf8Error(e) => (entry.to_path_buf(), None, None, e),
+        other => (entry.to_path_buf(), None, None, format!("{other:?}")),
+    };
+    KrikError::Theme(Box::new(ThemeError {
+        kind: ThemeErrorKind::StyleCompileError {
+            file,
+            line,
+            column,
+            message,
+        },
+        theme_path: asset_dir.parent().unwrap_or(asset_dir).to_path_buf(),
+        context: format!("Compiling stylesheet {}", entry.display()),
+    }))
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use image::{Rgb, RgbImage};
+use krik::generator::bundles::{bundle_dir, is_bundle_index};
+use krik::generator::SiteGenerator;
+use tempfile::TempDir;
+
+use common::write_file;
+
+fn write_png(path: &Path, width: u32, height: u32) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    RgbImage::from_pixel(width, height, Rgb([200, 100, 50]))
+        .save(path)
+        .unwrap();
+}
+
+fn site() -> TempDir {
+    let tmp = TempDir::new().unwrap();
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+    let theme_dir = tmp_dir.join("theme");
+    write_file(&content_dir.join("site.toml"), "title = \"Trips\"\n");
+    write_file(
+        &content_dir.join("posts/my-trip/index.md"),
+        "---\ntitle: My Trip\n---\n\n![Beach](photo.png)\n",
+    );
+    write_png(&content_dir.join("posts/my-trip/photo.png"), 4, 3);
+    write_png(&content_dir.join("posts/my-trip/gallery/dunes.png"), 2, 5);
+    write_file(&content_dir.join("posts/my-trip/route.gpx"), "<gpx/>");
+    write_file(
+        &content_dir.join("posts/my-trip/day-one/index.md"),
+        "---\ntitle: Day One\n---\n\nSunny.\n",
+    );
+    write_file(
+        &content_dir.join("posts/my-trip/day-one/notes.txt"),
+        "notes",
+    );
+    write_file(
+        &content_dir.join("posts/plain.md"),
+        "---\ntitle: Plain\n---\n\nNo bundle.\n",
+    );
+    write_file(
+        &theme_dir.join("templates/post.html"),
+        "{{ content | safe }}\n{% for r in resources %}{{ r.path }}|{{ r.name }}|{{ r.kind }}\
+         {% if r.width %}|{{ r.width }}x{{ r.height }}{% endif %};{% endfor %}",
+    );
+    write_file(&theme_dir.join("templates/index.html"), "index");
+    tmp
+}
+
+#[test]
+fn bundle_index_pages() {
+    assert!(is_bundle_index("posts/my-trip/index.md"));
+    assert!(is_bundle_index("posts/my-trip/index.it.md"));
+    assert!(!is_bundle_index("index.md"));
+    assert!(!is_bundle_index("posts/my-trip.md"));
+    assert!(!is_bundle_index("posts/indexes.md"));
+    assert_eq!(
+        bundle_dir("posts/my-trip/index.md"),
+        Some(Path::new("posts/my-trip"))
+    );
+}
+
+#[test]
+fn bundles_render_with_resources_copied_alongside() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = site();
+    let tmp_dir = tmp.path();
+    let output_dir = tmp_dir.join("_site");
+
+    let generator = SiteGenerator::new(
+        &tmp_dir.join("content"),
+        &output_dir,
+        Some(&tmp_dir.join("theme")),
+    )?;
+    generator.generate_site()?;
+
+    let page = fs::read_to_string(output_dir.join("posts/my-trip/index.html"))?;
+    assert!(page.contains("src=\"photo.png\""));
+    assert!(page.ends_with(
+        "gallery/dunes.png|dunes.png|image|2x5;photo.png|photo.png|image|4x3;\
+         route.gpx|route.gpx|file;"
+    ));
+    assert!(output_dir.join("posts/my-trip/photo.png").exists());
+    assert!(output_dir.join("posts/my-trip/gallery/dunes.png").exists());
+
+    // Nested bundles own their files
+    let nested = fs::read_to_string(output_dir.join("posts/my-trip/day-one/index.html"))?;
+    assert!(nested.ends_with("notes.txt|notes.txt|file;"));
+    let plain = fs::read_to_string(output_dir.join("posts/plain.html"))?;
+    assert!(!plain.contains('|'));
+
+    Ok(())
+}
+
+#[test]
+fn added_resources_re_render_the_bundle_page() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = site();
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+    let output_dir = tmp_dir.join("_site");
+
+    let mut generator =
+        SiteGenerator::new(&content_dir, &output_dir, Some(&tmp_dir.join("theme")))?;
+    generator.scan_files()?;
+    generator.generate_site()?;
+
+    write_file(&content_dir.join("posts/my-trip/ticket.pdf"), "%PDF");
+    let added = fs::canonicalize(content_dir.join("posts/my-trip/ticket.pdf"))?;
+    generator.generate_incremental_for_path(&added, false)?;
+
+    let page = fs::read_to_string(output_dir.join("posts/my-trip/index.html"))?;
+    assert!(page.ends_with("route.gpx|route.gpx|file;ticket.pdf|ticket.pdf|file;"));
+    assert!(output_dir.join("posts/my-trip/ticket.pdf").exists());
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use krik::generator::ast_parser::{
+    callouts_to_markdown, parse_markdown_ast, parse_markdown_ast_with_options, MarkdownOptions,
+};
+
+#[test]
+fn alerts_render_as_callouts() {
+    let html =
+        parse_markdown_ast("> [!WARNING]\n> Mind the **gap**.\n\n> Plain quote\n").html_content;
+    assert!(html.contains(
+        "<div class=\"callout callout-warning\">\n<p class=\"callout-title\">Warning</p>\n"
+    ));
+    assert!(html.contains("Mind the <strong>gap</strong>."));
+    assert!(!html.contains("[!WARNING]"));
+    assert!(html.contains("<blockquote>\n<p>Plain quote</p>\n</blockquote>"));
+}
+
+#[test]
+fn callout_titles_follow_document_language() {
+    let options = MarkdownOptions {
+        language: "it".to_string(),
+        ..Default::default()
+    };
+    let html = parse_markdown_ast_with_options("> [!TIP]\n> Prova.\n", &options).html_content;
+    assert!(html.contains("<p class=\"callout-title\">Suggerimento</p>"));
+}
+
+#[test]
+fn pdf_markdown_keeps_callouts_readable() {
+    let markdown = "> [!NOTE]\n> Read this.\n\n```\n> [!NOTE]\n```\n";
+    assert_eq!(
+        callouts_to_markdown(markdown, "de"),
+        "> **Hinweis**\\\n> Read this.\n\n```\n> [!NOTE]\n```\n"
+    );
+}
@@ -0,0 +1,98 @@
+use krik::generator::ast_parser::{parse_markdown_ast_with_options, MarkdownOptions};
+use krik::generator::code_blocks::parse_info_string;
+use krik::site::HighlightConfig;
+use std::fs;
+use tempfile::TempDir;
+
+fn render(markdown: &str, options: &MarkdownOptions) -> String {
+    parse_markdown_ast_with_options(markdown, options).html_content
+}
+
+#[test]
+fn parses_fence_attributes() {
+    let (lang, attrs) = parse_info_string("rust {linenos=true hl_lines=[3,5-7] title=\"main.rs\"}");
+    assert_eq!(lang, "rust");
+    assert!(attrs.linenos);
+    assert_eq!(attrs.hl_lines, vec![(3, 3), (5, 7)]);
+    assert_eq!(attrs.title.as_deref(), Some("main.rs"));
+}
+
+#[test]
+fn renders_line_numbers_highlighted_lines_and_title() {
+    let md =
+        "```rust {linenos=true hl_lines=[2] title=\"main.rs\"}\n/* a\n b */\nfn main() {}\n```\n";
+    let html = render(md, &MarkdownOptions::default());
+
+    assert!(html.contains("<figure class=\"code-block\">"));
+    assert!(html.contains("<figcaption class=\"code-title\">main.rs</figcaption>"));
+    assert_eq!(html.matches("<span class=\"line-number\"").count(), 3);
+    assert_eq!(html.matches("<span class=\"line hl-line\">").count(), 1);
+    assert!(html.contains("<span class=\"line-number\" aria-hidden=\"true\">3</span>"));
+    assert_eq!(
+        html.matches("<span").count(),
+        html.matches("</span>").count(),
+        "spans must stay balanced per line"
+    );
+}
+
+#[test]
+fn includes_line_range_from_file() {
+    let dir = TempDir::new().unwrap();
+    fs::create_dir_all(dir.path().join("posts")).unwrap();
+    fs::write(
+        dir.path().join("example.py"),
+        "one = 1\ntwo = 2\nthree = 3\nfour = 4\n",
+    )
+    .unwrap();
+    let options = MarkdownOptions {
+        source_path: Some(dir.path().join("posts/guide.md")),
+        source_root: Some(dir.path().to_path_buf()),
+        highlight: HighlightConfig {
+            enabled: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let md = "```{include=\"../example.py\" lines=\"2-3\" linenos=true}\n```\n";
+    let html = render(md, &options);
+    assert!(html.contains("data-lang=\"py\""));
+    assert!(html.contains("two = 2"));
+    assert!(html.contains("three = 3"));
+    assert!(!html.contains("one = 1"));
+    assert!(html.contains("<span class=\"line-number\" aria-hidden=\"true\">2</span>"));
+}
+
+#[test]
+fn includes_outside_the_content_directory_are_rejected() {
+    let dir = TempDir::new().unwrap();
+    fs::create_dir_all(dir.path().join("content/posts")).unwrap();
+    fs::write(dir.path().join("secret.txt"), "token = 42\n").unwrap();
+    let options = MarkdownOptions {
+        source_path: Some(dir.path().join("content/posts/guide.md")),
+        source_root: Some(dir.path().join("content")),
+        ..Default::default()
+    };
+
+    let md = "```text {include=\"../../secret.txt\"}\n```\n";
+    assert!(!render(md, &options).contains("token = 42"));
+    let out = krik::generator::code_blocks::expand_includes(
+        md,
+        &dir.path().join("content/posts/guide.md"),
+        &dir.path().join("content"),
+    );
+    assert_eq!(out, md);
+}
+
+#[test]
+fn expand_includes_rewrites_markdown_for_pdf() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("main.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+    let md = "Intro\n\n```rust {include=\"main.rs\" lines=\"2-2\"}\n```\n\n```text\nkept\n```\n";
+    let out =
+        krik::generator::code_blocks::expand_includes(md, &dir.path().join("doc.md"), dir.path());
+    assert_eq!(
+        out,
+        "Intro\n\n```rust\nfn b() {}\n```\n\n```text\nkept\n```\n"
+    );
+}
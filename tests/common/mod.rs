@@ -0,0 +1,12 @@
+//! Helpers shared by the integration tests
+
+use std::fs;
+use std::path::Path;
+
+/// Write `contents` to `path`, creating missing parent directories
+pub fn write_file(path: &Path, contents: &str) {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+    fs::write(path, contents).unwrap();
+}
@@ -0,0 +1,174 @@
+mod common;
+
+use std::fs;
+use std::io::Read;
+
+use flate2::read::GzDecoder;
+use krik::generator::compress::{compress_output, manifest_path};
+use krik::generator::SiteGenerator;
+use krik::server::static_files::serve_static_files;
+use tempfile::TempDir;
+
+use common::write_file;
+
+fn site() -> TempDir {
+    let tmp = TempDir::new().unwrap();
+    let tmp_dir = tmp.path();
+    let theme_dir = tmp_dir.join("theme");
+    write_file(&tmp_dir.join("content/site.toml"), "title = \"Packed\"\n");
+    write_file(
+        &tmp_dir.join("content/posts/long.md"),
+        &format!(
+            "---\ntitle: Long\n---\n\n{}",
+            "A paragraph that repeats. ".repeat(200)
+        ),
+    );
+    write_file(
+        &tmp_dir.join("content/posts/other.md"),
+        &format!(
+            "---\ntitle: Other\n---\n\n{}",
+            "Another paragraph. ".repeat(200)
+        ),
+    );
+    write_file(
+        &tmp_dir.join("content/posts/short.md"),
+        "---\ntitle: Short\n---\n\nHi.\n",
+    );
+    write_file(&tmp_dir.join("content/images/logo.png"), &"x".repeat(4096));
+    write_file(
+        &theme_dir.join("templates/post.html"),
+        "<main>{{ content | safe }}</main>",
+    );
+    write_file(&theme_dir.join("templates/index.html"), "index");
+    tmp
+}
+
+fn gunzip(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    GzDecoder::new(bytes).read_to_end(&mut out).unwrap();
+    out
+}
+
+fn unbrotli(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    brotli::Decompressor::new(bytes, 4096)
+        .read_to_end(&mut out)
+        .unwrap();
+    out
+}
+
+#[test]
+fn compress_writes_siblings_for_large_text_files() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = site();
+    let tmp_dir = tmp.path();
+    let output_dir = tmp_dir.join("_site");
+
+    let mut generator = SiteGenerator::new(
+        &tmp_dir.join("content"),
+        &output_dir,
+        Some(&tmp_dir.join("theme")),
+    )?;
+    generator.compress = true;
+    generator.generate_site()?;
+
+    let page = fs::read(output_dir.join("posts/long.html"))?;
+    assert_eq!(
+        gunzip(&fs::read(output_dir.join("posts/long.html.gz"))?),
+        page
+    );
+    assert_eq!(
+        unbrotli(&fs::read(output_dir.join("posts/long.html.br"))?),
+        page
+    );
+    assert!(output_dir.join("posts/other.html.br").exists());
+    assert!(!output_dir.join("posts/short.html.gz").exists());
+    assert!(!output_dir.join("images/logo.png.gz").exists());
+
+    // The sibling manifest stays out of the deployable output
+    let manifest = fs::read_to_string(manifest_path(&tmp_dir.join("content"), &output_dir))?;
+    assert!(manifest.lines().any(|line| line == "posts/long.html.gz"));
+    assert!(fs::read_dir(&output_dir)?
+        .filter_map(|e| e.ok())
+        .all(|e| !e.file_name().to_string_lossy().starts_with(".krik")));
+
+    // Fresh siblings are kept; siblings of removed files are cleaned up
+    fs::remove_file(output_dir.join("posts/long.html"))?;
+    let before = fs::metadata(output_dir.join("posts/other.html.br"))?.modified()?;
+    compress_output(&tmp_dir.join("content"), &output_dir)?;
+    assert!(!output_dir.join("posts/long.html.gz").exists());
+    assert!(!output_dir.join("posts/long.html.br").exists());
+    assert_eq!(
+        fs::metadata(output_dir.join("posts/other.html.br"))?.modified()?,
+        before
+    );
+
+    Ok(())
+}
+
+#[test]
+fn compress_keeps_archives_and_other_site_files() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = site();
+    let tmp_dir = tmp.path();
+    let output_dir = tmp_dir.join("_site");
+    write_file(
+        &tmp_dir.join("content/downloads/source.tar.gz"),
+        "not really gzip",
+    );
+    write_file(&tmp_dir.join("content/data/feed.json.br"), "brotli data");
+
+    let mut generator = SiteGenerator::new(
+        &tmp_dir.join("content"),
+        &output_dir,
+        Some(&tmp_dir.join("theme")),
+    )?;
+    generator.compress = true;
+    generator.generate_site()?;
+    compress_output(&tmp_dir.join("content"), &output_dir)?;
+
+    assert!(output_dir.join("downloads/source.tar.gz").exists());
+    assert!(output_dir.join("data/feed.json.br").exists());
+    assert!(output_dir.join("posts/long.html.gz").exists());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn dev_server_negotiates_precompressed_files() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = site();
+    let tmp_dir = tmp.path();
+    let output_dir = tmp_dir.join("_site");
+    let page = "<p>compressible</p>".repeat(100);
+    write_file(&output_dir.join("posts/index.html"), &page);
+    compress_output(&tmp_dir.join("content"), &output_dir)?;
+    let routes = serve_static_files(output_dir.clone());
+
+    let response = warp::test::request()
+        .path("/posts/")
+        .header("accept-encoding", "gzip, br")
+        .reply(&routes)
+        .await;
+    assert_eq!(response.headers()["content-encoding"], "br");
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/html; charset=utf-8"
+    );
+    assert_eq!(response.headers()["vary"], "Accept-Encoding");
+    assert_eq!(unbrotli(response.body()), page.as_bytes());
+
+    let response = warp::test::request()
+        .path("/posts/index.html")
+        .header("accept-encoding", "gzip, br;q=0")
+        .reply(&routes)
+        .await;
+    assert_eq!(response.headers()["content-encoding"], "gzip");
+    assert_eq!(gunzip(response.body()), page.as_bytes());
+
+    let response = warp::test::request()
+        .path("/posts/index.html")
+        .reply(&routes)
+        .await;
+    assert!(response.headers().get("content-encoding").is_none());
+    assert_eq!(response.body(), page.as_bytes());
+
+    Ok(())
+}
@@ -1,16 +1,14 @@
 use krik::content::{create_page, create_post};
-use std::fs;
-use std::path::Path;
+use tempfile::TempDir;
 
 #[test]
 fn create_page_and_post_smoke() {
-    let tmp = std::env::temp_dir().join(format!("krik_test_content_{}", std::process::id()));
-    let _ = fs::remove_dir_all(&tmp);
-    fs::create_dir_all(&tmp).unwrap();
+    let tmp = TempDir::new().unwrap();
+    let tmp_dir = tmp.path();
 
-    create_post(Path::new(&tmp), "Hello World", None).unwrap();
-    create_page(Path::new(&tmp), "About", None).unwrap();
+    create_post(tmp_dir, "Hello World", None).unwrap();
+    create_page(tmp_dir, "About", None).unwrap();
 
-    assert!(tmp.join("posts/hello-world.md").exists());
-    assert!(tmp.join("pages/about.md").exists());
+    assert!(tmp_dir.join("posts/hello-world.md").exists());
+    assert!(tmp_dir.join("pages/about.md").exists());
 }
@@ -0,0 +1,58 @@
+use krik::generator::ast_parser::{
+    parse_markdown_ast, parse_markdown_ast_with_options, MarkdownOptions,
+};
+use krik::site::{ExternalLinksConfig, LanguageConfig, SiteConfig};
+
+fn site_config() -> SiteConfig {
+    SiteConfig {
+        base_url: Some("https://example.com/blog/".to_string()),
+        external_links: ExternalLinksConfig {
+            rel: Some("noopener noreferrer nofollow".to_string()),
+            target: Some("_blank".to_string()),
+            class: Some("external".to_string()),
+            internal_domains: vec!["friends.org".to_string()],
+        },
+        languages: [(
+            "it".to_string(),
+            LanguageConfig {
+                base_url: Some("https://example.it/".to_string()),
+                ..Default::default()
+            },
+        )]
+        .into_iter()
+        .collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn external_links_get_configured_attributes() {
+    let options = MarkdownOptions::new(&site_config(), "en");
+    let markdown = "[Rust](https://www.rust-lang.org/learn?a=1&b=2 \"The \\\"Rust\\\" site\") and <https://crates.io>\n";
+    let html = parse_markdown_ast_with_options(markdown, &options).html_content;
+    assert!(html.contains(
+        "<a href=\"https://www.rust-lang.org/learn?a=1&amp;b=2\" title=\"The &quot;Rust&quot; site\" class=\"external\" rel=\"noopener noreferrer nofollow\" target=\"_blank\">Rust</a>"
+    ));
+    assert!(html.contains(
+        "<a href=\"https://crates.io\" class=\"external\" rel=\"noopener noreferrer nofollow\" target=\"_blank\">https://crates.io</a>"
+    ));
+}
+
+#[test]
+fn own_and_allowlisted_domains_stay_internal() {
+    let options = MarkdownOptions::new(&site_config(), "en");
+    let markdown = "[a](https://example.com/about.html) [b](https://docs.friends.org/) \
+                    [it](https://example.it/chi-siamo.html) [c](../posts/guide.md) [d](#top) [e](mailto:me@elsewhere.net)\n";
+    let html = parse_markdown_ast_with_options(markdown, &options).html_content;
+    assert!(!html.contains("external"));
+    assert!(!html.contains("target="));
+    assert!(html.contains("<a href=\"https://example.com/about.html\">a</a>"));
+    assert!(html.contains("<a href=\"https://docs.friends.org/\">b</a>"));
+    assert!(html.contains("<a href=\"https://example.it/chi-siamo.html\">it</a>"));
+}
+
+#[test]
+fn links_are_unchanged_without_a_policy() {
+    let html = parse_markdown_ast("[Rust](https://www.rust-lang.org/)\n").html_content;
+    assert!(html.contains("<a href=\"https://www.rust-lang.org/\">Rust</a>"));
+}
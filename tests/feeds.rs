@@ -2,8 +2,7 @@ use krik::generator::feeds::generate_feed;
 use krik::parser::{Document, FrontMatter};
 use krik::site::SiteConfig;
 use std::collections::HashMap;
-use std::fs;
-use std::path::Path;
+use tempfile::TempDir;
 
 #[test]
 fn feed_generation_smoke() {
@@ -27,13 +26,18 @@ fn feed_generation_smoke() {
         language: "en".into(),
         base_name: "test".into(),
         toc: None,
+        footnotes: Vec::new(),
+        links: Vec::new(),
+        resources: Vec::new(),
+        mount_dir: None,
     };
     let docs = vec![post];
-    let mut cfg = SiteConfig::default();
-    cfg.base_url = Some("https://example.com".into());
-    let out = std::env::temp_dir().join(format!("krik_test_feed_{}", std::process::id()));
-    let _ = fs::remove_dir_all(&out);
-    fs::create_dir_all(&out).unwrap();
-    generate_feed(&docs, &cfg, Path::new(&out)).unwrap();
+    let cfg = SiteConfig {
+        base_url: Some("https://example.com".into()),
+        ..Default::default()
+    };
+    let tmp = TempDir::new().unwrap();
+    let out = tmp.path();
+    generate_feed(&docs, &cfg, out).unwrap();
     assert!(out.join("feed.xml").exists());
 }
@@ -0,0 +1,100 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+
+use krik::generator::SiteGenerator;
+use krik::theme::fingerprint::{fingerprinted_path, integrity, AssetManifest};
+use tempfile::TempDir;
+
+use common::write_file;
+
+fn site() -> TempDir {
+    let tmp = TempDir::new().unwrap();
+    let tmp_dir = tmp.path();
+    write_file(&tmp_dir.join("content/site.toml"), "title = \"Hashes\"\n");
+    write_file(
+        &tmp_dir.join("content/posts/hello.md"),
+        "---\ntitle: Hello\n---\n\nHi.\n",
+    );
+    tmp
+}
+
+#[test]
+fn fingerprinted_names_and_integrity() {
+    assert_eq!(
+        fingerprinted_path("css/main.css", b"body{}"),
+        "css/main.7c98040a.css"
+    );
+    assert_eq!(fingerprinted_path("LICENSE", b"body{}"), "LICENSE.7c98040a");
+    assert_eq!(fingerprinted_path(".hidden", b"body{}"), ".hidden.7c98040a");
+    assert_eq!(
+        integrity(b"body{}"),
+        "sha384-myyg/hQ74aSgjBBvVME/QXAXEkT4Y9dHbVQ5C0lIyGpldvNLJV2IWc5ElXbqLi06"
+    );
+}
+
+#[test]
+fn default_theme_links_fingerprinted_assets() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = site();
+    let tmp_dir = tmp.path();
+    let output_dir = tmp_dir.join("_site");
+
+    let generator = SiteGenerator::new(&tmp_dir.join("content"), &output_dir, None::<&PathBuf>)?;
+    generator.generate_site()?;
+
+    let manifest: AssetManifest = serde_json::from_str(&format!(
+        "{{\"assets\": {}}}",
+        fs::read_to_string(output_dir.join("assets/manifest.json"))?
+    ))?;
+    let css = manifest.get("css/main.css").unwrap();
+    let original = fs::read(output_dir.join("assets/css/main.css"))?;
+    assert_eq!(css.path, fingerprinted_path("css/main.css", &original));
+    assert_eq!(
+        fs::read(output_dir.join("assets").join(&css.path))?,
+        original
+    );
+
+    let html = fs::read_to_string(output_dir.join("posts/hello.html"))?;
+    assert!(html.contains(&format!("href=\"../assets/{}\"", css.path)));
+    let js = manifest.get("js/main.js").unwrap();
+    assert!(html.contains(&format!("src=\"../assets/{}\"", js.path)));
+
+    Ok(())
+}
+
+#[test]
+fn templates_can_add_integrity_and_unknown_assets_fail() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = site();
+    let tmp_dir = tmp.path();
+    let theme_dir = tmp_dir.join("theme");
+    let output_dir = tmp_dir.join("_site");
+    write_file(&theme_dir.join("assets/app.js"), "console.log(1);\n");
+    write_file(
+        &theme_dir.join("templates/post.html"),
+        "<script src=\"{{ assets_path }}/{{ asset_url(path=\"app.js\") }}\" \
+         integrity=\"{{ asset_integrity(path=\"/app.js\") }}\"></script>",
+    );
+    write_file(&theme_dir.join("templates/index.html"), "index");
+
+    let generator = SiteGenerator::new(&tmp_dir.join("content"), &output_dir, Some(&theme_dir))?;
+    generator.generate_site()?;
+    let html = fs::read_to_string(output_dir.join("posts/hello.html"))?;
+    assert_eq!(
+        html,
+        format!(
+            "<script src=\"../assets/{}\" integrity=\"{}\"></script>",
+            fingerprinted_path("app.js", b"console.log(1);\n"),
+            integrity(b"console.log(1);\n")
+        )
+    );
+
+    write_file(
+        &theme_dir.join("templates/post.html"),
+        "{{ asset_url(path=\"missing.js\") }}",
+    );
+    let generator = SiteGenerator::new(&tmp_dir.join("content"), &output_dir, Some(&theme_dir))?;
+    assert!(generator.generate_site().is_err());
+
+    Ok(())
+}
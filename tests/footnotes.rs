@@ -0,0 +1,88 @@
+use krik::generator::ast_parser::{
+    parse_markdown_ast, parse_markdown_ast_with_options, MarkdownOptions,
+};
+use krik::generator::markdown::process_markdown_content_with_options;
+use krik::parser::parse_markdown_with_frontmatter;
+use krik::site::FootnoteStyle;
+
+const DOC: &str =
+    "First[^a] and second[^b], first again[^a].\n\n[^b]: Bee note.\n\n[^a]: Ay note.\n";
+
+#[test]
+fn endnotes_are_numbered_with_backlinks() {
+    let result = parse_markdown_ast(DOC);
+    let html = &result.html_content;
+
+    assert!(html.contains(
+        "First<sup class=\"footnote-reference\" id=\"fnref-1\"><a href=\"#fn-1\">1</a></sup>"
+    ));
+    assert!(html.contains(
+        "second<sup class=\"footnote-reference\" id=\"fnref-2\"><a href=\"#fn-2\">2</a></sup>"
+    ));
+    assert!(html.contains(
+        "again<sup class=\"footnote-reference\" id=\"fnref-1-2\"><a href=\"#fn-1\">1</a></sup>"
+    ));
+
+    let notes = html.split("<section class=\"footnotes\"").nth(1).unwrap();
+    let ay = notes.find("<li id=\"fn-1\"").unwrap();
+    let bee = notes.find("<li id=\"fn-2\"").unwrap();
+    assert!(ay < bee);
+    assert!(notes.contains("Ay note. <a href=\"#fnref-1\" class=\"footnote-return\""));
+    assert!(notes.contains("<a href=\"#fnref-1-2\" class=\"footnote-return\" role=\"doc-backlink\" aria-label=\"Back to reference 2\">↩<sup>2</sup></a></p>"));
+
+    let a = &result.footnotes["a"];
+    assert_eq!(a.number, 1);
+    assert_eq!(a.html, "<p>Ay note.</p>\n");
+    assert_eq!(a.backrefs, vec!["fnref-1", "fnref-1-2"]);
+}
+
+#[test]
+fn sidenotes_are_placed_at_the_first_reference() {
+    let options = MarkdownOptions {
+        footnotes: FootnoteStyle::Sidenotes,
+        ..Default::default()
+    };
+    let html = parse_markdown_ast_with_options(DOC, &options).html_content;
+    assert!(html.contains(
+        "<label for=\"sn-1\" id=\"fnref-1\" class=\"margin-toggle sidenote-number\">1</label>\
+         <input type=\"checkbox\" id=\"sn-1\" class=\"margin-toggle\">\
+         <span class=\"sidenote\" id=\"fn-1\"><span class=\"sidenote-label\">1</span> Ay note.</span>"
+    ));
+    assert!(html.contains("<a href=\"#fn-1\">1</a></sup>"));
+    assert!(!html.contains("class=\"footnotes\""));
+}
+
+#[test]
+fn sidenotes_with_block_content_stay_end_notes() {
+    let options = MarkdownOptions {
+        footnotes: FootnoteStyle::Sidenotes,
+        ..Default::default()
+    };
+    let source = "Short[^a] and long[^b].\n\n[^a]: One.\n\n    Two.\n\n[^b]: Steps:\n\n    - first\n    - second\n";
+    let html = parse_markdown_ast_with_options(source, &options).html_content;
+
+    assert!(html.contains("<span class=\"sidenote-label\">1</span> One.<br>Two.</span>"));
+    assert!(html.contains(
+        "long<sup class=\"footnote-reference\" id=\"fnref-2\"><a href=\"#fn-2\">2</a></sup>"
+    ));
+    let notes = html.split("<section class=\"footnotes\"").nth(1).unwrap();
+    assert!(!notes.contains("id=\"fn-1\""));
+    assert!(notes.contains("<li id=\"fn-2\" class=\"footnote-definition\">"));
+    assert!(notes.contains("<li>second</li>"));
+}
+
+#[test]
+fn footnotes_are_returned_for_templates() {
+    let source = "---\ntitle: Notes\nfootnotes: sidenotes\n---\nText[^n].\n\n[^n]: A note.\n";
+    let (front_matter, markdown) = parse_markdown_with_frontmatter(source).unwrap();
+    let rendered = process_markdown_content_with_options(
+        &markdown,
+        &front_matter,
+        &MarkdownOptions::default(),
+    )
+    .unwrap();
+    assert!(rendered.html.contains("class=\"sidenote\""));
+    assert_eq!(rendered.footnotes.len(), 1);
+    assert_eq!(rendered.footnotes[0].id, "n");
+    assert_eq!(rendered.footnotes[0].number, 1);
+}
@@ -0,0 +1,67 @@
+use krik::generator::ast_parser::{
+    parse_markdown_ast, parse_markdown_ast_with_options, slugify, MarkdownOptions,
+};
+use krik::site::{HeadingConfig, SiteConfig, SlugStrategy};
+
+#[test]
+fn duplicate_headings_get_distinct_ids_in_html() {
+    let html = parse_markdown_ast("## Example\n\nOne\n\n## Example\n\nTwo\n").html_content;
+    assert!(html.contains("<h2 id=\"example\">Example</h2>"));
+    assert!(html.contains("<h2 id=\"example-1\">Example</h2>"));
+}
+
+#[test]
+fn custom_ids_are_kept_and_reserved() {
+    let result = parse_markdown_ast("## Setup\n\n## Install *now* {#setup}\n");
+    assert_eq!(result.headings[0].id, "setup-1");
+    assert_eq!(result.headings[1].id, "setup");
+    assert_eq!(result.headings[1].text, "Install now");
+    assert!(result
+        .html_content
+        .contains("<h2 id=\"setup\">Install <em>now</em></h2>"));
+}
+
+#[test]
+fn slug_strategies() {
+    assert_eq!(
+        slugify("Überblick & Ziele", SlugStrategy::Unicode),
+        "überblick-ziele"
+    );
+    assert_eq!(slugify("日本語", SlugStrategy::Unicode), "日本語");
+    assert_eq!(
+        slugify("Überblick & Ziele", SlugStrategy::Transliterate),
+        "uberblick-ziele"
+    );
+    assert_eq!(slugify("日本語", SlugStrategy::Transliterate), "ri-ben-yu");
+
+    // Headings without letters or digits still get an ID
+    let result = parse_markdown_ast("## 🚀\n\n## 🎉\n");
+    assert_eq!(result.headings[0].id, "section");
+    assert_eq!(result.headings[1].id, "section-1");
+}
+
+#[test]
+fn anchors_are_configured_in_site_toml() {
+    let config: SiteConfig =
+        toml::from_str("[headings]\nslug = \"transliterate\"\nanchors = true\n").unwrap();
+    assert_eq!(config.headings.slug, SlugStrategy::Transliterate);
+
+    let options = MarkdownOptions::new(&config, "en");
+    let html = parse_markdown_ast_with_options("## Café\n", &options).html_content;
+    assert!(html.contains(
+        "<h2 id=\"cafe\">Café <a class=\"heading-anchor\" href=\"#cafe\" aria-hidden=\"true\">#</a></h2>"
+    ));
+
+    let html = parse_markdown_ast_with_options("## Quoted {#a\"b}\n", &options).html_content;
+    assert!(html.contains("href=\"#a%22b\""), "{html}");
+
+    let plain = parse_markdown_ast_with_options(
+        "## Café\n",
+        &MarkdownOptions {
+            headings: HeadingConfig::default(),
+            ..Default::default()
+        },
+    );
+    assert!(!plain.html_content.contains("heading-anchor"));
+    assert_eq!(plain.headings[0].id, "café");
+}
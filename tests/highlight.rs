@@ -0,0 +1,77 @@
+use krik::generator::ast_parser::{parse_markdown_ast_with_options, MarkdownOptions};
+use krik::generator::highlight::highlight_css;
+use krik::site::{HighlightConfig, HighlightFallback, SiteConfig};
+
+fn render(markdown: &str, highlight: HighlightConfig) -> String {
+    let options = MarkdownOptions {
+        highlight,
+        ..Default::default()
+    };
+    parse_markdown_ast_with_options(markdown, &options).html_content
+}
+
+#[test]
+fn fenced_code_is_highlighted_with_classes() {
+    let html = render(
+        "```rust\nfn main() { let x = 1; }\n```\n",
+        HighlightConfig {
+            enabled: true,
+            ..Default::default()
+        },
+    );
+    assert!(html.contains("<pre class=\"highlight hl-code\" data-lang=\"rust\">"));
+    assert!(html.contains("<span class=\"hl-"));
+    assert!(!html.contains("language-rust"));
+}
+
+#[test]
+fn unknown_or_disabled_languages_use_fallback() {
+    let md = "```nosuchlang\n<a> & b\n```\n\n```python\nprint(1)\n```\n";
+
+    let client = render(
+        md,
+        HighlightConfig {
+            enabled: true,
+            languages: vec!["rust".to_string()],
+            ..Default::default()
+        },
+    );
+    assert!(client.contains("class=\"language-nosuchlang\""));
+    assert!(client.contains("class=\"language-python\""));
+
+    let plain = render(
+        md,
+        HighlightConfig {
+            enabled: true,
+            fallback: HighlightFallback::Plain,
+            ..Default::default()
+        },
+    );
+    assert!(plain.contains(
+        "<pre class=\"highlight\" data-lang=\"nosuchlang\"><code>&lt;a&gt; &amp; b\n</code></pre>"
+    ));
+    assert!(plain.contains("data-lang=\"python\""));
+
+    let disabled = render(md, HighlightConfig::default());
+    assert!(!disabled.contains("hl-"));
+}
+
+#[test]
+fn highlight_settings_are_read_from_site_toml() {
+    let config: SiteConfig = toml::from_str("").unwrap();
+    assert!(!config.highlight.enabled);
+
+    let config: SiteConfig = toml::from_str(
+        "[highlight]\nenabled = true\ntheme = \"Solarized (light)\"\nlanguages = [\"rust\"]\nfallback = \"plain\"\n",
+    )
+    .unwrap();
+    assert!(config.highlight.enabled);
+    assert_eq!(config.highlight.dark_theme, "base16-ocean.dark");
+    assert_eq!(config.highlight.fallback, HighlightFallback::Plain);
+
+    let css = highlight_css(&config.highlight);
+    assert!(css.contains(".hl-code {"));
+    assert!(css.contains("@media (prefers-color-scheme: dark)"));
+    assert!(css.contains(":root:not([data-theme=\"light\"]) .hl-code {"));
+    assert!(css.contains("[data-theme=\"dark\"] .hl-code {"));
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use std::fs;
+
+use krik::generator::minify::minify_html;
+use krik::generator::SiteGenerator;
+use tempfile::TempDir;
+
+use common::write_file;
+
+fn site() -> TempDir {
+    let tmp = TempDir::new().unwrap();
+    let tmp_dir = tmp.path();
+    let theme_dir = tmp_dir.join("theme");
+    write_file(
+        &tmp_dir.join("content/site.toml"),
+        "title = \"Tidy\"\n\n[html]\nminify = true\n",
+    );
+    write_file(
+        &tmp_dir.join("content/posts/hello.md"),
+        "---\ntitle: Hello\n---\n\nSome *emphasis* here.\n\n```\nfn main() {\n    run();\n}\n```\n",
+    );
+    write_file(
+        &theme_dir.join("templates/post.html"),
+        "<!DOCTYPE html>\n<html>\n  <!-- layout -->\n  <body>\n    <main>\n      {{ content | safe }}\n    </main>\n  </body>\n</html>\n",
+    );
+    write_file(
+        &theme_dir.join("templates/index.html"),
+        "<ul>\n  {% for post in posts %}\n  <li>{{ post.title }}</li>\n  {% endfor %}\n</ul>\n",
+    );
+    tmp
+}
+
+#[test]
+fn whitespace_collapses_outside_preformatted_elements() {
+    let html = "<div>\n  <p>Hello   <em>there</em>\n  world</p>\n  <!-- note -->\n\
+                <pre>  keep\n    this</pre>\n<p>Use <code>a  b</code> now</p>\n\
+                <textarea>\n  typed\n</textarea>\n\
+                <script>\n  if (a < b) { go(); }\n</script>\n</div>\n";
+    assert_eq!(
+        minify_html(html),
+        "<div><p>Hello <em>there</em> world</p><pre>  keep\n    this</pre>\
+         <p>Use <code>a  b</code> now</p><textarea>\n  typed\n</textarea> \
+         <script>\n  if (a < b) { go(); }\n</script></div>"
+    );
+}
+
+#[test]
+fn raw_elements_end_at_their_own_closing_tag() {
+    let html = "<CODE>a  </codex>  b</Code >  <p>\n  c</p><STYLE>p  {}</style>";
+    assert_eq!(
+        minify_html(html),
+        "<CODE>a  </codex>  b</Code ><p>c</p><STYLE>p  {}</style>"
+    );
+}
+
+#[test]
+fn attributes_entities_and_conditional_comments_are_kept() {
+    let html = "<a title=\"a > b\"  href='/x'>link</a>\u{a0}&nbsp; <!--[if IE]>ie<![endif]-->";
+    assert_eq!(
+        minify_html(html),
+        "<a title=\"a > b\"  href='/x'>link</a>\u{a0}&nbsp; <!--[if IE]>ie<![endif]-->"
+    );
+    assert_eq!(minify_html("x < y <3"), "x < y <3");
+}
+
+#[test]
+fn pages_and_index_are_minified_except_in_development() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = site();
+    let tmp_dir = tmp.path();
+    let output_dir = tmp_dir.join("_site");
+
+    let generator = SiteGenerator::new(
+        &tmp_dir.join("content"),
+        &output_dir,
+        Some(&tmp_dir.join("theme")),
+    )?;
+    generator.generate_site()?;
+
+    let page = fs::read_to_string(output_dir.join("posts/hello.html"))?;
+    assert!(
+        page.starts_with("<!DOCTYPE html><html><body><main><p>Some <em>emphasis</em> here.</p>")
+    );
+    assert!(page.contains("fn main() {\n    run();\n}"));
+    assert!(!page.contains("<!-- layout -->"));
+    assert_eq!(
+        fs::read_to_string(output_dir.join("index.html"))?,
+        "<ul><li>Hello</li></ul>"
+    );
+
+    let dev_output = tmp_dir.join("_dev");
+    let mut generator = SiteGenerator::new(
+        &tmp_dir.join("content"),
+        &dev_output,
+        Some(&tmp_dir.join("theme")),
+    )?;
+    generator.set_dev(true)?;
+    generator.generate_site()?;
+    let page = fs::read_to_string(dev_output.join("posts/hello.html"))?;
+    assert!(page.contains("<!-- layout -->"));
+
+    Ok(())
+}
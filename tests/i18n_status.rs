@@ -0,0 +1,84 @@
+use krik::i18n::status::{format_status_table, translation_status};
+use std::fs;
+use tempfile::TempDir;
+
+fn write(dir: &std::path::Path, rel: &str, contents: &str) {
+    let path = dir.join(rel);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+}
+
+#[test]
+fn reports_missing_outdated_and_mismatched_translations() {
+    let temp = TempDir::new().unwrap();
+    let content = temp.path();
+
+    write(
+        content,
+        "posts/hello.md",
+        "---\ntitle: Hello\ndate: 2024-01-01T00:00:00Z\ntags: [a, b]\nupdated: 2024-03-01\n---\nHi\n",
+    );
+    write(
+        content,
+        "posts/hello.it.md",
+        "---\ntitle: Ciao\ndate: 2024-01-02T00:00:00Z\ntags: [b, a]\npdf: true\nupdated: 2024-02-01\n---\nCiao\n",
+    );
+    write(content, "pages/about.md", "---\ntitle: About\n---\nAbout\n");
+    write(
+        content,
+        "pages/draft.it.md",
+        "---\ntitle: Bozza\ndraft: true\n---\nBozza\n",
+    );
+
+    let report = translation_status(content).unwrap();
+
+    assert_eq!(report.documents, 2);
+    let it = report
+        .languages
+        .iter()
+        .find(|l| l.language == "it")
+        .unwrap();
+    assert_eq!(it.translated, 1);
+    assert_eq!(it.missing, 1);
+    assert!((it.coverage - 50.0).abs() < f64::EPSILON);
+
+    assert_eq!(report.missing.len(), 1);
+    assert_eq!(report.missing[0].source, "pages/about.md");
+    assert_eq!(report.missing[0].missing_languages, vec!["it".to_string()]);
+
+    assert_eq!(report.outdated.len(), 1);
+    assert_eq!(report.outdated[0].translation, "posts/hello.it.md");
+    assert_eq!(report.outdated[0].timestamp_source, "updated");
+
+    let fields: Vec<&str> = report.mismatches.iter().map(|m| m.field.as_str()).collect();
+    assert_eq!(fields, vec!["date", "pdf"], "tag order should not matter");
+
+    let table = format_status_table(&report);
+    assert!(table.contains("it (Italiano)"));
+    assert!(table.contains("50.0%"));
+}
+
+#[test]
+fn timestamps_of_different_kinds_are_not_compared() {
+    let temp = TempDir::new().unwrap();
+    let content = temp.path();
+
+    write(
+        content,
+        "posts/bye.md",
+        "---\ntitle: Bye\nupdated: 2999-01-01\n---\nBye\n",
+    );
+    write(content, "posts/bye.it.md", "---\ntitle: Ciao\n---\nCiao\n");
+
+    let report = translation_status(content).unwrap();
+    assert!(report.outdated.is_empty());
+    assert_eq!(report.unknown_freshness.len(), 1);
+    assert_eq!(report.unknown_freshness[0].translation, "posts/bye.it.md");
+    assert_eq!(report.unknown_freshness[0].source_timestamp, "updated");
+    assert_eq!(report.unknown_freshness[0].translation_timestamp, "mtime");
+
+    let table = format_status_table(&report);
+    assert!(
+        table.contains("posts/bye.it.md cannot be compared with posts/bye.md (mtime vs updated)")
+    );
+}
@@ -0,0 +1,156 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use krik::generator::SiteGenerator;
+use krik::lint::lint_content;
+use krik::site::IgnoreRules;
+use tempfile::TempDir;
+
+use common::write_file;
+
+fn site() -> TempDir {
+    let tmp = TempDir::new().unwrap();
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+    let theme_dir = tmp_dir.join("theme");
+    write_file(
+        &content_dir.join("site.toml"),
+        "title = \"Quiet\"\nexclude = [\"*.psd\", \"/notes.md\"]\n\n[assets]\nminify = true\n",
+    );
+    write_file(
+        &content_dir.join(".krikignore"),
+        "# work in progress\ndrafts/\n",
+    );
+    write_file(
+        &content_dir.join("posts/hello.md"),
+        "---\ntitle: Hello\n---\n\nHi.\n",
+    );
+    write_file(
+        &content_dir.join("drafts/idea.md"),
+        "no front matter [x](missing.md)",
+    );
+    write_file(&content_dir.join("drafts/sketch.png"), "png");
+    write_file(&content_dir.join("notes.md"), "---\ntitle: Notes\n---\n");
+    write_file(
+        &content_dir.join("posts/notes.md"),
+        "---\ntitle: Kept\n---\n",
+    );
+    write_file(&content_dir.join("images/cover.psd"), "psd");
+    write_file(&tmp_dir.join("static/favicon.ico"), "icon");
+    write_file(&tmp_dir.join("static/.nojekyll"), "");
+    write_file(&tmp_dir.join("static/art/logo.psd"), "psd");
+    write_file(
+        &tmp_dir.join("static/vendor/lib.css"),
+        "/* kept */\nbody {\n    margin: 0;\n}\n",
+    );
+    write_file(&theme_dir.join("templates/post.html"), "{{ title }}");
+    write_file(&theme_dir.join("templates/page.html"), "{{ title }}");
+    write_file(&theme_dir.join("templates/index.html"), "index");
+    tmp
+}
+
+#[test]
+fn patterns_match_relative_and_absolute_paths() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = site();
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+    let rules = IgnoreRules::load(&content_dir, &["*.psd".to_string()])?;
+
+    assert!(rules.is_ignored(Path::new("drafts/idea.md")));
+    assert!(rules.is_ignored(&content_dir.join("drafts")));
+    assert!(rules.is_ignored(&fs::canonicalize(content_dir.join("drafts/sketch.png"))?));
+    assert!(rules.is_ignored(Path::new("images/cover.psd")));
+    assert!(!rules.is_ignored(Path::new("posts/hello.md")));
+    assert!(!rules.is_ignored(&tmp_dir.join("theme/templates/post.html")));
+    assert!(IgnoreRules::load(&content_dir, &["a{b".to_string()]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn ignored_files_are_left_out_and_static_files_are_copied() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tmp = site();
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+    let output_dir = tmp_dir.join("_site");
+
+    let generator = SiteGenerator::new(&content_dir, &output_dir, Some(&tmp_dir.join("theme")))?;
+    generator.generate_site()?;
+
+    assert!(output_dir.join("posts/hello.html").exists());
+    assert!(output_dir.join("posts/notes.html").exists());
+    assert!(!output_dir.join("notes.html").exists());
+    assert!(!output_dir.join("drafts").exists());
+    assert!(!output_dir.join("images/cover.psd").exists());
+    assert!(!output_dir.join(".krikignore").exists());
+
+    // Static files land in the output root, unprocessed
+    assert_eq!(fs::read_to_string(output_dir.join("favicon.ico"))?, "icon");
+    assert!(output_dir.join(".nojekyll").exists());
+    assert!(!output_dir.join("art/logo.psd").exists());
+    assert!(fs::read_to_string(output_dir.join("vendor/lib.css"))?.starts_with("/* kept */"));
+
+    let report = lint_content(&content_dir)?;
+    assert!(report.errors.iter().all(|e| !e.contains("drafts")));
+    assert!(report.warnings.iter().all(|w| !w.contains("drafts")));
+
+    Ok(())
+}
+
+#[test]
+fn incremental_builds_skip_ignored_paths_and_copy_static_files(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = site();
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+    let output_dir = tmp_dir.join("_site");
+
+    let mut generator =
+        SiteGenerator::new(&content_dir, &output_dir, Some(&tmp_dir.join("theme")))?;
+    generator.scan_files()?;
+    generator.generate_site()?;
+
+    write_file(&content_dir.join("drafts/new.md"), "---\ntitle: New\n---\n");
+    generator.generate_incremental_for_path(
+        fs::canonicalize(content_dir.join("drafts/new.md"))?,
+        false,
+    )?;
+    assert!(!output_dir.join("drafts/new.html").exists());
+
+    write_file(&tmp_dir.join("static/robots-extra.txt"), "extra");
+    let added = fs::canonicalize(tmp_dir.join("static/robots-extra.txt"))?;
+    generator.generate_incremental_for_path(&added, false)?;
+    assert_eq!(
+        fs::read_to_string(output_dir.join("robots-extra.txt"))?,
+        "extra"
+    );
+
+    fs::remove_file(&added)?;
+    generator.generate_incremental_for_path(&added, true)?;
+    assert!(!output_dir.join("robots-extra.txt").exists());
+
+    // Paths reported through a symlink to the site resolve to `static/` too
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(tmp_dir, tmp_dir.join("link"))?;
+        let linked = tmp_dir.join("link/static/.htaccess");
+        write_file(&linked, "deny");
+        generator.generate_incremental_for_path(&linked, false)?;
+        assert_eq!(fs::read_to_string(output_dir.join(".htaccess"))?, "deny");
+
+        fs::remove_file(&linked)?;
+        generator.generate_incremental_for_path(&linked, true)?;
+        assert!(!output_dir.join(".htaccess").exists());
+    }
+
+    // Editing .krikignore reloads the patterns
+    write_file(&content_dir.join(".krikignore"), "");
+    generator
+        .generate_incremental_for_path(fs::canonicalize(content_dir.join(".krikignore"))?, false)?;
+    assert!(output_dir.join("drafts/new.html").exists());
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use std::fs;
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use image::metadata::Orientation;
+use image::{ImageDecoder, ImageFormat, ImageReader, RgbImage};
+use krik::generator::image_metadata::{has_gps_data, strip_metadata};
+use krik::generator::SiteGenerator;
+use krik::lint::lint_content;
+use tempfile::TempDir;
+
+/// Little-endian TIFF block with orientation 6 (rotate 90°) and a GPS position
+fn exif_with_gps() -> Vec<u8> {
+    let mut tiff = b"II\x2a\x00\x08\x00\x00\x00".to_vec();
+    tiff.extend_from_slice(&2u16.to_le_bytes());
+    // Orientation: SHORT 6
+    tiff.extend_from_slice(&[0x12, 0x01, 3, 0, 1, 0, 0, 0, 6, 0, 0, 0]);
+    // GPS IFD pointer: LONG 38
+    tiff.extend_from_slice(&[0x25, 0x88, 4, 0, 1, 0, 0, 0, 38, 0, 0, 0]);
+    tiff.extend_from_slice(&0u32.to_le_bytes());
+    // GPS IFD: latitude as three RATIONALs at offset 56
+    tiff.extend_from_slice(&1u16.to_le_bytes());
+    tiff.extend_from_slice(&[0x02, 0x00, 5, 0, 3, 0, 0, 0, 56, 0, 0, 0]);
+    tiff.extend_from_slice(&0u32.to_le_bytes());
+    for value in [45u32, 1, 30, 1, 0, 1] {
+        tiff.extend_from_slice(&value.to_le_bytes());
+    }
+    tiff
+}
+
+fn encoded(format: ImageFormat) -> Vec<u8> {
+    let photo = RgbImage::from_fn(8, 4, |x, y| image::Rgb([x as u8 * 30, y as u8 * 60, 90]));
+    let mut bytes = Vec::new();
+    photo
+        .write_to(&mut Cursor::new(&mut bytes), format)
+        .unwrap();
+    bytes
+}
+
+/// A JPEG carrying EXIF (with GPS) and XMP segments right after SOI
+fn jpeg_with_metadata() -> Vec<u8> {
+    let jpeg = encoded(ImageFormat::Jpeg);
+    let mut segments = Vec::new();
+    for payload in [
+        [b"Exif\0\0".as_slice(), &exif_with_gps()].concat(),
+        b"http://ns.adobe.com/xap/1.0/\0<x:xmpmeta exif:GPSLatitude=\"45,30N\"/>".to_vec(),
+    ] {
+        segments.extend_from_slice(&[0xFF, 0xE1]);
+        segments.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+        segments.extend_from_slice(&payload);
+    }
+    [&jpeg[..2], &segments, &jpeg[2..]].concat()
+}
+
+fn orientation(bytes: &[u8]) -> Orientation {
+    ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .unwrap()
+        .into_decoder()
+        .unwrap()
+        .orientation()
+        .unwrap()
+}
+
+#[test]
+fn jpeg_metadata_is_stripped_keeping_orientation() {
+    let original = jpeg_with_metadata();
+    assert!(has_gps_data(&original));
+    assert_eq!(orientation(&original), Orientation::Rotate90);
+
+    let stripped = strip_metadata(&original).unwrap();
+    assert!(!has_gps_data(&stripped));
+    assert!(!String::from_utf8_lossy(&stripped).contains("xmpmeta"));
+    assert_eq!(orientation(&stripped), Orientation::Rotate90);
+    assert!(stripped.len() < original.len());
+    assert_eq!(
+        image::load_from_memory(&stripped).unwrap().to_rgb8(),
+        image::load_from_memory(&original).unwrap().to_rgb8()
+    );
+}
+
+#[test]
+fn png_exif_and_text_chunks_are_stripped() {
+    let png = encoded(ImageFormat::Png);
+    let chunk = |kind: &[u8], data: &[u8]| {
+        let mut typed = kind.to_vec();
+        typed.extend_from_slice(data);
+        let mut crc = 0xFFFF_FFFFu32;
+        for &byte in &typed {
+            crc ^= u32::from(byte);
+            for _ in 0..8 {
+                crc = if crc & 1 == 1 {
+                    (crc >> 1) ^ 0xEDB8_8320
+                } else {
+                    crc >> 1
+                };
+            }
+        }
+        [
+            &(data.len() as u32).to_be_bytes()[..],
+            &typed,
+            &(!crc).to_be_bytes(),
+        ]
+        .concat()
+    };
+    // Insert after the IHDR chunk (8-byte signature + 25-byte chunk)
+    let original = [
+        &png[..33],
+        &chunk(b"eXIf", &exif_with_gps()),
+        &chunk(b"tEXt", b"Comment\0taken at home"),
+        &png[33..],
+    ]
+    .concat();
+    assert!(has_gps_data(&original));
+
+    let stripped = strip_metadata(&original).unwrap();
+    assert!(!has_gps_data(&stripped));
+    assert!(!String::from_utf8_lossy(&stripped).contains("taken at home"));
+    assert_eq!(orientation(&stripped), Orientation::Rotate90);
+    assert_eq!(
+        image::load_from_memory(&stripped).unwrap().to_rgb8(),
+        image::load_from_memory(&png).unwrap().to_rgb8()
+    );
+}
+
+#[test]
+fn published_images_are_stripped_and_lint_warns() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = TempDir::new()?;
+    let content_dir = tmp_dir.path().join("content");
+    let output_dir = tmp_dir.path().join("_site");
+    fs::create_dir_all(content_dir.join("images"))?;
+    fs::write(content_dir.join("images/beach.jpg"), jpeg_with_metadata())?;
+
+    let report = lint_content(&content_dir)?;
+    assert!(report.warnings.iter().any(|w| w.contains("beach.jpg")
+        && w.contains("image contains GPS location data; set `strip_metadata = true`")));
+
+    // Copied as-is by default
+    let generator = SiteGenerator::new(&content_dir, &output_dir, None::<&PathBuf>)?;
+    generator.generate_site()?;
+    assert!(has_gps_data(&fs::read(
+        output_dir.join("images/beach.jpg")
+    )?));
+
+    fs::write(
+        content_dir.join("site.toml"),
+        "title = \"Beach\"\n\n[images]\nstrip_metadata = true\n",
+    )?;
+    let generator = SiteGenerator::new(&content_dir, &output_dir, None::<&PathBuf>)?;
+    generator.generate_site()?;
+    let published = fs::read(output_dir.join("images/beach.jpg"))?;
+    assert!(!has_gps_data(&published));
+    assert_eq!(orientation(&published), Orientation::Rotate90);
+
+    Ok(())
+}
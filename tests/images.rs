@@ -0,0 +1,156 @@
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use image::{ImageBuffer, Rgb};
+use krik::generator::images::{variant_path, variant_widths};
+use krik::generator::SiteGenerator;
+use krik::site::ImageConfig;
+use tempfile::TempDir;
+
+use common::write_file;
+
+fn write_photo(path: &Path, width: u32, height: u32) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let photo = ImageBuffer::from_fn(width, height, |x, y| {
+        Rgb([(x % 256) as u8, (y % 256) as u8, 128])
+    });
+    photo.save(path).unwrap();
+}
+
+fn site(images: &str) -> TempDir {
+    let tmp = TempDir::new().unwrap();
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+    write_file(
+        &content_dir.join("site.toml"),
+        &format!("title = \"Photos\"\n\n[images]\n{images}"),
+    );
+    write_photo(&content_dir.join("posts/trip/cat.jpg"), 300, 150);
+    write_photo(&content_dir.join("images/tiny.png"), 50, 40);
+    write_file(
+        &content_dir.join("posts/trip.md"),
+        "---\ntitle: Trip\n---\n\n![Cat & dog](trip/cat.jpg \"Cat\")\n\n![Tiny](/images/tiny.png)\n\n\
+         ![Remote](https://example.com/cat.jpg)\n",
+    );
+    tmp
+}
+
+#[test]
+fn variant_names_and_widths() {
+    let config = ImageConfig {
+        widths: vec![960, 480, 1600, 480],
+        ..Default::default()
+    };
+    assert_eq!(variant_widths(1000, &config), vec![480, 960]);
+    assert_eq!(variant_widths(400, &config), Vec::<u32>::new());
+    assert_eq!(
+        variant_path("../img/cat.jpg", 480, None),
+        "../img/cat-480w.jpg"
+    );
+    assert_eq!(
+        variant_path("a.b/cat.png", 480, Some("webp")),
+        "a.b/cat-480w.webp"
+    );
+}
+
+#[test]
+fn images_get_srcset_dimensions_and_resized_variants() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = site("enabled = true\nwidths = [100, 200, 400]\n");
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+    let output_dir = tmp_dir.join("_site");
+
+    let generator = SiteGenerator::new(&content_dir, &output_dir, None::<&PathBuf>)?;
+    generator.generate_site()?;
+
+    let html = fs::read_to_string(output_dir.join("posts/trip.html"))?;
+    assert!(html.contains(
+        "<img src=\"trip/cat.jpg\" alt=\"Cat &amp; dog\" title=\"Cat\" width=\"300\" height=\"150\" \
+         srcset=\"trip/cat-100w.jpg 100w, trip/cat-200w.jpg 200w, trip/cat.jpg 300w\" sizes=\"100vw\" \
+         loading=\"lazy\" decoding=\"async\" />"
+    ));
+    assert!(html.contains(
+        "<img src=\"/images/tiny.png\" alt=\"Tiny\" width=\"50\" height=\"40\" loading=\"lazy\" decoding=\"async\" />"
+    ));
+    assert!(html.contains("<img src=\"https://example.com/cat.jpg\" alt=\"Remote\" />"));
+
+    let small = image::open(output_dir.join("posts/trip/cat-100w.jpg"))?;
+    assert_eq!((small.width(), small.height()), (100, 50));
+    assert!(output_dir.join("posts/trip/cat-200w.jpg").exists());
+    assert!(output_dir.join("posts/trip/cat.jpg").exists());
+    assert!(!output_dir.join("posts/trip/cat-400w.jpg").exists());
+
+    Ok(())
+}
+
+#[test]
+fn webp_variants_are_offered_through_picture() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = site("enabled = true\nwidths = [100]\nwebp = true\nlazy = false\n");
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+    let output_dir = tmp_dir.join("_site");
+    write_photo(&content_dir.join("posts/trip/map.png"), 300, 150);
+    write_file(
+        &content_dir.join("posts/map.md"),
+        "---\ntitle: Map\n---\n\n![Map](trip/map.png)\n",
+    );
+
+    let generator = SiteGenerator::new(&content_dir, &output_dir, None::<&PathBuf>)?;
+    generator.generate_site()?;
+
+    let html = fs::read_to_string(output_dir.join("posts/map.html"))?;
+    assert!(html.contains(
+        "<picture><source type=\"image/webp\" srcset=\"trip/map-100w.webp 100w, trip/map-300w.webp 300w\" sizes=\"100vw\">\
+         <img src=\"trip/map.png\""
+    ));
+    assert!(!html.contains("loading=\"lazy\""));
+    let webp = image::open(output_dir.join("posts/trip/map-300w.webp"))?;
+    assert_eq!(webp.width(), 300);
+
+    // Lossless WebP is only offered where it is smaller than the fallback
+    let size = |path: &str| fs::metadata(output_dir.join(path)).map(|m| m.len());
+    assert!(size("posts/trip/map-100w.webp")? <= size("posts/trip/map-100w.png")?);
+    assert!(size("posts/trip/map-300w.webp")? <= size("posts/trip/map.png")?);
+    let html = fs::read_to_string(output_dir.join("posts/trip.html"))?;
+    assert!(html.contains("<img src=\"trip/cat.jpg\""));
+    assert!(!html.contains("cat-100w.webp"));
+    assert!(!output_dir.join("posts/trip/cat-100w.webp").exists());
+    assert!(!output_dir.join("posts/trip/cat-300w.webp").exists());
+
+    Ok(())
+}
+
+#[test]
+fn resized_images_are_cached_by_content_hash() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = site("enabled = true\nwidths = [100]\n");
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+    let output_dir = tmp_dir.join("_site");
+    let cache_dir = tmp_dir.join(".krik-cache/images");
+
+    let generator = SiteGenerator::new(&content_dir, &output_dir, None::<&PathBuf>)?;
+    generator.generate_site()?;
+    let entries: Vec<_> = fs::read_dir(&cache_dir)?.collect::<Result<_, _>>()?;
+    assert_eq!(entries.len(), 1);
+    let cached = entries[0].path();
+    assert!(cached.to_string_lossy().ends_with("-100w.jpg"));
+
+    // A cached variant is reused instead of resizing the original again
+    fs::write(&cached, b"cached")?;
+    fs::remove_dir_all(&output_dir)?;
+    let generator = SiteGenerator::new(&content_dir, &output_dir, None::<&PathBuf>)?;
+    generator.generate_site()?;
+    assert_eq!(
+        fs::read(output_dir.join("posts/trip/cat-100w.jpg"))?,
+        b"cached"
+    );
+
+    // Changing the image changes its hash
+    write_photo(&content_dir.join("posts/trip/cat.jpg"), 320, 160);
+    generator.generate_site()?;
+    assert_eq!(fs::read_dir(&cache_dir)?.count(), 2);
+
+    Ok(())
+}
@@ -1,26 +1,19 @@
-use std::fs::{self, File};
-use std::io::Write;
+mod common;
+
+use std::fs;
 use std::path::PathBuf;
 
 use krik::generator::SiteGenerator;
+use tempfile::TempDir;
 
-fn write_file(path: &PathBuf, contents: &str) {
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).unwrap();
-    }
-    let mut f = File::create(path).unwrap();
-    f.write_all(contents.as_bytes()).unwrap();
-}
+use common::write_file;
 
 #[test]
 fn index_selection_prefers_default_language_when_both_exist(
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Prepare temporary workspace
-    let mut tmp_dir: PathBuf = std::env::temp_dir();
-    tmp_dir.push(format!("krik_test_index_selection_{}", std::process::id()));
-    // Start with a clean directory if it exists from a previous run
-    let _ = fs::remove_dir_all(&tmp_dir);
-    fs::create_dir_all(&tmp_dir)?;
+    let tmp = TempDir::new()?;
+    let tmp_dir = tmp.path();
 
     let content_dir = tmp_dir.join("content");
     let output_dir = tmp_dir.join("_site");
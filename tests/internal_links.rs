@@ -0,0 +1,119 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+
+use krik::generator::SiteGenerator;
+use tempfile::TempDir;
+
+use common::write_file;
+
+fn setup() -> TempDir {
+    let tmp = TempDir::new().unwrap();
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+    write_file(&content_dir.join("site.toml"), "title = \"Test\"\n");
+    write_file(
+        &content_dir.join("posts/guide.md"),
+        "---\ntitle: Guide\n---\n\n## Setup\n\nInstall it.\n",
+    );
+    write_file(
+        &content_dir.join("posts/guide.it.md"),
+        "---\ntitle: Guida\n---\n\n## Setup\n\nInstallalo.\n",
+    );
+    tmp
+}
+
+#[test]
+fn markdown_links_are_rewritten_to_output_urls() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = setup();
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+    let output_dir = tmp_dir.join("_site");
+    write_file(
+        &content_dir.join("pages/about.md"),
+        "---\ntitle: About\n---\n\nSee the [guide](../posts/guide.md#setup) and [home](/posts/guide.md).\n",
+    );
+    write_file(
+        &content_dir.join("pages/about.it.md"),
+        "---\ntitle: Chi siamo\n---\n\nVedi la [guida](../posts/guide.md#setup).\n",
+    );
+
+    let generator = SiteGenerator::new(&content_dir, &output_dir, None::<&PathBuf>)?;
+    generator.generate_site()?;
+
+    let en_html = fs::read_to_string(output_dir.join("pages/about.html"))?;
+    assert!(en_html.contains("href=\"../posts/guide.html#setup\""));
+    assert!(en_html.contains("href=\"../posts/guide.html\""));
+    assert!(!en_html.contains("guide.md"));
+
+    let it_html = fs::read_to_string(output_dir.join("pages/about.it.html"))?;
+    assert!(
+        it_html.contains("href=\"../posts/guide.it.html#setup\""),
+        "the reader's language variant is preferred"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn broken_links_warn_by_default_and_fail_in_strict_mode() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tmp = setup();
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+    let output_dir = tmp_dir.join("_site");
+    write_file(
+        &content_dir.join("pages/about.md"),
+        "---\ntitle: About\n---\n\n[Missing](missing.md) and [anchor](../posts/guide.md#nope).\n",
+    );
+
+    let generator = SiteGenerator::new(&content_dir, &output_dir, None::<&PathBuf>)?;
+    generator.generate_site()?;
+    let html = fs::read_to_string(output_dir.join("pages/about.html"))?;
+    assert!(
+        html.contains("href=\"missing.md\""),
+        "unresolved links are left as written"
+    );
+
+    let mut strict = SiteGenerator::new(&content_dir, &output_dir, None::<&PathBuf>)?;
+    strict.strict = true;
+    let message = strict.generate_site().unwrap_err().to_string();
+    assert!(
+        message.contains("Found 2 broken internal link(s)"),
+        "{message}"
+    );
+    assert!(
+        message.contains("pages/about.md: 'missing.md' links to a document that does not exist")
+    );
+    assert!(message.contains(
+        "'../posts/guide.md#nope' links to a heading that does not exist in posts/guide.md"
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn encoded_paths_and_anchors_resolve_in_strict_mode() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = setup();
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+    let output_dir = tmp_dir.join("_site");
+    write_file(
+        &content_dir.join("posts/über uns.md"),
+        "---\ntitle: Über uns\n---\n\n## Überblick\n\nHallo.\n",
+    );
+    write_file(
+        &content_dir.join("pages/about.md"),
+        "---\ntitle: About\n---\n\nSee [x](../posts/über%20uns.md#überblick).\n",
+    );
+
+    let mut generator = SiteGenerator::new(&content_dir, &output_dir, None::<&PathBuf>)?;
+    generator.strict = true;
+    generator.generate_site()?;
+
+    let html = fs::read_to_string(output_dir.join("pages/about.html"))?;
+    assert!(!html.contains(".md"), "{html}");
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use krik::error::{KrikError, MarkdownErrorKind};
+use krik::generator::ast_parser::{parse_markdown_ast, MarkdownOptions};
+use krik::generator::markdown::markdown_to_html_with_options;
+use krik::generator::math::tex_to_mathml;
+use std::path::PathBuf;
+use std::process::Command;
+
+#[test]
+fn inline_and_display_math_render_as_mathml() {
+    let result = parse_markdown_ast("Euler: $e^{i\\pi} + 1 = 0$\n\n$$\n\\frac{a}{b}\n$$\n");
+    let html = result.html_content;
+    assert!(html.contains("<math"));
+    assert!(html.contains("<mfrac>"));
+    assert!(html.contains("display=\"block\""));
+    assert!(!html.contains("$"));
+    assert!(result.math_errors.is_empty());
+}
+
+#[test]
+fn code_keeps_dollar_signs() {
+    let html = parse_markdown_ast("`$x$`\n\n```\necho $HOME $PATH\n```\n").html_content;
+    assert!(html.contains("<code>$x$</code>"));
+    assert!(html.contains("echo $HOME $PATH"));
+    assert!(!html.contains("<math"));
+}
+
+#[test]
+fn invalid_tex_reports_file_and_line() {
+    let options = MarkdownOptions {
+        source_path: Some(PathBuf::from("content/post.md")),
+        line_offset: 4,
+        ..Default::default()
+    };
+    let markdown = "# Title\n\nBroken $\\frac{a}{\\nosuchcommand}$ here\n";
+
+    let rendered = parse_markdown_ast(markdown);
+    assert_eq!(rendered.math_errors.len(), 1);
+    assert_eq!(rendered.math_errors[0].line, 3);
+    assert!(rendered.html_content.contains("class=\"math-error\""));
+
+    let err = markdown_to_html_with_options(markdown, false, None, &options).unwrap_err();
+    let KrikError::Markdown(err) = err else {
+        panic!("expected a markdown error, got {err:?}");
+    };
+    assert!(matches!(err.kind, MarkdownErrorKind::InvalidMath(_)));
+    assert_eq!(err.file, PathBuf::from("content/post.md"));
+    assert_eq!(err.line, Some(7));
+}
+
+#[test]
+fn inputs_that_crash_the_converter_are_rejected_quietly() {
+    if std::env::var_os("KRIK_MATH_PANIC_CHILD").is_some() {
+        for tex in [
+            r"a \middle x b",
+            r"\left( a \middle\vert b \right)",
+            r"a \middle{x} b",
+            r"a \middle",
+        ] {
+            let err = tex_to_mathml(tex, false).unwrap_err();
+            assert!(err.contains("\\middle"), "{tex}: {err}");
+        }
+        for tex in [r"\left( a \middle| b \right)", r"a \middle{\langle} b"] {
+            assert!(tex_to_mathml(tex, false).is_ok(), "{tex}");
+        }
+        return;
+    }
+
+    // Run in a child process, since a panic message would only show up on stderr
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args([
+            "inputs_that_crash_the_converter_are_rejected_quietly",
+            "--exact",
+            "--nocapture",
+        ])
+        .env("KRIK_MATH_PANIC_CHILD", "1")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stderr}");
+    assert!(!stderr.contains("panicked"), "{stderr}");
+}
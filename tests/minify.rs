@@ -0,0 +1,177 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use krik::generator::minify::{bundle, is_minifiable, minify};
+use krik::generator::SiteGenerator;
+use tempfile::TempDir;
+
+use common::write_file;
+
+fn site(bundle_files: &str) -> TempDir {
+    let tmp = TempDir::new().unwrap();
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+    let theme_dir = tmp_dir.join("theme");
+    write_file(
+        &content_dir.join("site.toml"),
+        "title = \"Small\"\n\n[assets]\nminify = true\n",
+    );
+    write_file(
+        &content_dir.join("posts/hello.md"),
+        "---\ntitle: Hello\n---\n\nHi.\n",
+    );
+    write_file(
+        &content_dir.join("css/extra.css"),
+        "/* extra */\n.note {\n    color: red;\n}\n",
+    );
+    write_file(
+        &theme_dir.join("theme.toml"),
+        &format!(
+            "name = \"small\"\nversion = \"1.0.0\"\n\n[templates]\n\n\
+             [[bundles]]\noutput = \"css/bundle.css\"\nfiles = [{bundle_files}]\n"
+        ),
+    );
+    write_file(
+        &theme_dir.join("assets/css/reset.css"),
+        "/* reset */\nbody {\n    margin: 0;\n}\n",
+    );
+    write_file(
+        &theme_dir.join("assets/css/main.css"),
+        "main {\n    padding: 1rem;\n}\n",
+    );
+    write_file(
+        &theme_dir.join("assets/js/app.js"),
+        "// greet\nfunction greet(name) {\n    return \"Hi \" + name;\n}\n",
+    );
+    write_file(
+        &theme_dir.join("templates/post.html"),
+        "<link rel=\"stylesheet\" href=\"{{ assets_path }}/{{ asset_url(path=\"css/bundle.css\") }}\">",
+    );
+    write_file(&theme_dir.join("templates/index.html"), "index");
+    tmp
+}
+
+#[test]
+fn minifiable_files_and_bundle_source_maps() {
+    assert!(is_minifiable(Path::new("css/main.css")));
+    assert!(is_minifiable(Path::new("js/App.JS")));
+    assert!(!is_minifiable(Path::new("js/vendor.min.js")));
+    assert!(!is_minifiable(Path::new("images/cat.png")));
+
+    let members = vec![
+        ("reset.css".to_string(), "a {}\nb {}\n".to_string()),
+        ("../vendor/grid.css".to_string(), "c {}".to_string()),
+    ];
+    let plain = bundle("css/bundle.css", &members, false);
+    assert_eq!(plain.contents, "a {}\nb {}\nc {}\n");
+    assert!(plain.source_map.is_none());
+
+    let mapped = bundle("css/bundle.css", &members, true);
+    assert_eq!(
+        mapped.contents,
+        "a {}\nb {}\nc {}\n/*# sourceMappingURL=bundle.css.map */\n"
+    );
+    let map: serde_json::Value = serde_json::from_str(&mapped.source_map.unwrap()).unwrap();
+    assert_eq!(
+        map,
+        serde_json::json!({
+            "version": 3,
+            "file": "bundle.css",
+            "sources": ["reset.css", "../vendor/grid.css"],
+            "names": [],
+            "mappings": "AAAA;AACA;ACDA",
+        })
+    );
+}
+
+#[test]
+fn scripts_without_semicolons_keep_statement_breaks() {
+    let script = Path::new("js/app.js");
+    assert_eq!(
+        minify(script, "let a = 1\nlet b = 2\nconsole.log(a + b)\n"),
+        "let a=1\nlet b=2\nconsole.log(a+b)"
+    );
+    assert_eq!(
+        minify(
+            script,
+            "function f() {\n  return\n}\nlet i = 0\ni++\nf(\n  i,\n  2\n)\n"
+        ),
+        "function f(){return}\nlet i=0\ni++\nf(i,2)"
+    );
+}
+
+#[test]
+fn production_builds_minify_and_bundle() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = site("\"css/reset.css\", \"css/main.css\"");
+    let tmp_dir = tmp.path();
+    let output_dir = tmp_dir.join("_site");
+
+    let generator = SiteGenerator::new(
+        &tmp_dir.join("content"),
+        &output_dir,
+        Some(&tmp_dir.join("theme")),
+    )?;
+    generator.generate_site()?;
+
+    let bundle = fs::read_to_string(output_dir.join("assets/css/bundle.css"))?;
+    assert_eq!(bundle, "body{margin:0;}\nmain{padding:1rem;}\n");
+    assert!(!output_dir.join("assets/css/bundle.css.map").exists());
+    assert_eq!(
+        fs::read_to_string(output_dir.join("assets/js/app.js"))?,
+        "function greet(name){return\"Hi \"+name;}"
+    );
+    assert_eq!(
+        fs::read_to_string(output_dir.join("css/extra.css"))?,
+        ".note{color:red;}"
+    );
+
+    let bundled = generator.theme.assets.get("css/bundle.css").unwrap();
+    let html = fs::read_to_string(output_dir.join("posts/hello.html"))?;
+    assert!(html.contains(&format!("href=\"../assets/{}\"", bundled.path)));
+    assert_eq!(
+        fs::read_to_string(output_dir.join("assets").join(&bundled.path))?,
+        bundle
+    );
+
+    Ok(())
+}
+
+#[test]
+fn development_builds_write_source_maps() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = site("\"css/reset.css\", \"css/main.css\"");
+    let tmp_dir = tmp.path();
+    let output_dir = tmp_dir.join("_site");
+
+    let mut generator = SiteGenerator::new(
+        &tmp_dir.join("content"),
+        &output_dir,
+        Some(&tmp_dir.join("theme")),
+    )?;
+    generator.set_dev(true)?;
+    generator.generate_site()?;
+
+    let bundle = fs::read_to_string(output_dir.join("assets/css/bundle.css"))?;
+    assert!(bundle.starts_with("/* reset */\nbody {\n"));
+    assert!(bundle.ends_with("/*# sourceMappingURL=bundle.css.map */\n"));
+    let map: serde_json::Value = serde_json::from_str(&fs::read_to_string(
+        output_dir.join("assets/css/bundle.css.map"),
+    )?)?;
+    assert_eq!(map["sources"], serde_json::json!(["reset.css", "main.css"]));
+    assert!(fs::read_to_string(output_dir.join("css/extra.css"))?.contains("/* extra */"));
+
+    Ok(())
+}
+
+#[test]
+fn bundles_with_unknown_files_fail() {
+    let tmp = site("\"css/missing.css\"");
+    let tmp_dir = tmp.path();
+    let result = SiteGenerator::new(
+        &tmp_dir.join("content"),
+        &tmp_dir.join("_site"),
+        Some(&tmp_dir.join("theme")),
+    );
+    assert!(result.is_err());
+}
@@ -0,0 +1,176 @@
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use krik::generator::SiteGenerator;
+use krik::i18n::status::translation_status;
+use krik::site::SiteConfig;
+use tempfile::TempDir;
+
+use common::write_file;
+
+fn site() -> TempDir {
+    let tmp = TempDir::new().unwrap();
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+    let handbook_dir = tmp_dir.join("handbook");
+    write_file(
+        &content_dir.join("site.toml"),
+        "title = \"Docs\"\n\n[[mounts]]\nsource = \"../handbook\"\ntarget = \"/docs/\"\n",
+    );
+    write_file(
+        &content_dir.join("pages/about.md"),
+        "---\ntitle: About\n---\n\nRead the [setup guide](../docs/setup.md#install).\n",
+    );
+    write_file(
+        &content_dir.join("docs/faq.md"),
+        "---\ntitle: Site FAQ\n---\n\nFrom the site.\n",
+    );
+    write_file(
+        &handbook_dir.join("setup.md"),
+        "---\ntitle: Setup\n---\n\n## Install\n\nSee the [FAQ](faq.md).\n\n![Diagram](img/flow.svg)\n",
+    );
+    write_file(
+        &handbook_dir.join("setup.it.md"),
+        "---\ntitle: Installazione\n---\n\n## Install\n\nCiao.\n",
+    );
+    write_file(
+        &handbook_dir.join("faq.md"),
+        "---\ntitle: Handbook FAQ\n---\n\nFrom the handbook.\n",
+    );
+    write_file(&handbook_dir.join("img/flow.svg"), "<svg/>");
+    write_file(&handbook_dir.join(".krikignore"), "notes/\n");
+    write_file(
+        &handbook_dir.join("notes/todo.md"),
+        "---\ntitle: Todo\n---\n",
+    );
+    tmp
+}
+
+#[test]
+fn mounts_are_resolved_and_validated() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = site();
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+
+    let config = SiteConfig::load_from_path(&content_dir)?;
+    let handbook_dir = fs::canonicalize(tmp_dir.join("handbook"))?;
+    assert_eq!(config.mounts.len(), 1);
+    assert_eq!(config.mounts[0].dir, handbook_dir);
+    assert_eq!(config.mounts[0].target_path(), Path::new("docs"));
+    assert_eq!(
+        config.site_path(&content_dir, &handbook_dir.join("img/flow.svg")),
+        Some(PathBuf::from("docs/img/flow.svg"))
+    );
+    assert!(config.is_ignored(&handbook_dir.join("notes/todo.md")));
+
+    for mount in [
+        "source = \"../missing\"\ntarget = \"docs\"",
+        "source = \"../handbook\"\ntarget = \"../docs\"",
+        "source = \"../handbook\"\ntarget = \"/\"",
+    ] {
+        write_file(
+            &content_dir.join("site.toml"),
+            &format!("[[mounts]]\n{mount}\n"),
+        );
+        assert!(SiteConfig::load_from_path(&content_dir).is_err(), "{mount}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn mounted_files_are_built_under_their_target() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = site();
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+    let output_dir = tmp_dir.join("_site");
+
+    let generator = SiteGenerator::new(&content_dir, &output_dir, None::<&PathBuf>)?;
+    generator.generate_site()?;
+
+    let setup = fs::read_to_string(output_dir.join("docs/setup.html"))?;
+    assert!(setup.contains("href=\"faq.html\""));
+    assert!(setup.contains("src=\"img/flow.svg\""));
+    assert!(setup.contains("setup.it.html"), "translations are detected");
+    assert!(output_dir.join("docs/setup.it.html").exists());
+    assert!(output_dir.join("docs/img/flow.svg").exists());
+    assert!(!output_dir.join("docs/notes").exists());
+    assert!(!output_dir.join("docs/.krikignore").exists());
+
+    // Content files take precedence over mounted files at the same path
+    let faq = fs::read_to_string(output_dir.join("docs/faq.html"))?;
+    assert!(faq.contains("From the site."));
+
+    let about = fs::read_to_string(output_dir.join("pages/about.html"))?;
+    assert!(about.contains("href=\"../docs/setup.html#install\""));
+
+    // Documents remember the directory they were read from, even when a content
+    // file lives under the mount's target
+    let mut scanner = SiteGenerator::new(&content_dir, &output_dir, None::<&PathBuf>)?;
+    scanner.scan_files()?;
+    let root_of = |file_path: &str| {
+        let doc = scanner
+            .documents
+            .iter()
+            .find(|doc| doc.file_path == file_path)
+            .unwrap();
+        scanner
+            .site_config
+            .content_root(&scanner.source_dir, doc)
+            .file()
+    };
+    let handbook_dir = fs::canonicalize(tmp_dir.join("handbook"))?;
+    assert_eq!(root_of("docs/setup.md"), handbook_dir.join("setup.md"));
+    assert_eq!(root_of("docs/faq.md"), content_dir.join("docs/faq.md"));
+    assert_eq!(
+        root_of("pages/about.md"),
+        content_dir.join("pages/about.md")
+    );
+
+    let report = translation_status(&content_dir)?;
+    assert!(report.missing.iter().any(|m| m.source == "docs/faq.md"));
+    assert!(report.missing.iter().all(|m| m.source != "docs/setup.md"));
+
+    Ok(())
+}
+
+#[test]
+fn mounted_changes_rebuild_incrementally() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = site();
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+    let handbook_dir = fs::canonicalize(tmp_dir.join("handbook"))?;
+    let output_dir = tmp_dir.join("_site");
+
+    let mut generator = SiteGenerator::new(&content_dir, &output_dir, None::<&PathBuf>)?;
+    generator.scan_files()?;
+    generator.generate_site()?;
+
+    write_file(
+        &handbook_dir.join("guides/deploy.md"),
+        "---\ntitle: Deploy\n---\n\nShip it.\n",
+    );
+    generator.generate_incremental_for_path(handbook_dir.join("guides/deploy.md"), false)?;
+    let page = fs::read_to_string(output_dir.join("docs/guides/deploy.html"))?;
+    assert!(page.contains("Ship it."));
+
+    write_file(&handbook_dir.join("img/logo.svg"), "<svg/>");
+    generator.generate_incremental_for_path(handbook_dir.join("img/logo.svg"), false)?;
+    assert!(output_dir.join("docs/img/logo.svg").exists());
+
+    fs::remove_file(handbook_dir.join("guides/deploy.md"))?;
+    generator.generate_incremental_for_path(handbook_dir.join("guides/deploy.md"), true)?;
+    assert!(!output_dir.join("docs/guides/deploy.html").exists());
+
+    // Ignored files in the mount are skipped
+    write_file(
+        &handbook_dir.join("notes/later.md"),
+        "---\ntitle: Later\n---\n",
+    );
+    generator.generate_incremental_for_path(handbook_dir.join("notes/later.md"), false)?;
+    assert!(!output_dir.join("docs/notes/later.html").exists());
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+
+use krik::generator::SiteGenerator;
+use tempfile::TempDir;
+
+use common::write_file;
+
+#[test]
+fn page_links_use_current_language_with_default_fallback() -> Result<(), Box<dyn std::error::Error>>
+{
+    let tmp = TempDir::new()?;
+    let tmp_dir = tmp.path();
+
+    let content_dir = tmp_dir.join("content");
+    let output_dir = tmp_dir.join("_site");
+
+    write_file(&content_dir.join("site.toml"), "title = \"Test\"\n");
+    write_file(
+        &content_dir.join("pages/about.md"),
+        "---\ntitle: About\n---\n\nAbout us.\n",
+    );
+    write_file(
+        &content_dir.join("pages/about.it.md"),
+        "---\ntitle: Chi siamo\n---\n\nChi siamo.\n",
+    );
+    write_file(
+        &content_dir.join("pages/contact.md"),
+        "---\ntitle: Contact\n---\n\nWrite to us.\n",
+    );
+    write_file(
+        &content_dir.join("pages/solo.it.md"),
+        "---\ntitle: Solo italiano\n---\n\nSolo.\n",
+    );
+
+    let generator = SiteGenerator::new(&content_dir, &output_dir, None::<&PathBuf>)?;
+    generator.generate_site()?;
+
+    let it_html = fs::read_to_string(output_dir.join("pages/about.it.html"))?;
+    assert!(it_html.contains("<a href=\"about.it.html\">Chi siamo</a>"));
+    assert!(it_html.contains("<a href=\"contact.html\" class=\"not-translated\">Contact</a>"));
+    assert!(it_html.contains(">Solo italiano</a>"));
+    assert!(!it_html.contains("<a href=\"about.html\">About</a>"));
+
+    let en_html = fs::read_to_string(output_dir.join("pages/about.html"))?;
+    assert!(en_html.contains("<a href=\"about.html\">About</a>"));
+    assert!(en_html.contains("<a href=\"contact.html\">Contact</a>"));
+    assert!(
+        !en_html.contains(">Solo italiano</a>"),
+        "pages without a default-language version stay out of other languages"
+    );
+
+    Ok(())
+}
@@ -26,6 +26,10 @@ fn create_test_document() -> Document {
         language: "en".to_string(),
         base_name: "test".to_string(),
         toc: Some("<ul><li><a href=\"#section\">Section</a></li></ul>".to_string()),
+        footnotes: Vec::new(),
+        links: Vec::new(),
+        resources: Vec::new(),
+        mount_dir: None,
     }
 }
 
@@ -41,7 +45,7 @@ fn test_create_base_context() {
     );
     assert_eq!(context.get("language").unwrap().as_str().unwrap(), "en");
     assert_eq!(context.get("base_name").unwrap().as_str().unwrap(), "test");
-    assert_eq!(context.get("pdf").unwrap().as_bool().unwrap(), true);
+    assert!(context.get("pdf").unwrap().as_bool().unwrap());
 
     // Test content
     assert!(context.get("content").is_some());
@@ -79,6 +83,10 @@ fn test_create_base_context_minimal() {
         language: "en".to_string(),
         base_name: "simple".to_string(),
         toc: None,
+        footnotes: Vec::new(),
+        links: Vec::new(),
+        resources: Vec::new(),
+        mount_dir: None,
     };
 
     let context = create_base_context(&document);
@@ -186,6 +194,10 @@ fn test_write_output_file_creates_directories() {
         language: "en".to_string(),
         base_name: "test".to_string(),
         toc: None,
+        footnotes: Vec::new(),
+        links: Vec::new(),
+        resources: Vec::new(),
+        mount_dir: None,
     };
 
     let rendered_content = "<html>test</html>";
@@ -1,26 +1,29 @@
 use krik::generator::robots::generate_robots;
 use krik::site::SiteConfig;
 use std::fs;
-use std::path::Path;
 use krik::robots::generate_robots_content;
+use tempfile::TempDir;
 
 #[test]
 fn robots_includes_sitemap_and_defaults() {
-    let mut cfg = SiteConfig::default();
-    cfg.base_url = Some("https://example.com".into());
-    let out = std::env::temp_dir().join(format!("krik_test_robots_{}", std::process::id()));
-    let _ = fs::remove_dir_all(&out);
-    fs::create_dir_all(&out).unwrap();
-    generate_robots(&cfg, Path::new(&out)).unwrap();
+    let cfg = SiteConfig {
+        base_url: Some("https://example.com".into()),
+        ..Default::default()
+    };
+    let tmp = TempDir::new().unwrap();
+    let out = tmp.path();
+    generate_robots(&cfg, out).unwrap();
     let txt = fs::read_to_string(out.join("robots.txt")).unwrap();
     assert!(txt.contains("Sitemap: https://example.com/sitemap.xml"));
 }
 
 #[test]
 fn test_generate_robots_content_with_base_url() {
-    let mut site_config = SiteConfig::default();
-    site_config.title = Some("Test Site".to_string());
-    site_config.base_url = Some("https://example.com".to_string());
+    let site_config = SiteConfig {
+        title: Some("Test Site".to_string()),
+        base_url: Some("https://example.com".to_string()),
+        ..Default::default()
+    };
 
     let content = generate_robots_content(&site_config);
 
@@ -0,0 +1,141 @@
+mod common;
+
+use std::fs;
+
+use krik::error::{KrikError, ThemeErrorKind};
+use krik::generator::SiteGenerator;
+use tempfile::TempDir;
+
+use common::write_file;
+
+fn site() -> TempDir {
+    let tmp = TempDir::new().unwrap();
+    let tmp_dir = tmp.path();
+    let theme_dir = tmp_dir.join("theme");
+    write_file(&tmp_dir.join("content/site.toml"), "title = \"Sassy\"\n");
+    write_file(
+        &tmp_dir.join("content/posts/hello.md"),
+        "---\ntitle: Hello\n---\n\nHi.\n",
+    );
+    write_file(
+        &theme_dir.join("assets/css/_colors.scss"),
+        "$accent: red;\n",
+    );
+    write_file(
+        &theme_dir.join("assets/css/main.scss"),
+        "@use 'colors';\n\na {\n  color: colors.$accent;\n}\n",
+    );
+    write_file(
+        &theme_dir.join("assets/css/print.sass"),
+        "body\n  margin: 0\n",
+    );
+    write_file(
+        &theme_dir.join("templates/post.html"),
+        "<link rel=\"stylesheet\" href=\"{{ assets_path }}/{{ asset_url(path=\"css/main.css\") }}\">",
+    );
+    write_file(&theme_dir.join("templates/index.html"), "index");
+    tmp
+}
+
+#[test]
+fn theme_stylesheets_are_compiled_to_css() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = site();
+    let tmp_dir = tmp.path();
+    let output_dir = tmp_dir.join("_site");
+
+    let generator = SiteGenerator::new(
+        &tmp_dir.join("content"),
+        &output_dir,
+        Some(&tmp_dir.join("theme")),
+    )?;
+    generator.generate_site()?;
+
+    let main = fs::read_to_string(output_dir.join("assets/css/main.css"))?;
+    assert_eq!(main, "a {\n  color: red;\n}\n");
+    assert_eq!(
+        fs::read_to_string(output_dir.join("assets/css/print.css"))?,
+        "body {\n  margin: 0;\n}\n"
+    );
+    assert!(!output_dir.join("assets/css/main.scss").exists());
+    assert!(!output_dir.join("assets/css/_colors.scss").exists());
+    assert!(!output_dir.join("assets/css/_colors.css").exists());
+
+    let html = fs::read_to_string(output_dir.join("posts/hello.html"))?;
+    let entry = generator.theme.assets.get("css/main.css").unwrap();
+    assert!(html.contains(&format!("href=\"../assets/{}\"", entry.path)));
+
+    Ok(())
+}
+
+#[test]
+fn changed_partials_recompile_dependent_stylesheets() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = site();
+    let tmp_dir = tmp.path();
+    let theme_dir = tmp_dir.join("theme");
+    let output_dir = tmp_dir.join("_site");
+
+    let mut generator =
+        SiteGenerator::new(&tmp_dir.join("content"), &output_dir, Some(&theme_dir))?;
+    generator.set_dev(true)?;
+    generator.generate_site()?;
+
+    // Stylesheets that do not load the partial are left alone
+    write_file(
+        &theme_dir.join("assets/css/print.sass"),
+        "body\n  margin: 1px\n",
+    );
+    write_file(
+        &theme_dir.join("assets/css/_colors.scss"),
+        "$accent: blue;\n",
+    );
+    let partial = fs::canonicalize(theme_dir.join("assets/css/_colors.scss"))?;
+    generator.generate_incremental_for_path(&partial, false)?;
+
+    assert_eq!(
+        fs::read_to_string(output_dir.join("assets/css/main.css"))?,
+        "a {\n  color: blue;\n}\n"
+    );
+    assert_eq!(
+        fs::read_to_string(output_dir.join("assets/css/print.css"))?,
+        "body {\n  margin: 0;\n}\n"
+    );
+    let html = fs::read_to_string(output_dir.join("posts/hello.html"))?;
+    let entry = generator.theme.assets.get("css/main.css").unwrap();
+    assert!(html.contains(&entry.path));
+
+    Ok(())
+}
+
+#[test]
+fn compile_errors_report_file_and_line() {
+    let tmp = site();
+    let tmp_dir = tmp.path();
+    let theme_dir = tmp_dir.join("theme");
+    write_file(
+        &theme_dir.join("assets/css/_colors.scss"),
+        "$accent: red;\n\n.broken {\n  color: $missing;\n}\n",
+    );
+
+    let err = SiteGenerator::new(
+        &tmp_dir.join("content"),
+        &tmp_dir.join("_site"),
+        Some(&theme_dir),
+    )
+    .expect_err("expected the stylesheet to fail to compile");
+    match &err {
+        KrikError::Theme(theme_error) => match &theme_error.kind {
+            ThemeErrorKind::StyleCompileError {
+                file, line, column, ..
+            } => {
+                assert!(file.ends_with("_colors.scss"), "{}", file.display());
+                assert_eq!(*line, Some(4));
+                assert_eq!(*column, Some(10));
+            }
+            other => panic!("unexpected theme error: {other:?}"),
+        },
+        other => panic!("unexpected error: {other}"),
+    }
+    assert!(err
+        .to_string()
+        .contains("_colors.scss at line 4, column 10"));
+}
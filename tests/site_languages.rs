@@ -0,0 +1,92 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+
+use krik::generator::SiteGenerator;
+use krik::site::SiteConfig;
+use tempfile::TempDir;
+
+use common::write_file;
+
+const SITE_TOML: &str = r#"
+title = "My Blog"
+description = "Notes and articles"
+base_url = "https://example.com"
+
+[[menu]]
+title = "About"
+url = "/pages/about.html"
+
+[languages.it]
+title = "Il Mio Blog"
+base_url = "https://example.it"
+
+[[languages.it.menu]]
+title = "Chi siamo"
+url = "/pages/about.it.html"
+"#;
+
+#[test]
+fn language_overrides_fall_back_to_site_values() {
+    let config: SiteConfig = toml::from_str(SITE_TOML).unwrap();
+
+    let it = config.for_language("it");
+    assert_eq!(it.get_site_title(), "Il Mio Blog");
+    assert_eq!(it.description.as_deref(), Some("Notes and articles"));
+    assert_eq!(it.base_url.as_deref(), Some("https://example.it"));
+    assert_eq!(it.menu.unwrap()[0].title, "Chi siamo");
+
+    let fr = config.for_language("fr");
+    assert_eq!(fr.get_site_title(), "My Blog");
+    assert_eq!(config.feed_languages(), vec!["it".to_string()]);
+}
+
+#[test]
+fn per_language_context_and_feeds() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let tmp_dir = tmp.path();
+
+    let content_dir = tmp_dir.join("content");
+    let output_dir = tmp_dir.join("_site");
+
+    write_file(&content_dir.join("site.toml"), SITE_TOML);
+    write_file(
+        &content_dir.join("pages/about.md"),
+        "---\ntitle: About\n---\n\nAbout us.\n",
+    );
+    write_file(
+        &content_dir.join("pages/about.it.md"),
+        "---\ntitle: Chi siamo\n---\n\nChi siamo.\n",
+    );
+    write_file(
+        &content_dir.join("posts/hello.md"),
+        "---\ntitle: Hello\ndate: 2024-01-01T00:00:00Z\n---\n\nHello.\n",
+    );
+    write_file(
+        &content_dir.join("posts/hello.it.md"),
+        "---\ntitle: Ciao\ndate: 2024-01-01T00:00:00Z\n---\n\nCiao.\n",
+    );
+
+    let generator = SiteGenerator::new(&content_dir, &output_dir, None::<&PathBuf>)?;
+    generator.generate_site()?;
+
+    let it_html = fs::read_to_string(output_dir.join("posts/hello.it.html"))?;
+    assert!(it_html.contains("Il Mio Blog"));
+    assert!(it_html.contains("Chi siamo</a>"));
+    assert!(it_html.contains("feed.it.xml"));
+
+    let index_html = fs::read_to_string(output_dir.join("index.html"))?;
+    assert!(index_html.contains("Notes and articles"));
+
+    let feed = fs::read_to_string(output_dir.join("feed.xml"))?;
+    assert!(feed.contains("https://example.com/posts/hello.html"));
+    assert!(!feed.contains("hello.it.html"));
+
+    let it_feed = fs::read_to_string(output_dir.join("feed.it.xml"))?;
+    assert!(it_feed.contains("<title>Il Mio Blog</title>"));
+    assert!(it_feed.contains("https://example.it/posts/hello.it.html"));
+    assert!(it_feed.contains("https://example.it/feed.it.xml"));
+
+    Ok(())
+}
@@ -2,6 +2,7 @@ use krik::generator::sitemap::generate_sitemap;
 use krik::parser::{Document, FrontMatter};
 use krik::site::SiteConfig;
 use std::collections::HashMap;
+use tempfile::TempDir;
 
 fn base_doc(file_path: &str, layout: Option<&str>, draft: Option<bool>, lang: &str) -> Document {
     let mut extra = HashMap::new();
@@ -23,6 +24,10 @@ fn base_doc(file_path: &str, layout: Option<&str>, draft: Option<bool>, lang: &s
         language: lang.into(),
         base_name: "base".into(),
         toc: None,
+        footnotes: Vec::new(),
+        links: Vec::new(),
+        resources: Vec::new(),
+        mount_dir: None,
     }
 }
 
@@ -30,14 +35,15 @@ fn base_doc(file_path: &str, layout: Option<&str>, draft: Option<bool>, lang: &s
 fn sitemap_inclusion_and_is_post() {
     let page = base_doc("pages/about.md", Some("page"), None, "en");
     // Write a minimal sitemap and ensure no crash (smoke test)
-    let out = std::env::temp_dir().join(format!("krik_test_sitemap_more_{}", std::process::id()));
-    let _ = std::fs::remove_dir_all(&out);
-    std::fs::create_dir_all(&out).unwrap();
-    let mut cfg = SiteConfig::default();
-    cfg.base_url = Some("https://example.com".into());
+    let tmp = TempDir::new().unwrap();
+    let out = tmp.path();
+    let cfg = SiteConfig {
+        base_url: Some("https://example.com".into()),
+        ..Default::default()
+    };
     let post = base_doc("posts/test.md", Some("post"), None, "en");
     let draft = base_doc("posts/draft.md", Some("post"), Some(true), "en");
     let docs = vec![page, post, draft];
-    generate_sitemap(&docs, &cfg, &out).unwrap();
+    generate_sitemap(&docs, &cfg, out).unwrap();
     assert!(out.join("sitemap.xml").exists());
 }
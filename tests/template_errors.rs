@@ -1,11 +1,9 @@
-use std::fs;
-use std::path::Path;
-
 use krik::error::KrikError;
 use krik::generator::templates;
 use krik::parser::{Document, FrontMatter};
 use krik::site::SiteConfig;
 use krik::theme::{Theme, ThemeConfig};
+use tempfile::TempDir;
 
 fn make_doc(path: &str) -> Document {
     Document {
@@ -23,6 +21,10 @@ fn make_doc(path: &str) -> Document {
         language: "en".into(),
         base_name: "x".into(),
         toc: None,
+        footnotes: Vec::new(),
+        links: Vec::new(),
+        resources: Vec::new(),
+        mount_dir: None,
     }
 }
 
@@ -34,11 +36,13 @@ fn build_empty_theme() -> Theme {
         author: None,
         description: None,
         templates: Default::default(),
+        bundles: Vec::new(),
     };
     Theme {
         config,
         templates: tera::Tera::default(),
         theme_path: std::path::PathBuf::from("<test>"),
+        assets: Default::default(),
     }
 }
 
@@ -46,12 +50,11 @@ fn build_empty_theme() -> Theme {
 fn render_page_maps_template_error() {
     let theme = build_empty_theme();
     let site = SiteConfig::default();
-    let out = std::env::temp_dir().join(format!("krik_test_out_{}", std::process::id()));
-    let _ = fs::remove_dir_all(&out);
-    fs::create_dir_all(&out).unwrap();
+    let tmp = TempDir::new().unwrap();
+    let out = tmp.path();
 
     let doc = make_doc("posts/missing.md");
-    let err = templates::generate_page(&doc, &[doc.clone()], &theme, &site, Path::new(&out))
+    let err = templates::generate_page(&doc, std::slice::from_ref(&doc), &theme, &site, out)
         .expect_err("expected template render to fail");
 
     match err {
@@ -67,12 +70,11 @@ fn render_page_maps_template_error() {
 fn render_index_maps_template_error() {
     let theme = build_empty_theme();
     let site = SiteConfig::default();
-    let out = std::env::temp_dir().join(format!("krik_test_out_idx_{}", std::process::id()));
-    let _ = fs::remove_dir_all(&out);
-    fs::create_dir_all(&out).unwrap();
+    let tmp = TempDir::new().unwrap();
+    let out = tmp.path();
 
     let docs: Vec<Document> = vec![];
-    let err = templates::generate_index(&docs, &theme, &site, Path::new(&out))
+    let err = templates::generate_index(&docs, &theme, &site, out)
         .expect_err("expected index render to fail");
 
     match err {
@@ -0,0 +1,81 @@
+use krik::generator::ast_parser::{generate_toc_with_options, parse_markdown_ast};
+use krik::generator::markdown::process_markdown_content;
+use krik::parser::parse_markdown_with_frontmatter;
+use krik::site::{SiteConfig, TocConfig};
+
+const DOC: &str = "# Title\n\n## Intro\n\n### Details\n\n#### Deep\n\n## Usage\n\n### Flags\n";
+
+#[test]
+fn toc_is_a_nested_tree() {
+    let headings = parse_markdown_ast(DOC).headings;
+    let toc = generate_toc_with_options(&headings, Some("Title"), &TocConfig::default());
+    assert_eq!(
+        toc,
+        "<ul class=\"toc\">\n\
+         <li><a href=\"#intro\">Intro</a>\n<ul>\n\
+         <li><a href=\"#details\">Details</a>\n<ul>\n\
+         <li><a href=\"#deep\">Deep</a></li>\n</ul>\n</li>\n</ul>\n</li>\n\
+         <li><a href=\"#usage\">Usage</a>\n<ul>\n\
+         <li><a href=\"#flags\">Flags</a></li>\n</ul>\n</li>\n</ul>"
+    );
+}
+
+#[test]
+fn toc_levels_and_numbering() {
+    let headings = parse_markdown_ast(DOC).headings;
+    let config = TocConfig {
+        min: 2,
+        max: 3,
+        numbered: true,
+    };
+    let toc = generate_toc_with_options(&headings, None, &config);
+    assert!(!toc.contains("Title"));
+    assert!(!toc.contains("Deep"));
+    assert!(toc.contains("<span class=\"toc-number\">1.1</span> Details"));
+    assert!(toc.contains("<span class=\"toc-number\">2</span> Usage"));
+    assert!(toc.contains("<span class=\"toc-number\">2.1</span> Flags"));
+
+    let site: SiteConfig = toml::from_str("[toc]\nmax = 3\nnumbered = true\n").unwrap();
+    assert_eq!(site.toc.min, 1);
+    assert_eq!(site.toc.max, 3);
+}
+
+#[test]
+fn skipped_levels_nest_and_number_correctly() {
+    let headings = parse_markdown_ast("## Alpha\n\n#### Deep\n\n### Mid\n\n## Beta\n").headings;
+    let config = TocConfig {
+        numbered: true,
+        ..TocConfig::default()
+    };
+    let toc = generate_toc_with_options(&headings, None, &config);
+    assert_eq!(
+        toc,
+        "<ul class=\"toc\">\n\
+         <li><a href=\"#alpha\"><span class=\"toc-number\">1</span> Alpha</a>\n<ul>\n\
+         <li><a href=\"#deep\"><span class=\"toc-number\">1.1</span> Deep</a></li>\n\
+         <li><a href=\"#mid\"><span class=\"toc-number\">1.2</span> Mid</a></li>\n</ul>\n</li>\n\
+         <li><a href=\"#beta\"><span class=\"toc-number\">2</span> Beta</a></li>\n</ul>"
+    );
+}
+
+#[test]
+fn toc_entries_are_escaped_like_headings() {
+    let result = parse_markdown_ast("## `Vec<T>` & more\n\n## Quotes {#a\"b}\n");
+    let toc = generate_toc_with_options(&result.headings, None, &TocConfig::default());
+    assert!(toc.contains(">Vec&lt;T&gt; &amp; more</a>"), "{toc}");
+    assert!(toc.contains("<a href=\"#a%22b\">Quotes</a>"), "{toc}");
+    assert!(!toc.contains("<T>"));
+}
+
+#[test]
+fn front_matter_overrides_and_inline_marker() {
+    let source = "---\ntitle: Title\ntoc: true\ntoc_min: 2\ntoc_max: 2\ntoc_numbered: true\n---\n\
+                  [[toc]]\n\n## One\n\n### Skipped\n\n## Two\n";
+    let (front_matter, markdown) = parse_markdown_with_frontmatter(source).unwrap();
+    let (html, toc) = process_markdown_content(&markdown, &front_matter);
+
+    assert!(toc.contains("<span class=\"toc-number\">2</span> Two"));
+    assert!(!toc.contains("Skipped"));
+    assert!(html.starts_with("<nav class=\"toc-inline\">\n<ul class=\"toc\">"));
+    assert!(!html.contains("[[toc]]"));
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+
+use krik::generator::templates::paths::get_translation_key;
+use krik::generator::SiteGenerator;
+use krik::parser::{Document, FrontMatter};
+use std::collections::HashMap;
+use tempfile::TempDir;
+
+use common::write_file;
+
+fn doc_with_key(file_path: &str, key: Option<&str>) -> Document {
+    let mut extra = HashMap::new();
+    if let Some(k) = key {
+        extra.insert(
+            "translation_key".to_string(),
+            serde_yaml::Value::String(k.to_string()),
+        );
+    }
+    Document {
+        front_matter: FrontMatter {
+            title: None,
+            date: None,
+            tags: None,
+            lang: None,
+            draft: None,
+            pdf: None,
+            extra,
+        },
+        content: String::new(),
+        file_path: file_path.to_string(),
+        language: "en".to_string(),
+        base_name: String::new(),
+        toc: None,
+        footnotes: Vec::new(),
+        links: Vec::new(),
+        resources: Vec::new(),
+        mount_dir: None,
+    }
+}
+
+#[test]
+fn translation_key_prefers_front_matter_over_base_path() {
+    assert_eq!(
+        get_translation_key(&doc_with_key("pages/about.it.md", None)),
+        "pages/about"
+    );
+    assert_eq!(
+        get_translation_key(&doc_with_key("pages/chi-siamo.it.md", Some("about"))),
+        "about"
+    );
+    assert_eq!(
+        get_translation_key(&doc_with_key("pages/about.md", Some("  "))),
+        "pages/about"
+    );
+}
+
+#[test]
+fn translations_with_different_filenames_are_linked() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let tmp_dir = tmp.path();
+
+    let content_dir = tmp_dir.join("content");
+    let output_dir = tmp_dir.join("_site");
+
+    write_file(
+        &content_dir.join("site.toml"),
+        "title = \"Test\"\nbase_url = \"https://example.com\"\n",
+    );
+    write_file(
+        &content_dir.join("pages/about.md"),
+        "---\ntitle: About\ntranslation_key: about\n---\n\nAbout us.\n",
+    );
+    write_file(
+        &content_dir.join("pages/chi-siamo.it.md"),
+        "---\ntitle: Chi siamo\ntranslation_key: about\n---\n\nChi siamo.\n",
+    );
+    write_file(
+        &content_dir.join("posts/hello.md"),
+        "---\ntitle: Hello\ntranslation_key: greeting\n---\n\nHello.\n",
+    );
+    write_file(
+        &content_dir.join("posts/ciao.it.md"),
+        "---\ntitle: Ciao\ntranslation_key: greeting\n---\n\nCiao.\n",
+    );
+
+    let generator = SiteGenerator::new(&content_dir, &output_dir, None::<&PathBuf>)?;
+    generator.generate_site()?;
+
+    let about_html = fs::read_to_string(output_dir.join("pages/about.html"))?;
+    assert!(
+        about_html.contains("chi-siamo.it.html"),
+        "English page should link to its Italian translation"
+    );
+
+    let index_html = fs::read_to_string(output_dir.join("index.html"))?;
+    assert!(index_html.contains("href=\"posts/hello.html\""));
+    assert!(
+        !index_html.contains("href=\"posts/ciao.it.html\""),
+        "index should list one entry per translation key"
+    );
+
+    let sitemap = fs::read_to_string(output_dir.join("sitemap.xml"))?;
+    assert!(
+        sitemap.contains("hreflang=\"it\" href=\"https://example.com/pages/chi-siamo.it.html\"")
+    );
+    assert_eq!(
+        sitemap
+            .matches("<loc>https://example.com/pages/chi-siamo.it.html</loc>")
+            .count(),
+        0,
+        "translations should share a single sitemap entry"
+    );
+
+    Ok(())
+}
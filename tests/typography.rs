@@ -0,0 +1,57 @@
+use krik::generator::ast_parser::{parse_markdown_ast_with_options, MarkdownOptions};
+use krik::generator::typography::localize_markdown;
+
+fn render(markdown: &str, language: &str) -> String {
+    let options = MarkdownOptions {
+        language: language.to_string(),
+        ..Default::default()
+    };
+    parse_markdown_ast_with_options(markdown, &options).html_content
+}
+
+#[test]
+fn quotes_follow_document_language() {
+    let md = "He said \"hello\" -- then left.\n\n```\nlet s = \"code\";\n```\n";
+
+    let en = render(md, "en");
+    assert!(en.contains("\u{201c}hello\u{201d}"));
+
+    let it = render(md, "it");
+    assert!(it.contains("\u{ab}hello\u{bb}"));
+    assert!(
+        it.contains("let s = \"code\";"),
+        "code blocks stay untouched"
+    );
+
+    let de = render(md, "de");
+    assert!(de.contains("\u{201e}hello\u{201c}"));
+}
+
+#[test]
+fn french_spacing_and_dashes() {
+    let fr = render("Il dit \"oui\" : vraiment ? Oui --- bien sûr !", "fr");
+    assert!(fr.contains("\u{ab}\u{202f}oui\u{202f}\u{bb}"));
+    assert!(fr.contains("\u{a0}: vraiment\u{202f}?"));
+    assert!(fr.contains("Oui\u{a0}\u{2013} bien"));
+    assert!(fr.contains("sûr\u{202f}!"));
+
+    let unspaced = render(
+        "Bonjour! Quoi?! Note: à 12:30; voir https://example.com?",
+        "fr",
+    );
+    assert!(unspaced.contains("Bonjour\u{202f}!"));
+    assert!(unspaced.contains("Quoi\u{202f}?!"));
+    assert!(unspaced.contains("Note\u{a0}:"));
+    assert!(unspaced.contains("12:30\u{202f};"));
+    assert!(unspaced.contains("https://example.com\u{202f}?"));
+}
+
+#[test]
+fn localize_markdown_keeps_code_and_escapes() {
+    let md = "Ciao \"mondo\" `\"code\"`\n\n    \"indented\"\n\nNo \\\"escape\\\" here\n";
+    let out = localize_markdown(md, "it");
+    assert!(out.starts_with("Ciao \u{ab}mondo\u{bb} `\"code\"`"));
+    assert!(out.contains("    \"indented\""));
+    assert!(out.contains("No \\\"escape\\\" here"));
+    assert_eq!(localize_markdown(md, "en"), md);
+}
@@ -0,0 +1,155 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+
+use krik::generator::ast_parser::parse_markdown_ast;
+use krik::generator::SiteGenerator;
+use krik::lint::lint_content;
+use tempfile::TempDir;
+
+use common::write_file;
+
+fn garden() -> TempDir {
+    let tmp = TempDir::new().unwrap();
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+    write_file(&content_dir.join("site.toml"), "title = \"Garden\"\n");
+    write_file(
+        &content_dir.join("posts/guide.md"),
+        "---\ntitle: Getting Started\n---\n\n## Setup\n\nSee [[notes|my notes]].\n",
+    );
+    write_file(
+        &content_dir.join("pages/notes.md"),
+        "---\ntitle: Notes\n---\n\nRead [[getting started]], [[guide#setup]] and [[Nowhere]].\n\n\
+         `[[not a link]]`\n\n[[toc]]\n",
+    );
+    tmp
+}
+
+#[test]
+fn wiki_links_render_as_placeholders_outside_code() {
+    let html = parse_markdown_ast("[[Page Title]] and [[slug|a label]]\n\n```\n[[code]]\n```\n")
+        .html_content;
+    assert!(html.contains("<a class=\"wikilink\" data-wikilink=\"Page Title\">Page Title</a>"));
+    assert!(html.contains("<a class=\"wikilink\" data-wikilink=\"slug\">a label</a>"));
+    assert!(html.contains("[[code]]"));
+}
+
+#[test]
+fn wiki_links_resolve_with_backlinks_and_graph() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = garden();
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+    let output_dir = tmp_dir.join("_site");
+
+    let generator = SiteGenerator::new(&content_dir, &output_dir, None::<&PathBuf>)?;
+    generator.generate_site()?;
+
+    let notes = fs::read_to_string(output_dir.join("pages/notes.html"))?;
+    assert!(
+        notes.contains("<a class=\"wikilink\" href=\"../posts/guide.html\">getting started</a>")
+    );
+    assert!(
+        notes.contains("<a class=\"wikilink\" href=\"../posts/guide.html#setup\">guide#setup</a>")
+    );
+    assert!(notes
+        .contains("<a class=\"wikilink wikilink-missing\" data-wikilink=\"Nowhere\">Nowhere</a>"));
+    assert!(notes.contains("<code>[[not a link]]</code>"));
+    assert!(
+        !notes.contains("[[toc]]"),
+        "the TOC marker is not a wiki-link"
+    );
+
+    let guide = fs::read_to_string(output_dir.join("posts/guide.html"))?;
+    assert!(guide.contains("<a class=\"wikilink\" href=\"../pages/notes.html\">my notes</a>"));
+
+    let graph: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(output_dir.join("graph.json"))?)?;
+    assert_eq!(graph["nodes"].as_array().unwrap().len(), 2);
+    assert_eq!(
+        graph["links"],
+        serde_json::json!([
+            {"source": "pages/notes.md", "target": "posts/guide.md"},
+            {"source": "posts/guide.md", "target": "pages/notes.md"}
+        ])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn incremental_changes_update_linking_pages() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = garden();
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+    let output_dir = tmp_dir.join("_site");
+
+    let mut generator = SiteGenerator::new(&content_dir, &output_dir, None::<&PathBuf>)?;
+    generator.scan_files()?;
+    generator.generate_site()?;
+
+    // A new page resolves the missing link on the unchanged page linking to it
+    write_file(
+        &content_dir.join("posts/nowhere.md"),
+        "---\ntitle: Nowhere\n---\n\nFound.\n",
+    );
+    generator.generate_incremental_for_path(content_dir.join("posts/nowhere.md"), false)?;
+    let notes = fs::read_to_string(output_dir.join("pages/notes.html"))?;
+    assert!(notes.contains("<a class=\"wikilink\" href=\"../posts/nowhere.html\">Nowhere</a>"));
+
+    // Removing a page turns the links to it back into missing links
+    fs::remove_file(content_dir.join("posts/guide.md"))?;
+    generator.generate_incremental_for_path(content_dir.join("posts/guide.md"), true)?;
+    let notes = fs::read_to_string(output_dir.join("pages/notes.html"))?;
+    assert!(!notes.contains("guide.html"), "{notes}");
+    assert!(notes.contains("data-wikilink=\"getting started\""));
+
+    Ok(())
+}
+
+#[test]
+fn pages_receive_backlinks_in_context() -> Result<(), Box<dyn std::error::Error>> {
+    use krik::generator::links::resolve_internal_links;
+    use krik::generator::templates::render_page::build_page_context;
+    use krik::site::SiteConfig;
+
+    let tmp = garden();
+    let tmp_dir = tmp.path();
+    let content_dir = tmp_dir.join("content");
+
+    let mut documents = Vec::new();
+    krik::generator::markdown::scan_files(&content_dir, &SiteConfig::default(), &mut documents)?;
+    let (documents, unresolved) = resolve_internal_links(documents);
+    assert_eq!(unresolved.len(), 1);
+    assert_eq!(unresolved[0].href, "[[Nowhere]]");
+
+    let guide = documents
+        .iter()
+        .find(|doc| doc.file_path == "posts/guide.md")
+        .unwrap();
+    let context = build_page_context(guide, &documents, &SiteConfig::default());
+    let backlinks = context.get("backlinks").unwrap();
+    assert_eq!(
+        backlinks,
+        &serde_json::json!([{"title": "Notes", "url": "../pages/notes.html"}])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn lint_warns_about_unresolved_wiki_links() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = garden();
+    let tmp_dir = tmp.path();
+    let report = lint_content(&tmp_dir.join("content"))?;
+    let wiki_warnings: Vec<_> = report
+        .warnings
+        .iter()
+        .filter(|w| w.contains("wiki-link"))
+        .collect();
+    assert_eq!(wiki_warnings.len(), 1, "{wiki_warnings:?}");
+    assert!(wiki_warnings[0].contains("'[[Nowhere]]' does not match any document title or name"));
+
+    Ok(())
+}
@@ -80,33 +80,18 @@ document.addEventListener('DOMContentLoaded', function() {
   onScroll();
 });
 
-// Footnote return links
+// Smooth scrolling between footnote references and their notes
 document.addEventListener('DOMContentLoaded', function() {
-  const refs = document.querySelectorAll('.footnote-reference a');
-  refs.forEach(ref => {
-    const href = ref.getAttribute('href');
-    if (href && href.startsWith('#')) {
-      ref.id = 'fnref' + href.substring(1);
-    }
-  });
-  const defs = document.querySelectorAll('.footnote-definition');
-  defs.forEach(def => {
-    if (!def.id) return;
-    const refId = 'fnref' + def.id;
-    const a = document.createElement('a');
-    a.href = '#' + refId;
-    a.className = 'footnote-return';
-    a.textContent = ' ↩';
-    a.title = 'Return to text';
-    a.addEventListener('click', (e) => {
-      e.preventDefault();
-      const target = document.getElementById(refId);
+  document.querySelectorAll('.footnote-reference a, .footnote-return').forEach(link => {
+    link.addEventListener('click', (e) => {
+      const href = link.getAttribute('href');
+      const target = document.getElementById(href.substring(1));
       if (target) {
-        const sup = target.closest('.footnote-reference');
-        (sup || target).scrollIntoView({ behavior: 'smooth', block: 'center' });
+        e.preventDefault();
+        target.scrollIntoView({ behavior: 'smooth', block: 'center' });
+        history.replaceState(null, '', href);
       }
     });
-    def.appendChild(a);
   });
 });
 
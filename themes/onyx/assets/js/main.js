@@ -40,52 +40,18 @@ window.matchMedia('(prefers-color-scheme: dark)').addEventListener('change', (e)
 // Initialize theme on page load
 initTheme();
 
-// Add return links to footnotes  
+// Smooth scrolling between footnote references and their notes
 document.addEventListener('DOMContentLoaded', function() {
-    // First, add IDs to footnote references for easier navigation
-    var footnoteRefs = document.querySelectorAll('.footnote-reference a');
-    for (var i = 0; i < footnoteRefs.length; i++) {
-        var ref = footnoteRefs[i];
-        var href = ref.getAttribute('href');
-        if (href && href.startsWith('#')) {
-            var footnoteNum = href.substring(1);
-            ref.id = 'fnref' + footnoteNum;
-        }
-    }
-    
-    // Then add return links to footnote definitions
-    var definitions = document.querySelectorAll('.footnote-definition');
-    for (var i = 0; i < definitions.length; i++) {
-        var def = definitions[i];
-        if (def.id) {
-            var footnoteId = def.id;
-            var refId = 'fnref' + footnoteId;
-            
-            var link = document.createElement('a');
-            link.href = '#' + refId;
-            link.className = 'footnote-return';
-            link.innerHTML = ' ↩';
-            link.title = 'Return to text';
-            
-            // Smooth scroll back to the footnote reference
-            link.onclick = (function(refId) {
-                return function(e) {
-                    e.preventDefault();
-                    var target = document.getElementById(refId);
-                    if (target) {
-                        // Find the parent sup element to scroll to the entire footnote reference
-                        var supElement = target.closest('.footnote-reference');
-                        if (supElement) {
-                            supElement.scrollIntoView({ behavior: 'smooth', block: 'center' });
-                        } else {
-                            target.scrollIntoView({ behavior: 'smooth', block: 'center' });
-                        }
-                    }
-                };
-            })(refId);
-            
-            def.appendChild(link);
-        }
+    var links = document.querySelectorAll('.footnote-reference a, .footnote-return');
+    for (var i = 0; i < links.length; i++) {
+        links[i].addEventListener('click', function(e) {
+            var target = document.getElementById(this.getAttribute('href').substring(1));
+            if (target) {
+                e.preventDefault();
+                target.scrollIntoView({ behavior: 'smooth', block: 'center' });
+                history.replaceState(null, '', this.getAttribute('href'));
+            }
+        });
     }
 });
 
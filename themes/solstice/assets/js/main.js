@@ -74,47 +74,18 @@ document.addEventListener('DOMContentLoaded', function() {
   onScroll();
 });
 
-// Footnote return links
+// Smooth scrolling between footnote references and their notes
 document.addEventListener('DOMContentLoaded', function() {
-  // First, add IDs to footnote references for easier navigation
-  const footnoteRefs = document.querySelectorAll('.footnote-reference a');
-  footnoteRefs.forEach(ref => {
-    const href = ref.getAttribute('href');
-    if (href && href.startsWith('#')) {
-      const footnoteNum = href.substring(1);
-      ref.id = 'fnref' + footnoteNum;
-    }
-  });
-  
-  // Then add return links to footnote definitions
-  const defs = document.querySelectorAll('.footnote-definition');
-  defs.forEach(def => {
-    if (!def.id) return;
-    const footnoteId = def.id;
-    const refId = 'fnref' + footnoteId;
-    
-    const a = document.createElement('a');
-    a.href = '#' + refId;
-    a.className = 'footnote-return';
-    a.textContent = ' ↩';
-    a.title = 'Return to text';
-    
-    // Smooth scroll back to the footnote reference
-    a.addEventListener('click', (e) => {
-      e.preventDefault();
-      const target = document.getElementById(refId);
+  document.querySelectorAll('.footnote-reference a, .footnote-return').forEach(link => {
+    link.addEventListener('click', (e) => {
+      const href = link.getAttribute('href');
+      const target = document.getElementById(href.substring(1));
       if (target) {
-        // Find the parent sup element to scroll to the entire footnote reference
-        const supElement = target.closest('.footnote-reference');
-        if (supElement) {
-          supElement.scrollIntoView({ behavior: 'smooth', block: 'center' });
-        } else {
-          target.scrollIntoView({ behavior: 'smooth', block: 'center' });
-        }
+        e.preventDefault();
+        target.scrollIntoView({ behavior: 'smooth', block: 'center' });
+        history.replaceState(null, '', href);
       }
     });
-    
-    def.appendChild(a);
   });
 });
 